@@ -1,17 +1,21 @@
 use alloc::{boxed::Box, format, string::String, vec};
 use ckb_std::{ckb_constants::Source, ckb_types::prelude::*, high_level};
+use core::convert::TryInto;
 use das_core::{
     assert,
-    constants::{das_wallet_lock, DasLockType, OracleCellType, ScriptType, TypeScript},
+    constants::{
+        das_wallet_lock, DasLockType, OfferCollectionType, OracleCellType, ScriptType, TypeScript,
+        ACCOUNT_STATUS_LEASED,
+    },
     data_parser, debug,
-    eip712::{to_semantic_address, verify_eip712_hashes},
+    eip712::{to_semantic_address, to_semantic_capacity, to_semantic_records_diff, verify_eip712_hashes},
     error::Error,
     util, verifiers, warn,
     witness_parser::WitnessesParser,
 };
 use das_map::{map::Map, util as map_util};
 use das_types::{
-    constants::{AccountStatus, LockRole, SubAccountEnableStatus},
+    constants::{AccountStatus, DataType, LockRole, SubAccountEnableStatus},
     mixer::*,
     packed::*,
 };
@@ -103,7 +107,7 @@ pub fn main() -> Result<(), Error> {
                         &input_cell_witness_reader,
                         &output_cell_witness_reader,
                         Some("owner"),
-                        vec![],
+                        vec!["records_updated_at"],
                         vec!["last_transfer_account_at", "records"],
                     )?;
                     verifiers::account_cell::verify_account_witness_record_empty(
@@ -111,6 +115,13 @@ pub fn main() -> Result<(), Error> {
                         output_account_cells[0],
                         Source::Output,
                     )?;
+                    verifiers::account_cell::verify_records_updated_at(
+                        input_account_cells[0],
+                        output_account_cells[0],
+                        input_cell_witness_reader.records(),
+                        output_cell_witness_reader.records(),
+                        timestamp,
+                    )?;
                 }
                 b"edit_manager" => {
                     verify_eip712_hashes(&parser, edit_manager_to_semantic)?;
@@ -169,12 +180,15 @@ pub fn main() -> Result<(), Error> {
                         &output_cell_witness_reader,
                         timestamp,
                     )?;
-                    verifiers::account_cell::verify_account_cell_status(
-                        &input_cell_witness_reader,
-                        AccountStatus::Normal,
-                        input_account_cells[0],
-                        Source::Input,
-                    )?;
+
+                    // The lessee is the manager of a leased AccountCell, so they can edit records during the lease.
+                    let input_status = u8::from(input_cell_witness_reader.status());
+                    assert!(
+                        input_status == AccountStatus::Normal as u8 || input_status == ACCOUNT_STATUS_LEASED,
+                        Error::AccountCellStatusLocked,
+                        "inputs[{}] The AccountCell.witness.status should be NORMAL or LEASED.",
+                        input_account_cells[0]
+                    );
                     verifiers::account_cell::verify_account_expiration(
                         config_account,
                         input_account_cells[0],
@@ -186,14 +200,174 @@ pub fn main() -> Result<(), Error> {
                         &input_cell_witness_reader,
                         &output_cell_witness_reader,
                         None,
-                        vec![],
+                        vec!["records_updated_at"],
                         vec!["records", "last_edit_records_at"],
                     )?;
                     verifiers::account_cell::verify_records_keys(&parser, output_cell_witness_reader.records())?;
+
+                    let input_records = input_cell_witness_reader.records();
+                    let output_records = output_cell_witness_reader.records();
+                    let changed_records = verifiers::account_cell::get_changed_records(input_records, output_records);
+                    verifiers::account_cell::verify_records_ttl(&parser, output_records, &changed_records)?;
+                    verifiers::account_cell::verify_records_updated_at(
+                        input_account_cells[0],
+                        output_account_cells[0],
+                        input_records,
+                        output_records,
+                        timestamp,
+                    )?;
                 }
                 _ => unreachable!(),
             }
         }
+        b"lease_account" | b"end_lease" => {
+            verifiers::account_cell::verify_unlock_role(action, &parser.params)?;
+
+            let timestamp = util::load_oracle_data(OracleCellType::Time)?;
+
+            parser.parse_cell()?;
+            let config_account = parser.configs.account()?;
+
+            let (input_account_cells, output_account_cells) = util::load_self_cells_in_inputs_and_outputs()?;
+            assert!(
+                input_account_cells.len() == 1 && output_account_cells.len() == 1,
+                Error::InvalidTransactionStructure,
+                "There should be only one AccountCell in inputs and outputs."
+            );
+
+            let input_cell_witness = util::parse_account_cell_witness(&parser, input_account_cells[0], Source::Input)?;
+            let input_cell_witness_reader = input_cell_witness.as_reader();
+            let output_cell_witness =
+                util::parse_account_cell_witness(&parser, output_account_cells[0], Source::Output)?;
+            let output_cell_witness_reader = output_cell_witness.as_reader();
+
+            verify_action_throttle(
+                action,
+                config_account,
+                &input_cell_witness_reader,
+                &output_cell_witness_reader,
+                timestamp,
+            )?;
+            verifiers::account_cell::verify_account_cell_consistent_with_exception(
+                input_account_cells[0],
+                output_account_cells[0],
+                &input_cell_witness_reader,
+                &output_cell_witness_reader,
+                Some("manager"),
+                vec!["lease_end"],
+                vec!["status", "last_edit_manager_at"],
+            )?;
+
+            let input_data = high_level::load_cell_data(input_account_cells[0], Source::Input)?;
+            let output_data = high_level::load_cell_data(output_account_cells[0], Source::Output)?;
+            let input_status = u8::from(input_cell_witness_reader.status());
+            let output_status = u8::from(output_cell_witness_reader.status());
+
+            if action == b"lease_account" {
+                verify_eip712_hashes(&parser, lease_account_to_semantic)?;
+
+                verify_transaction_fee_spent_correctly(
+                    action,
+                    config_account,
+                    input_account_cells[0],
+                    output_account_cells[0],
+                )?;
+                verifiers::account_cell::verify_account_expiration(config_account, input_account_cells[0], timestamp)?;
+
+                debug!("Verify if the AccountCell is leased properly.");
+
+                assert!(
+                    input_status == AccountStatus::Normal as u8 && output_status == ACCOUNT_STATUS_LEASED,
+                    Error::AccountCellStatusLocked,
+                    "The AccountCell.witness.status should be changed from NORMAL to LEASED.(input: {}, output: {})",
+                    input_status,
+                    output_status
+                );
+
+                let expired_at = data_parser::account_cell::get_expired_at(&input_data);
+                let lease_end = match data_parser::account_cell::get_lease_end(&output_data) {
+                    Some(lease_end) => lease_end,
+                    None => {
+                        warn!("The AccountCell.data in outputs should have the lease_end in its tail.");
+                        return Err(Error::AccountCellLeaseError);
+                    }
+                };
+                assert!(
+                    lease_end > timestamp,
+                    Error::AccountCellLeaseError,
+                    "The lease should end in the future.(current: {}, lease_end: {})",
+                    timestamp,
+                    lease_end
+                );
+                assert!(
+                    lease_end <= expired_at,
+                    Error::AccountCellLeaseError,
+                    "The lease should end before the AccountCell expired.(lease_end: {}, expired_at: {})",
+                    lease_end,
+                    expired_at
+                );
+
+                debug!("Verify if the owner is paid by the lessee.");
+
+                let config_main = parser.configs.main()?;
+                let price = get_lease_price(&parser.params)?;
+                let owner_lock = util::derive_owner_lock_from_cell(input_account_cells[0], Source::Input)?;
+                let input_balance_cells = util::find_balance_cells(config_main, owner_lock.as_reader(), Source::Input)?;
+                let output_balance_cells =
+                    util::find_balance_cells(config_main, owner_lock.as_reader(), Source::Output)?;
+                let input_capacity = util::load_cells_capacity(&input_balance_cells, Source::Input)?;
+                let output_capacity = util::load_cells_capacity(&output_balance_cells, Source::Output)?;
+
+                assert!(
+                    output_capacity >= input_capacity + price,
+                    Error::AccountCellLeaseError,
+                    "The owner should be paid {} shannon for the lease.(inputs: {}, outputs: {})",
+                    price,
+                    input_capacity,
+                    output_capacity
+                );
+            } else {
+                // CAREFUL! This action is intentionally ignoring EIP712 verification, because it can be pushed by anyone.
+
+                debug!("Verify if the manager of the AccountCell is restored to the owner.");
+
+                assert!(
+                    input_status == ACCOUNT_STATUS_LEASED && output_status == AccountStatus::Normal as u8,
+                    Error::AccountCellStatusLocked,
+                    "The AccountCell.witness.status should be changed from LEASED to NORMAL.(input: {}, output: {})",
+                    input_status,
+                    output_status
+                );
+
+                let lease_end = data_parser::account_cell::get_lease_end(&input_data).unwrap_or(0);
+                assert!(
+                    timestamp >= lease_end,
+                    Error::AccountCellLeaseError,
+                    "The lease of the AccountCell is still not ended.(current: {}, lease_end: {})",
+                    timestamp,
+                    lease_end
+                );
+                assert!(
+                    data_parser::account_cell::get_lease_end(&output_data).is_none(),
+                    Error::AccountCellLeaseError,
+                    "The lease_end in the tail of AccountCell.data should be cleared when the lease ends."
+                );
+
+                let output_lock = high_level::load_cell_lock(output_account_cells[0], Source::Output)?;
+                let (owner_type, owner_args, manager_type, manager_args) =
+                    data_parser::das_lock_args::get_owner_and_manager(output_lock.as_reader().args().raw_data())?;
+                assert!(
+                    owner_type == manager_type && owner_args == manager_args,
+                    Error::AccountCellLeaseError,
+                    "The manager of the AccountCell should be restored to the owner when the lease ends."
+                );
+
+                verifiers::account_cell::verify_account_capacity_not_decrease(
+                    input_account_cells[0],
+                    output_account_cells[0],
+                )?;
+            }
+        }
         b"renew_account" => {
             parser.parse_cell()?;
 
@@ -353,6 +527,14 @@ pub fn main() -> Result<(), Error> {
                 Error::InvalidTransactionStructure,
             )?;
         }
+        b"register_account" => {
+            util::require_type_script(
+                &parser,
+                TypeScript::PreAccountCellType,
+                Source::Input,
+                Error::InvalidTransactionStructure,
+            )?;
+        }
         b"recycle_expired_account_by_keeper" => {
             return Err(Error::InvalidTransactionStructure);
         }
@@ -372,7 +554,15 @@ pub fn main() -> Result<(), Error> {
                 Error::InvalidTransactionStructure,
             )?;
         }
-        b"accept_offer" => {
+        b"counter_offer" => {
+            util::require_type_script(
+                &parser,
+                TypeScript::OfferCellType,
+                Source::Output,
+                Error::InvalidTransactionStructure,
+            )?;
+        }
+        b"cancel_offer" | b"accept_offer" | b"accept_counter_offer" => {
             util::require_type_script(
                 &parser,
                 TypeScript::OfferCellType,
@@ -386,64 +576,80 @@ pub fn main() -> Result<(), Error> {
             let config_main = parser.configs.main()?;
             let timestamp = util::load_oracle_data(OracleCellType::Time)?;
 
+            // There may be more than one AccountCell when they are sold as a bundle, they should be recovered together.
             let (input_cells, output_cells) = util::load_self_cells_in_inputs_and_outputs()?;
-            assert!(
-                input_cells.len() == 1 && output_cells.len() == 1,
-                Error::InvalidTransactionStructure,
-                "There should be one AccountCell in outputs and one in inputs."
-            );
-            assert!(
-                input_cells[0] == 0 && output_cells[0] == 0,
-                Error::InvalidTransactionStructure,
-                "The AccountCells should only appear at inputs[0] and outputs[0]."
-            );
-
-            let input_cell_witness = util::parse_account_cell_witness(&parser, input_cells[0], Source::Input)?;
-            let input_cell_witness_reader = input_cell_witness.as_reader();
-            let output_cell_witness = util::parse_account_cell_witness(&parser, output_cells[0], Source::Output)?;
-            let output_cell_witness_reader = output_cell_witness.as_reader();
-
-            debug!("Verify if the AccountCell is consistent in inputs and outputs.");
-
-            verifiers::account_cell::verify_account_capacity_not_decrease(input_cells[0], output_cells[0])?;
-            verifiers::account_cell::verify_account_cell_consistent_with_exception(
-                input_cells[0],
-                output_cells[0],
-                &input_cell_witness_reader,
-                &output_cell_witness_reader,
-                None,
-                vec![],
-                vec!["status"],
-            )?;
+            verifiers::account_sale_cell::verify_account_cells_of_bundle(&input_cells, &output_cells)?;
+
+            // Every AccountCell of a bundle should be in the same status, and all of them should be expired, otherwise the
+            // keepers could recover an account which is still on sale by its owner.
+            let input_status = {
+                let input_cell_witness = util::parse_account_cell_witness(&parser, input_cells[0], Source::Input)?;
+                u8::from(input_cell_witness.as_reader().status())
+            };
+            for (input_index, output_index) in input_cells.iter().zip(output_cells.iter()) {
+                let input_cell_witness = util::parse_account_cell_witness(&parser, *input_index, Source::Input)?;
+                let input_cell_witness_reader = input_cell_witness.as_reader();
+                let output_cell_witness = util::parse_account_cell_witness(&parser, *output_index, Source::Output)?;
+                let output_cell_witness_reader = output_cell_witness.as_reader();
+
+                debug!("Verify if the AccountCell is consistent in inputs and outputs.");
+
+                verifiers::account_cell::verify_account_capacity_not_decrease(*input_index, *output_index)?;
+                verifiers::account_cell::verify_account_cell_consistent_with_exception(
+                    *input_index,
+                    *output_index,
+                    &input_cell_witness_reader,
+                    &output_cell_witness_reader,
+                    None,
+                    vec![],
+                    vec!["status"],
+                )?;
 
-            debug!("Verify if the AccountCell status updated correctly.");
+                debug!("Verify if the AccountCell status updated correctly.");
 
-            let input_status = u8::from(input_cell_witness_reader.status());
-            assert!(
-                input_status != AccountStatus::Normal as u8,
-                Error::InvalidTransactionStructure,
-                "The AccountCell in inputs should not be in NORMAL status."
-            );
+                let status = u8::from(input_cell_witness_reader.status());
+                assert!(
+                    status == input_status,
+                    Error::InvalidTransactionStructure,
+                    "inputs[{}] The AccountCells in inputs should be in the same status.(expected: {}, current: {})",
+                    input_index,
+                    input_status,
+                    status
+                );
+                assert!(
+                    status != AccountStatus::Normal as u8,
+                    Error::InvalidTransactionStructure,
+                    "inputs[{}] The AccountCell in inputs should not be in NORMAL status.",
+                    input_index
+                );
+                assert!(
+                    status != ACCOUNT_STATUS_LEASED,
+                    Error::AccountCellLeaseError,
+                    "inputs[{}] The leased AccountCell can only be recovered by the end_lease action.",
+                    input_index
+                );
 
-            let output_status = u8::from(output_cell_witness_reader.status());
-            assert!(
-                output_status == AccountStatus::Normal as u8,
-                Error::InvalidTransactionStructure,
-                "The AccountCell in outputs should be in NORMAL status."
-            );
+                let output_status = u8::from(output_cell_witness_reader.status());
+                assert!(
+                    output_status == AccountStatus::Normal as u8,
+                    Error::InvalidTransactionStructure,
+                    "outputs[{}] The AccountCell in outputs should be in NORMAL status.",
+                    output_index
+                );
 
-            debug!("Verify if the AccountCell is actually expired.");
+                let input_cell_data = high_level::load_cell_data(*input_index, Source::Input)?;
+                let expired_at = data_parser::account_cell::get_expired_at(&input_cell_data);
 
-            let input_cell_data = high_level::load_cell_data(input_cells[0], Source::Input)?;
-            let expired_at = data_parser::account_cell::get_expired_at(&input_cell_data);
-            let account = data_parser::account_cell::get_account(&input_cell_data);
+                debug!("Verify if the AccountCell is actually expired.");
 
-            // It is a convention that the deal can be canceled immediately when expiring.
-            assert!(
-                timestamp > expired_at,
-                Error::AccountCellIsNotExpired,
-                "The AccountCell is still not expired."
-            );
+                // It is a convention that the deal can be canceled immediately when expiring.
+                assert!(
+                    timestamp > expired_at,
+                    Error::AccountCellIsNotExpired,
+                    "inputs[{}] The AccountCell is still not expired.",
+                    input_index
+                );
+            }
 
             let capacity_should_recycle;
             let cell;
@@ -452,24 +658,38 @@ pub fn main() -> Result<(), Error> {
                 let (input_sale_cells, output_sale_cells) =
                     util::find_cells_by_type_id_in_inputs_and_outputs(ScriptType::Type, type_id)?;
 
-                verifiers::common::verify_removed_cell_in_correct_position(
-                    "AccountSaleCell",
-                    &input_sale_cells,
-                    &output_sale_cells,
-                    Some(1),
-                )?;
+                if input_sale_cells.is_empty() {
+                    // The AccountCell may be locked by a counter offer instead of an AccountSaleCell, then the counter offer
+                    // is recycled and refunded to the owner.
+                    cell = verify_counter_offer_of_expired_account_cell(&parser, &input_cells)?;
+                } else {
+                    verifiers::common::verify_removed_cell_in_correct_position(
+                        "AccountSaleCell",
+                        &input_sale_cells,
+                        &output_sale_cells,
+                        Some(input_cells.len()),
+                    )?;
+
+                    let cell_witness =
+                        util::parse_account_sale_cell_witness(&parser, input_sale_cells[0], Source::Input)?;
+                    let cell_witness_reader = cell_witness.as_reader();
 
-                let cell_witness = util::parse_account_sale_cell_witness(&parser, input_sale_cells[0], Source::Input)?;
-                let cell_witness_reader = cell_witness.as_reader();
+                    verifiers::account_sale_cell::verify_sale_cell_account_and_id(
+                        &input_cells,
+                        input_sale_cells[0],
+                        Source::Input,
+                        &cell_witness_reader,
+                    )?;
 
+                    cell = input_sale_cells[0];
+                }
+            } else {
                 assert!(
-                    account == cell_witness_reader.account().raw_data(),
-                    Error::AccountSaleCellAccountIdInvalid,
-                    "The account in AccountCell and AccountSaleCell should be the same."
+                    input_cells.len() == 1,
+                    Error::InvalidTransactionStructure,
+                    "There should be one AccountCell in outputs and one in inputs."
                 );
 
-                cell = input_sale_cells[0];
-            } else {
                 // TODO Verify the account in AccountCell and AccountAuctionCell is the same.
                 cell = 0;
             }
@@ -488,7 +708,7 @@ pub fn main() -> Result<(), Error> {
                 "BalanceCell",
                 &input_balance_cells,
                 &outputs_balance_cells,
-                Some(1),
+                Some(output_cells.len()),
             )?;
 
             let expected_lock = util::derive_owner_lock_from_cell(input_cells[0], Source::Input)?;
@@ -512,15 +732,14 @@ pub fn main() -> Result<(), Error> {
             );
         }
         b"enable_sub_account" => {
-            // CAREFUL! This action is intentionally ignoring EIP712 verification.
-            // verify_eip712_hashes(&parser, transfer_account_to_semantic)?;
-
             verifiers::account_cell::verify_unlock_role(action, &parser.params)?;
 
             let timestamp = util::load_oracle_data(OracleCellType::Time)?;
 
             parser.parse_cell()?;
 
+            verify_eip712_hashes(&parser, enable_sub_account_to_semantic)?;
+
             let config_main = parser.configs.main()?;
             let config_account = parser.configs.account()?;
             let config_sub_account = parser.configs.sub_account()?;
@@ -696,6 +915,63 @@ pub fn main() -> Result<(), Error> {
     Ok(())
 }
 
+fn verify_counter_offer_of_expired_account_cell(
+    parser: &WitnessesParser,
+    input_cells: &[usize],
+) -> Result<usize, Error> {
+    assert!(
+        input_cells.len() == 1,
+        Error::InvalidTransactionStructure,
+        "The counter offer can only lock one AccountCell."
+    );
+
+    let type_id = parser.configs.main()?.type_id_table().offer_cell();
+    let (input_offer_cells, output_offer_cells) =
+        util::find_cells_by_type_id_in_inputs_and_outputs(ScriptType::Type, type_id)?;
+
+    verifiers::common::verify_removed_cell_in_correct_position(
+        "OfferCell",
+        &input_offer_cells,
+        &output_offer_cells,
+        Some(input_cells.len()),
+    )?;
+
+    let counter_offer_cell = input_offer_cells[0];
+    let counter_offer_data = high_level::load_cell_data(counter_offer_cell, Source::Input)?;
+    assert!(
+        data_parser::offer_cell::get_collection_type(&counter_offer_data) == Some(OfferCollectionType::Counter as u8),
+        Error::InvalidTransactionStructure,
+        "Inputs[{}] The cell should be a counter offer.",
+        counter_offer_cell
+    );
+
+    let expected_lock = util::derive_owner_lock_from_cell(input_cells[0], Source::Input)?;
+    let current_lock = high_level::load_cell_lock(counter_offer_cell, Source::Input)?;
+    assert!(
+        util::is_entity_eq(&expected_lock, &current_lock),
+        Error::InvalidTransactionStructure,
+        "Inputs[{}] The counter offer should be made by the owner of the AccountCell.",
+        counter_offer_cell
+    );
+
+    let (_, _, mol_bytes) = parser.verify_and_get(DataType::OfferCellData, counter_offer_cell, Source::Input)?;
+    let counter_offer_witness = OfferCellData::from_slice(mol_bytes.as_reader().raw_data()).map_err(|_| {
+        warn!("Decoding OfferCellData failed");
+        Error::WitnessEntityDecodingError
+    })?;
+
+    let account_cell_data = high_level::load_cell_data(input_cells[0], Source::Input)?;
+    assert!(
+        counter_offer_witness.as_reader().account().raw_data()
+            == data_parser::account_cell::get_account(&account_cell_data),
+        Error::InvalidTransactionStructure,
+        "Inputs[{}] The counter offer should be made for the account of the AccountCell.",
+        counter_offer_cell
+    );
+
+    Ok(counter_offer_cell)
+}
+
 fn transfer_account_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
     let type_id_table_reader = parser.configs.main()?.type_id_table();
     let (input_cells, output_cells) =
@@ -718,7 +994,36 @@ fn transfer_account_to_semantic(parser: &WitnessesParser) -> Result<String, Erro
 
 fn edit_manager_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
     let type_id_table_reader = parser.configs.main()?.type_id_table();
-    let (input_cells, _output_cells) =
+    let (input_cells, output_cells) =
+        util::find_cells_by_type_id_in_inputs_and_outputs(ScriptType::Type, type_id_table_reader.account_cell())?;
+
+    // Parse account from the data of the AccountCell in inputs.
+    let data_in_bytes = util::load_cell_data(input_cells[0], Source::Input)?;
+    let account_in_bytes = data_parser::account_cell::get_account(&data_in_bytes);
+    let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| Error::EIP712SerializationError)?;
+
+    // Parse the new manager from the AccountCell's lock script in outputs.
+    let to_lock = high_level::load_cell_lock(output_cells[0], Source::Output)?;
+    let to_address = to_semantic_address(parser, to_lock.as_reader().into(), LockRole::Manager)?;
+
+    Ok(format!("EDIT MANAGER OF ACCOUNT {} TO {}", account, to_address))
+}
+
+fn get_lease_price(params: &[Bytes]) -> Result<u64, Error> {
+    let bytes = params
+        .get(0)
+        .and_then(|param| param.raw_data().as_ref().try_into().ok())
+        .ok_or_else(|| {
+            warn!("The price of the lease should be the first param in 8 bytes.");
+            Error::ParamsDecodingError
+        })?;
+
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn lease_account_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
+    let type_id_table_reader = parser.configs.main()?.type_id_table();
+    let (input_cells, output_cells) =
         util::find_cells_by_type_id_in_inputs_and_outputs(ScriptType::Type, type_id_table_reader.account_cell())?;
 
     // Parse account from the data of the AccountCell in inputs.
@@ -726,11 +1031,45 @@ fn edit_manager_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
     let account_in_bytes = data_parser::account_cell::get_account(&data_in_bytes);
     let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| Error::EIP712SerializationError)?;
 
-    // TODO Improve semantic message of this transaction.
-    Ok(format!("EDIT MANAGER OF ACCOUNT {}", account))
+    // Parse the lessee from the manager of the AccountCell's lock script in outputs.
+    let to_lock = high_level::load_cell_lock(output_cells[0], Source::Output)?;
+    let to_address = to_semantic_address(parser, to_lock.as_reader().into(), LockRole::Manager)?;
+
+    let output_data = util::load_cell_data(output_cells[0], Source::Output)?;
+    let lease_end = data_parser::account_cell::get_lease_end(&output_data).ok_or(Error::AccountCellLeaseError)?;
+
+    let price = get_lease_price(&parser.params)?;
+
+    Ok(format!(
+        "LEASE THE MANAGER OF ACCOUNT {} TO {} UNTIL {} FOR {}",
+        account,
+        to_address,
+        lease_end,
+        to_semantic_capacity(price)
+    ))
 }
 
 fn edit_records_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
+    let type_id_table_reader = parser.configs.main()?.type_id_table();
+    let (input_cells, output_cells) =
+        util::find_cells_by_type_id_in_inputs_and_outputs(ScriptType::Type, type_id_table_reader.account_cell())?;
+
+    // Parse account from the data of the AccountCell in inputs.
+    let data_in_bytes = util::load_cell_data(input_cells[0], Source::Input)?;
+    let account_in_bytes = data_parser::account_cell::get_account(&data_in_bytes);
+    let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| Error::EIP712SerializationError)?;
+
+    let input_witness = util::parse_account_cell_witness(parser, input_cells[0], Source::Input)?;
+    let output_witness = util::parse_account_cell_witness(parser, output_cells[0], Source::Output)?;
+    let changes = to_semantic_records_diff(
+        input_witness.as_reader().records(),
+        output_witness.as_reader().records(),
+    )?;
+
+    Ok(format!("EDIT RECORDS OF ACCOUNT {}: {}", account, changes))
+}
+
+fn enable_sub_account_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
     let type_id_table_reader = parser.configs.main()?.type_id_table();
     let (input_cells, _output_cells) =
         util::find_cells_by_type_id_in_inputs_and_outputs(ScriptType::Type, type_id_table_reader.account_cell())?;
@@ -740,8 +1079,7 @@ fn edit_records_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
     let account_in_bytes = data_parser::account_cell::get_account(&data_in_bytes);
     let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| Error::EIP712SerializationError)?;
 
-    // TODO Improve semantic message of this transaction.
-    Ok(format!("EDIT RECORDS OF ACCOUNT {}", account))
+    Ok(format!("ENABLE SUB-ACCOUNT FOR ACCOUNT {}", account))
 }
 
 fn verify_transaction_fee_spent_correctly(
@@ -766,7 +1104,7 @@ fn verify_transaction_fee_spent_correctly(
 
     let fee = match action {
         b"transfer_account" => u64::from(config.transfer_account_fee()),
-        b"edit_manager" => u64::from(config.edit_manager_fee()),
+        b"edit_manager" | b"lease_account" => u64::from(config.edit_manager_fee()),
         b"edit_records" => u64::from(config.edit_records_fee()),
         _ => return Err(Error::ActionNotSupported),
     };
@@ -830,13 +1168,26 @@ fn verify_action_throttle<'a>(
                 last_transfer_account_at,
                 "last_transfer_account_at"
             ),
-            b"edit_manager" => assert_action_throttle!(
+            // The lease is a variant of edit_manager, so it shares the throttle of edit_manager.
+            b"edit_manager" | b"lease_account" => assert_action_throttle!(
                 input_witness_reader,
                 output_witness_reader,
                 edit_manager_throttle,
                 last_edit_manager_at,
                 "last_edit_manager_at"
             ),
+            b"end_lease" => {
+                // The end_lease action is not limited by the throttle, because it can only be pushed once the lease is
+                // ended.
+                let current = u64::from(output_witness_reader.last_edit_manager_at());
+                assert!(
+                    current_timestamp == current,
+                    Error::AccountCellThrottle,
+                    "The AccountCell.last_edit_manager_at in outputs should be the same as the timestamp in the TimeCell.(expected: {}, current: {})",
+                    current_timestamp,
+                    current
+                );
+            }
             b"edit_records" => assert_action_throttle!(
                 input_witness_reader,
                 output_witness_reader,
@@ -1,4 +1,4 @@
-use alloc::{boxed::Box, format, string::String, vec};
+use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
 use ckb_std::{
     ckb_constants::Source,
     ckb_types::{packed as ckb_packed, prelude::*},
@@ -52,23 +52,32 @@ pub fn main() -> Result<(), Error> {
                 util::find_cells_by_type_id_in_inputs_and_outputs(ScriptType::Type, account_cell_type_id)?;
             let (input_sale_cells, output_sale_cells) = util::load_self_cells_in_inputs_and_outputs()?;
 
-            assert!(
-                input_account_cells.len() == 1 && output_account_cells.len() == 1,
-                Error::InvalidTransactionStructure,
-                "There should be 1 AccountCell in both inputs and outputs."
-            );
-            assert!(
-                input_account_cells[0] == 0 && output_account_cells[0] == 0,
-                Error::InvalidTransactionStructure,
-                "The AccountCells should only appear in inputs[0] and outputs[0]."
-            );
-
-            let input_account_cell_witness =
-                util::parse_account_cell_witness(&parser, input_account_cells[0], Source::Input)?;
-            let input_account_cell_witness_reader = input_account_cell_witness.as_reader();
-            let output_account_cell_witness =
-                util::parse_account_cell_witness(&parser, output_account_cells[0], Source::Output)?;
-            let output_account_cell_witness_reader = output_account_cell_witness.as_reader();
+            // All AccountCells of a bundle are placed at the beginning, then the AccountSaleCell follows them.
+            verifiers::account_sale_cell::verify_account_cells_of_bundle(&input_account_cells, &output_account_cells)?;
+            let sale_cell_index = input_account_cells.len();
+
+            let mut input_account_cell_witnesses = Vec::new();
+            let mut output_account_cell_witnesses = Vec::new();
+            for (input_index, output_index) in input_account_cells.iter().zip(output_account_cells.iter()) {
+                input_account_cell_witnesses.push(util::parse_account_cell_witness(
+                    &parser,
+                    *input_index,
+                    Source::Input,
+                )?);
+                output_account_cell_witnesses.push(util::parse_account_cell_witness(
+                    &parser,
+                    *output_index,
+                    Source::Output,
+                )?);
+            }
+            let input_account_cell_witness_readers = input_account_cell_witnesses
+                .iter()
+                .map(|witness| witness.as_reader())
+                .collect::<Vec<_>>();
+            let output_account_cell_witness_readers = output_account_cell_witnesses
+                .iter()
+                .map(|witness| witness.as_reader())
+                .collect::<Vec<_>>();
 
             match action {
                 b"start_account_sale" => {
@@ -78,7 +87,7 @@ pub fn main() -> Result<(), Error> {
                         "AccountSaleCell",
                         &input_sale_cells,
                         &output_sale_cells,
-                        Some(1),
+                        Some(sale_cell_index),
                     )?;
 
                     let sender_lock = high_level::load_cell_lock(0, Source::Input)?;
@@ -99,25 +108,33 @@ pub fn main() -> Result<(), Error> {
                         u64::from(config_secondary_market.common_fee()),
                     )?;
 
-                    debug!("Verify if the AccountCell is consistent in inputs and outputs.");
-
-                    // The AccountCell should be consistent in inputs and outputs except the status field.
-                    verify_account_cell_consistent_except_status(
-                        config_account,
-                        timestamp,
-                        input_account_cells[0],
-                        output_account_cells[0],
-                        &input_account_cell_witness_reader,
-                        &output_account_cell_witness_reader,
-                    )?;
-
-                    // If a user willing to sell owned account, the AccountCell should be in AccountStatus::Normal status.
-                    verifiers::account_cell::verify_account_cell_status_update_correctly(
-                        &input_account_cell_witness_reader,
-                        &output_account_cell_witness_reader,
-                        AccountStatus::Normal,
-                        AccountStatus::Selling,
-                    )?;
+                    debug!("Verify if all AccountCells of the bundle belong to the same owner.");
+
+                    verify_bundle_has_same_owner(&input_account_cells)?;
+
+                    debug!("Verify if the AccountCells are consistent in inputs and outputs.");
+
+                    for (i, (input_index, output_index)) in
+                        input_account_cells.iter().zip(output_account_cells.iter()).enumerate()
+                    {
+                        // The AccountCell should be consistent in inputs and outputs except the status field.
+                        verify_account_cell_consistent_except_status(
+                            config_account,
+                            timestamp,
+                            *input_index,
+                            *output_index,
+                            &input_account_cell_witness_readers[i],
+                            &output_account_cell_witness_readers[i],
+                        )?;
+
+                        // If a user willing to sell owned account, the AccountCell should be in AccountStatus::Normal status.
+                        verifiers::account_cell::verify_account_cell_status_update_correctly(
+                            &input_account_cell_witness_readers[i],
+                            &output_account_cell_witness_readers[i],
+                            AccountStatus::Normal,
+                            AccountStatus::Selling,
+                        )?;
+                    }
 
                     debug!("Verify if all fields of AccountSaleCell is properly set.");
 
@@ -126,7 +143,12 @@ pub fn main() -> Result<(), Error> {
                     let output_sale_cell_witness_reader = output_sale_cell_witness.as_reader();
 
                     verify_sale_cell_capacity(config_secondary_market, output_sale_cells[0])?;
-                    verify_sale_cell_account_and_id(input_account_cells[0], &output_sale_cell_witness_reader)?;
+                    verifiers::account_sale_cell::verify_sale_cell_account_and_id(
+                        &input_account_cells,
+                        output_sale_cells[0],
+                        Source::Output,
+                        &output_sale_cell_witness_reader,
+                    )?;
                     verify_price(config_secondary_market, &output_sale_cell_witness_reader)?;
                     verify_description(config_secondary_market, &output_sale_cell_witness_reader)?;
                     verify_buyer_inviter_profit_rate(&output_sale_cell_witness_reader)?;
@@ -139,7 +161,7 @@ pub fn main() -> Result<(), Error> {
                         "AccountSaleCell",
                         &input_sale_cells,
                         &output_sale_cells,
-                        Some(1),
+                        Some(sale_cell_index),
                     )?;
 
                     debug!("Verify if there is no redundant cells in inputs.");
@@ -160,33 +182,42 @@ pub fn main() -> Result<(), Error> {
                     )?;
 
                     debug!(
-                        "Verify if the AccountCell is consistent in inputs and outputs and its status is updated correctly."
+                        "Verify if the AccountCells are consistent in inputs and outputs and their status are updated correctly."
                     );
 
-                    verify_account_cell_consistent_except_status(
-                        config_account,
-                        timestamp,
-                        input_account_cells[0],
-                        output_account_cells[0],
-                        &input_account_cell_witness_reader,
-                        &output_account_cell_witness_reader,
-                    )?;
-
-                    // If a user want to cancel account sale, the AccountCell should be in AccountStatus::Selling status.
-                    verifiers::account_cell::verify_account_cell_status_update_correctly(
-                        &input_account_cell_witness_reader,
-                        &output_account_cell_witness_reader,
-                        AccountStatus::Selling,
-                        AccountStatus::Normal,
-                    )?;
-
-                    debug!("Verify if the AccountSaleCell has the same account ID with the AccountCell inputs.");
+                    for (i, (input_index, output_index)) in
+                        input_account_cells.iter().zip(output_account_cells.iter()).enumerate()
+                    {
+                        verify_account_cell_consistent_except_status(
+                            config_account,
+                            timestamp,
+                            *input_index,
+                            *output_index,
+                            &input_account_cell_witness_readers[i],
+                            &output_account_cell_witness_readers[i],
+                        )?;
+
+                        // If a user want to cancel account sale, the AccountCell should be in AccountStatus::Selling status.
+                        verifiers::account_cell::verify_account_cell_status_update_correctly(
+                            &input_account_cell_witness_readers[i],
+                            &output_account_cell_witness_readers[i],
+                            AccountStatus::Selling,
+                            AccountStatus::Normal,
+                        )?;
+                    }
+
+                    debug!("Verify if the AccountSaleCell has the same account IDs with the AccountCells in inputs.");
 
                     let input_sale_cell_witness =
                         util::parse_account_sale_cell_witness(&parser, input_sale_cells[0], Source::Input)?;
                     let input_sale_cell_witness_reader = input_sale_cell_witness.as_reader();
 
-                    verify_sale_cell_account_and_id(input_account_cells[0], &input_sale_cell_witness_reader)?;
+                    verifiers::account_sale_cell::verify_sale_cell_account_and_id(
+                        &input_account_cells,
+                        input_sale_cells[0],
+                        Source::Input,
+                        &input_sale_cell_witness_reader,
+                    )?;
                 }
                 b"buy_account" => {
                     verify_eip712_hashes(&parser, buy_account_to_semantic)?;
@@ -195,10 +226,10 @@ pub fn main() -> Result<(), Error> {
                         "AccountSaleCell",
                         &input_sale_cells,
                         &output_sale_cells,
-                        Some(1),
+                        Some(sale_cell_index),
                     )?;
 
-                    let buyer_lock = high_level::load_cell_lock(2, Source::Input)?;
+                    let buyer_lock = high_level::load_cell_lock(sale_cell_index + 1, Source::Input)?;
                     let buyer_lock_reader = buyer_lock.as_reader();
                     let input_balance_cells = util::find_balance_cells(config_main, buyer_lock_reader, Source::Input)?;
 
@@ -210,66 +241,79 @@ pub fn main() -> Result<(), Error> {
                         Source::Input,
                     )?;
 
-                    debug!("Verify if the AccountCell is consistent in inputs and outputs.");
-
-                    verifiers::account_cell::verify_account_expiration(
-                        config_account,
-                        input_account_cells[0],
-                        timestamp,
-                    )?;
-                    verifiers::account_cell::verify_account_capacity_not_decrease(
-                        input_account_cells[0],
-                        output_account_cells[0],
-                    )?;
-                    verifiers::account_cell::verify_account_data_consistent(
-                        input_account_cells[0],
-                        output_account_cells[0],
-                        vec![],
-                    )?;
-                    verifiers::account_cell::verify_account_witness_consistent(
-                        input_account_cells[0],
-                        output_account_cells[0],
-                        &input_account_cell_witness_reader,
-                        &output_account_cell_witness_reader,
-                        vec!["status", "records"],
-                    )?;
-
-                    // If a user willing to buy the account, the AccountCell should be in AccountStatus::Selling status.
-                    verifiers::account_cell::verify_account_cell_status_update_correctly(
-                        &input_account_cell_witness_reader,
-                        &output_account_cell_witness_reader,
-                        AccountStatus::Selling,
-                        AccountStatus::Normal,
-                    )?;
-
-                    verifiers::account_cell::verify_account_witness_record_empty(
-                        &output_account_cell_witness_reader,
-                        output_account_cells[0],
-                        Source::Output,
-                    )?;
-
-                    debug!("Verify if the AccountSaleCell is belong to the AccountCell.");
+                    debug!("Verify if the AccountCells are consistent in inputs and outputs.");
+
+                    for (i, (input_index, output_index)) in
+                        input_account_cells.iter().zip(output_account_cells.iter()).enumerate()
+                    {
+                        verifiers::account_cell::verify_account_expiration(config_account, *input_index, timestamp)?;
+                        verifiers::account_cell::verify_account_capacity_not_decrease(*input_index, *output_index)?;
+                        verifiers::account_cell::verify_account_data_consistent(
+                            *input_index,
+                            *output_index,
+                            vec!["records_updated_at"],
+                        )?;
+                        verifiers::account_cell::verify_account_witness_consistent(
+                            *input_index,
+                            *output_index,
+                            &input_account_cell_witness_readers[i],
+                            &output_account_cell_witness_readers[i],
+                            vec!["status", "records"],
+                        )?;
+
+                        // If a user willing to buy the account, the AccountCell should be in AccountStatus::Selling status.
+                        verifiers::account_cell::verify_account_cell_status_update_correctly(
+                            &input_account_cell_witness_readers[i],
+                            &output_account_cell_witness_readers[i],
+                            AccountStatus::Selling,
+                            AccountStatus::Normal,
+                        )?;
+
+                        verifiers::account_cell::verify_account_witness_record_empty(
+                            &output_account_cell_witness_readers[i],
+                            *output_index,
+                            Source::Output,
+                        )?;
+                        verifiers::account_cell::verify_records_updated_at(
+                            *input_index,
+                            *output_index,
+                            input_account_cell_witness_readers[i].records(),
+                            output_account_cell_witness_readers[i].records(),
+                            timestamp,
+                        )?;
+
+                        debug!(
+                            "outputs[{}] Verify if the AccountCell.lock is changed to new owner's lock properly.",
+                            output_index
+                        );
+
+                        let output_account_cell_lock = high_level::load_cell_lock(*output_index, Source::Output)?;
+
+                        assert!(
+                            util::is_entity_eq(&buyer_lock, &output_account_cell_lock),
+                            Error::AccountSaleCellNewOwnerError,
+                            "outputs[{}] The new owner's lock of AccountCell is mismatch with the BalanceCell in inputs.(expected: {}, current: {})",
+                            output_index,
+                            buyer_lock,
+                            output_account_cell_lock
+                        );
+                    }
+
+                    debug!("Verify if the AccountSaleCell is belong to the AccountCells.");
 
                     let input_sale_cell_witness =
                         util::parse_account_sale_cell_witness(&parser, input_sale_cells[0], Source::Input)?;
                     let input_sale_cell_witness_reader = input_sale_cell_witness.as_reader();
 
-                    verify_sale_cell_account_and_id(input_account_cells[0], &input_sale_cell_witness_reader)?;
+                    verifiers::account_sale_cell::verify_sale_cell_account_and_id(
+                        &input_account_cells,
+                        input_sale_cells[0],
+                        Source::Input,
+                        &input_sale_cell_witness_reader,
+                    )?;
                     // The cell carry refund capacity should be combined with the cell carry profit capacity, so skip checking refund here.
                     // verify_refund_correctly(config_main, config_secondary_market, input_sale_cells[0])?;
 
-                    debug!("Verify if the AccountCell.lock is changed to new owner's lock properly.");
-
-                    let output_account_cell_lock = high_level::load_cell_lock(output_account_cells[0], Source::Output)?;
-
-                    assert!(
-                        util::is_entity_eq(&buyer_lock, &output_account_cell_lock),
-                        Error::AccountSaleCellNewOwnerError,
-                        "The new owner's lock of AccountCell is mismatch with the BalanceCell in inputs.(expected: {}, current: {})",
-                        buyer_lock,
-                        output_account_cell_lock
-                    );
-
                     debug!("Verify if buyer get their change properly.");
 
                     let price = u64::from(input_sale_cell_witness_reader.price());
@@ -294,6 +338,7 @@ pub fn main() -> Result<(), Error> {
 
                     debug!("Verify if the profit is distribute correctly.");
 
+                    // All AccountCells in a bundle have the same owner, which has been verified when the sale started.
                     let seller_lock = util::derive_owner_lock_from_cell(input_account_cells[0], Source::Input)?;
                     let (inviter_lock, channel_lock) = decode_scripts_from_params(&parser.params)?;
                     let account_sale_cell_capacity =
@@ -443,23 +488,29 @@ fn decode_scripts_from_params(params: &[Bytes]) -> Result<(ckb_packed::Script, c
     Ok((inviter_lock, channel_lock))
 }
 
-fn start_account_sale_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
+fn accounts_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
     let type_id_table_reader = parser.configs.main()?.type_id_table();
     let account_cells =
         util::find_cells_by_type_id(ScriptType::Type, type_id_table_reader.account_cell(), Source::Input)?;
-    let account_sale_cells = util::find_cells_by_type_id(
-        ScriptType::Type,
-        type_id_table_reader.account_sale_cell(),
-        Source::Output,
-    )?;
 
-    // Parse account from the data of the AccountCell in inputs.
-    let data_in_bytes = util::load_cell_data(account_cells[0], Source::Input)?;
-    let account_in_bytes = data_parser::account_cell::get_account(&data_in_bytes);
-    let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| Error::EIP712SerializationError)?;
+    // Parse accounts from the data of the AccountCells in inputs, there will be more than one account if they are sold as a bundle.
+    let mut accounts = Vec::new();
+    for i in account_cells {
+        let data_in_bytes = util::load_cell_data(i, Source::Input)?;
+        let account_in_bytes = data_parser::account_cell::get_account(&data_in_bytes);
+        let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| Error::EIP712SerializationError)?;
+        accounts.push(account);
+    }
+
+    Ok(accounts.join(", "))
+}
+
+fn sale_price_to_semantic(parser: &WitnessesParser, source: Source) -> Result<String, Error> {
+    let type_id_table_reader = parser.configs.main()?.type_id_table();
+    let account_sale_cells =
+        util::find_cells_by_type_id(ScriptType::Type, type_id_table_reader.account_sale_cell(), source)?;
 
-    let (version, _, witness) =
-        parser.verify_and_get(DataType::AccountSaleCellData, account_sale_cells[0], Source::Output)?;
+    let (version, _, witness) = parser.verify_and_get(DataType::AccountSaleCellData, account_sale_cells[0], source)?;
 
     let price = if version == 1 {
         let entity = AccountSaleCellDataV1::from_slice(witness.as_reader().raw_data()).map_err(|_| {
@@ -475,83 +526,33 @@ fn start_account_sale_to_semantic(parser: &WitnessesParser) -> Result<String, Er
         to_semantic_capacity(u64::from(entity.price()))
     };
 
-    Ok(format!("SELL {} FOR {}", account, price))
+    Ok(price)
 }
 
-fn edit_account_sale_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
-    let type_id_table_reader = parser.configs.main()?.type_id_table();
-    let account_sale_cells = util::find_cells_by_type_id(
-        ScriptType::Type,
-        type_id_table_reader.account_sale_cell(),
-        Source::Output,
-    )?;
+fn start_account_sale_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
+    let accounts = accounts_to_semantic(parser)?;
+    let price = sale_price_to_semantic(parser, Source::Output)?;
 
-    let (version, _, witness) =
-        parser.verify_and_get(DataType::AccountSaleCellData, account_sale_cells[0], Source::Output)?;
+    Ok(format!("SELL {} FOR {}", accounts, price))
+}
 
-    let price = if version == 1 {
-        let entity = AccountSaleCellDataV1::from_slice(witness.as_reader().raw_data()).map_err(|_| {
-            warn!("EIP712 decoding AccountSaleCellData failed");
-            Error::WitnessEntityDecodingError
-        })?;
-        to_semantic_capacity(u64::from(entity.price()))
-    } else {
-        let entity = AccountSaleCellData::from_slice(witness.as_reader().raw_data()).map_err(|_| {
-            warn!("EIP712 decoding AccountSaleCellData failed");
-            Error::WitnessEntityDecodingError
-        })?;
-        to_semantic_capacity(u64::from(entity.price()))
-    };
+fn edit_account_sale_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
+    let price = sale_price_to_semantic(parser, Source::Output)?;
 
     Ok(format!("EDIT SALE INFO, CURRENT PRICE IS {}", price))
 }
 
 fn cancel_account_sale_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
-    let type_id_table_reader = parser.configs.main()?.type_id_table();
-    let account_cells =
-        util::find_cells_by_type_id(ScriptType::Type, type_id_table_reader.account_cell(), Source::Input)?;
-
-    // Parse account from the data of the AccountCell in inputs.
-    let data_in_bytes = util::load_cell_data(account_cells[0], Source::Input)?;
-    let account_in_bytes = data_parser::account_cell::get_account(&data_in_bytes);
-    let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| Error::EIP712SerializationError)?;
+    let accounts = accounts_to_semantic(parser)?;
 
-    Ok(format!("CANCEL SALE OF {}", account))
+    Ok(format!("CANCEL SALE OF {}", accounts))
 }
 
 fn buy_account_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
-    let type_id_table_reader = parser.configs.main()?.type_id_table();
-    let account_cells =
-        util::find_cells_by_type_id(ScriptType::Type, type_id_table_reader.account_cell(), Source::Input)?;
-    let account_sale_cells = util::find_cells_by_type_id(
-        ScriptType::Type,
-        type_id_table_reader.account_sale_cell(),
-        Source::Input,
-    )?;
-
-    // Parse account from the data of the AccountCell in inputs.
-    let data_in_bytes = util::load_cell_data(account_cells[0], Source::Input)?;
-    let account_in_bytes = data_parser::account_cell::get_account(&data_in_bytes);
-    let account = String::from_utf8(account_in_bytes.to_vec()).map_err(|_| Error::EIP712SerializationError)?;
-
-    let (version, _, witness) =
-        parser.verify_and_get(DataType::AccountSaleCellData, account_sale_cells[0], Source::Input)?;
-
-    let price = if version == 1 {
-        let entity = AccountSaleCellDataV1::from_slice(witness.as_reader().raw_data()).map_err(|_| {
-            warn!("EIP712 decoding AccountSaleCellData failed");
-            Error::WitnessEntityDecodingError
-        })?;
-        to_semantic_capacity(u64::from(entity.price()))
-    } else {
-        let entity = AccountSaleCellData::from_slice(witness.as_reader().raw_data()).map_err(|_| {
-            warn!("EIP712 decoding AccountSaleCellData failed");
-            Error::WitnessEntityDecodingError
-        })?;
-        to_semantic_capacity(u64::from(entity.price()))
-    };
+    let accounts = accounts_to_semantic(parser)?;
+    let price = sale_price_to_semantic(parser, Source::Input)?;
 
-    Ok(format!("BUY {} WITH {}", account, price))
+    Ok(format!("BUY {} WITH {}", accounts, price))
 }
 
 fn verify_account_cell_consistent_except_status<'a>(
@@ -595,31 +596,19 @@ fn verify_sale_cell_capacity(
     Ok(())
 }
 
-fn verify_sale_cell_account_and_id<'a>(
-    input_account_cell: usize,
-    witness_reader: &Box<dyn AccountSaleCellDataReaderMixer + 'a>,
-) -> Result<(), Error> {
-    let input_account_cell_data = util::load_cell_data(input_account_cell, Source::Input)?;
-    let account_cell_account = data_parser::account_cell::get_account(&input_account_cell_data);
-    let account_cell_account_id = data_parser::account_cell::get_id(&input_account_cell_data);
-
-    // read account_id from AccountSaleCell's witness
-    let account_sale_cell_account_id = witness_reader.account_id().raw_data();
-    // ensure the AccountSaleCell's args equal to accountCell's id
-    assert!(
-        account_cell_account_id == account_sale_cell_account_id,
-        Error::AccountSaleCellAccountIdInvalid,
-        "The AccountSaleCell.witness.account_id should be equal to the AccountCell.data.account_id ."
-    );
-
-    // read account from AccountSaleCell's witness
-    let account_sale_cell_account = witness_reader.account().raw_data();
-    // ensure the AccountSaleCell's args equal to accountCell's id
-    assert!(
-        account_cell_account == account_sale_cell_account,
-        Error::AccountSaleCellAccountIdInvalid,
-        "The AccountSaleCell.witness.account should be equal to the AccountCell.data.account ."
-    );
+fn verify_bundle_has_same_owner(input_account_cells: &[usize]) -> Result<(), Error> {
+    let expected_owner_lock = util::derive_owner_lock_from_cell(input_account_cells[0], Source::Input)?;
+    for i in input_account_cells.iter().skip(1) {
+        let owner_lock = util::derive_owner_lock_from_cell(*i, Source::Input)?;
+        assert!(
+            util::is_entity_eq(&expected_owner_lock, &owner_lock),
+            Error::AccountSaleCellBundleError,
+            "inputs[{}] All AccountCells sold in one bundle should have the same owner.(expected: {}, current: {})",
+            i,
+            expected_owner_lock,
+            owner_lock
+        );
+    }
 
     Ok(())
 }
@@ -732,6 +721,14 @@ fn verify_account_sale_cell_consistent<'a>(
         util::hex_string(output_account.raw_data())
     );
 
+    let input_data = util::load_cell_data(input_cell, Source::Input)?;
+    let output_data = util::load_cell_data(output_cell, Source::Output)?;
+    assert!(
+        input_data.get(32..) == output_data.get(32..),
+        Error::AccountSaleCellBundleError,
+        "The accounts in the bundle of AccountSaleCell should be consistent in inputs and outputs."
+    );
+
     let input_started_at = input_cell_witness_reader.started_at();
     let output_started_at = output_cell_witness_reader.started_at();
     assert!(
@@ -34,7 +34,7 @@ pub fn main() -> Result<(), Error> {
 
         // Because the semantic requirement of each action, some other type script is required to generate DAS_MESSAGE field in EIP712 properly.
         match action {
-            b"transfer_account" | b"edit_manager" | b"edit_records" => {
+            b"transfer_account" | b"edit_manager" | b"edit_records" | b"lease_account" | b"end_lease" => {
                 util::require_type_script(
                     &parser,
                     TypeScript::AccountCellType,
@@ -58,7 +58,7 @@ pub fn main() -> Result<(), Error> {
                     Error::InvalidTransactionStructure,
                 )?;
             }
-            b"declare_reverse_record" => {
+            b"declare_reverse_record" | b"batch_declare_reverse_record" => {
                 util::require_type_script(
                     &parser,
                     TypeScript::ReverseRecordCellType,
@@ -74,7 +74,15 @@ pub fn main() -> Result<(), Error> {
                     Error::InvalidTransactionStructure,
                 )?;
             }
-            b"make_offer" | b"edit_offer" => {
+            b"withdraw_income" | b"edit_income_payout" => {
+                util::require_type_script(
+                    &parser,
+                    TypeScript::IncomeCellType,
+                    Source::Input,
+                    Error::InvalidTransactionStructure,
+                )?;
+            }
+            b"make_offer" | b"edit_offer" | b"counter_offer" => {
                 util::require_type_script(
                     &parser,
                     TypeScript::OfferCellType,
@@ -82,7 +90,7 @@ pub fn main() -> Result<(), Error> {
                     Error::InvalidTransactionStructure,
                 )?;
             }
-            b"cancel_offer" | b"accept_offer" => {
+            b"cancel_offer" | b"accept_offer" | b"accept_counter_offer" | b"recycle_expired_offer_by_keeper" => {
                 util::require_type_script(
                     &parser,
                     TypeScript::OfferCellType,
@@ -1,9 +1,14 @@
-use alloc::{borrow::ToOwned, vec::Vec};
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
 use ckb_std::{ckb_constants::Source, debug, high_level};
 use core::result::Result;
-use core::slice::Iter;
 use das_core::{
-    assert, constants::*, error::Error, parse_witness, util, verifiers, warn, witness_parser::WitnessesParser,
+    assert,
+    constants::*,
+    eip712::{to_semantic_capacity, verify_eip712_hashes},
+    error::Error,
+    income_planner::{self, IncomeConfig},
+    parse_witness, util, verifiers, warn,
+    witness_parser::WitnessesParser,
 };
 use das_types::{constants::DataType, packed::*, prelude::*};
 
@@ -75,6 +80,17 @@ pub fn main() -> Result<(), Error> {
                 "The only one record should has the same capacity with ConfigCellIncome.basic_capacity ."
             );
 
+            // The creator may declare how its record should be paid out, but only with their signature.
+            let payouts = verifiers::income_cell::verify_payouts(output_cells[0], Source::Output, 1)?;
+            if payouts[0] != IncomePayoutType::Auto as u8 {
+                let cells = util::find_cells_by_script(ScriptType::Lock, record.belong_to(), Source::Input)?;
+                assert!(
+                    cells.len() > 0,
+                    Error::IncomeCellPayoutError,
+                    "The creator should provide at least one cell with their lock in inputs to prove their signature, because the payout is not the default one."
+                );
+            }
+
             let cell_capacity = high_level::load_cell_capacity(output_cells[0], Source::Output)?;
             let basic_capacity = u64::from(config_income.basic_capacity());
             assert!(
@@ -104,12 +120,14 @@ pub fn main() -> Result<(), Error> {
             parser.parse_cell()?;
 
             let config_income = parser.configs.income()?;
-            let income_cell_basic_capacity = u64::from(config_income.basic_capacity());
-            let income_cell_max_records = u32::from(config_income.max_records()) as usize;
-            let income_cell_min_transfer_capacity = u64::from(config_income.min_transfer_capacity());
-            let income_consolidate_profit_rate = u32::from(parser.configs.profit_rate()?.income_consolidate()) as u64;
+            let income_config = IncomeConfig {
+                basic_capacity: u64::from(config_income.basic_capacity()),
+                max_records: u32::from(config_income.max_records()) as usize,
+                min_transfer_capacity: u64::from(config_income.min_transfer_capacity()),
+                consolidate_profit_rate: u32::from(parser.configs.profit_rate()?.income_consolidate()) as u64,
+            };
 
-            debug!("Find all income records in inputs and merge them into unique script to capacity pair.");
+            debug!("Find all income records in inputs.");
 
             let mut creators = Vec::new();
             let mut input_records = Vec::new();
@@ -134,37 +152,74 @@ pub fn main() -> Result<(), Error> {
                     );
                 }
 
-                for record in income_cell_witness.records().into_iter() {
-                    input_records = merge_record(input_records, record);
-                }
-
-                creators.push(creator);
+                let payouts = verifiers::income_cell::verify_payouts(index, Source::Input, records.len())?;
+                input_records.push(to_planner_records(records, payouts));
+                creators.push(creator.as_slice().to_vec());
             }
 
             // Always include DAS in the members which is free from consolidating fee.
             let das_wallet_lock = das_wallet_lock();
-            creators.push(das_wallet_lock.into());
+            creators.push(das_wallet_lock.as_slice().to_vec());
 
-            debug!("Classify all income records in inputs for comparing them with outputs later.");
+            debug!("Classify the records with the same planner which the keepers use.");
 
-            let (records_should_transfer, mut records_should_keep, need_pad) = classify_income_records(
-                income_consolidate_profit_rate,
-                income_cell_max_records,
-                income_cell_basic_capacity,
-                income_cell_min_transfer_capacity,
-                input_records,
-            );
+            let records = income_planner::merge_records(&input_records).map_err(|_| Error::IncomeCellPayoutError)?;
+            let classification = income_planner::classify_records(&income_config, &records).map_err(|err| {
+                warn!("The IncomeCells can not be consolidated.(error: {:?})", err);
+                Error::IncomeCellConsolidateConditionNotSatisfied
+            })?;
 
             #[cfg(debug_assertions)]
-            inspect_records("Records should be kept:", &records_should_keep);
-            #[cfg(debug_assertions)]
-            inspect_records("Records should be transferred:", &records_should_transfer);
+            inspect_records("Records merged from inputs:", &records);
+
+            debug!("Find out the capacity transferred to each record.");
+
+            let type_id_table = parser.configs.main()?.type_id_table();
+            let das_lock = das_lock();
+            let das_lock_reader = das_lock.as_reader();
+            let mut transfers = Vec::new();
+            for i in classification.to_transfer.iter() {
+                let record = &records[*i];
+                let lock_script =
+                    Script::from_slice(&record.belong_to).map_err(|_| Error::WitnessEntityDecodingError)?;
+                let cells = util::find_cells_by_script(ScriptType::Lock, lock_script.as_reader(), Source::Output)?;
+                if cells.is_empty() {
+                    // The record may be not transferred when the IncomeCell needs capacity padding, the planner will
+                    // check if it is kept for padding.
+                    continue;
+                }
+
+                assert!(
+                    cells.len() == 1,
+                    Error::IncomeCellTransferError,
+                    "There should be only one cell for the {}th record, but {} cells are found.",
+                    i,
+                    cells.len()
+                );
+
+                let capacity_transferred = high_level::load_cell_capacity(cells[0], Source::Output)?;
+
+                debug!(
+                    "records[{}] {{ output_index: {}, args: {}, capacity_transferred: {} }}",
+                    i,
+                    cells[0],
+                    lock_script.args(),
+                    capacity_transferred
+                );
+
+                verify_payout_cell(
+                    record.payout,
+                    das_lock_reader.into(),
+                    type_id_table.balance_cell(),
+                    cells[0],
+                )?;
 
-            debug!("Conclusion of need_pad: {}", need_pad);
+                transfers.push(record.with_capacity(capacity_transferred));
+            }
 
-            debug!("Classify all income records in outputs.");
+            debug!("Find out the records kept in IncomeCells.");
 
-            let mut output_records: Vec<(Script, u64)> = Vec::new();
+            let mut output_records = Vec::new();
             for (i, cell_index) in output_cells.iter().enumerate() {
                 let (_, _, entity) =
                     parser.verify_and_get(DataType::IncomeCellData, cell_index.to_owned(), Source::Output)?;
@@ -179,30 +234,14 @@ pub fn main() -> Result<(), Error> {
                     Some(income_cell_witness.as_reader()),
                 );
 
-                assert!(
-                    income_cell_witness.records().len() <= income_cell_max_records,
-                    Error::IncomeCellConsolidateError,
-                    "Output[{}] Each IncomeCell can not store more than {} records.",
-                    i,
-                    income_cell_max_records
-                );
-
-                let mut records_total_capacity = 0;
-                for record in income_cell_witness.records().into_iter() {
-                    for exist_record in output_records.iter() {
-                        assert!(
-                            !util::is_entity_eq(&exist_record.0, &record.belong_to()),
-                            Error::IncomeCellConsolidateError,
-                            "Output[{}] There should be not duplicate income records in outputs.",
-                            i
-                        )
-                    }
-
-                    let capacity = u64::from(record.capacity());
-                    records_total_capacity += capacity;
-                    output_records.push((record.belong_to(), capacity));
-                }
+                let payouts = verifiers::income_cell::verify_payouts(
+                    cell_index.to_owned(),
+                    Source::Output,
+                    income_cell_witness.records().len(),
+                )?;
+                let records = to_planner_records(income_cell_witness.records(), payouts);
 
+                let records_total_capacity: u64 = records.iter().map(|record| record.capacity).sum();
                 let cell_capacity = high_level::load_cell_capacity(cell_index.to_owned(), Source::Output)?;
                 assert!(
                     records_total_capacity == cell_capacity,
@@ -212,182 +251,317 @@ pub fn main() -> Result<(), Error> {
                     records_total_capacity,
                     cell_capacity
                 );
-                assert!(
-                    cell_capacity >= income_cell_basic_capacity,
-                    Error::IncomeCellConsolidateError,
-                    "Output[{}] The IncomeCell.capacity should be always greater than or equal to {} shannon.",
-                    i,
-                    income_cell_basic_capacity
-                )
-            }
 
-            if records_should_keep.len() > 0 {
-                assert!(
-                    output_records.len() > 0,
-                    Error::InvalidTransactionStructure,
-                    "There should be some IncomeCell in the outputs, because the count of records_should_keep is {}",
-                    records_should_keep.len()
-                );
+                output_records.push(records);
             }
 
-            debug!("Check if transfer as expected.");
+            #[cfg(debug_assertions)]
+            inspect_records("Records kept in outputs:", &output_records.concat());
+            #[cfg(debug_assertions)]
+            inspect_records("Records transferred in outputs:", &transfers);
 
-            let type_id_table = parser.configs.main()?.type_id_table();
-            let das_lock = das_lock();
-            let das_lock_reader = das_lock.as_reader();
-            let mut records_used_for_pad = Vec::new();
-            for (i, item) in records_should_transfer.into_iter().enumerate() {
-                let lock_script = item.0.as_reader();
-                let cells = util::find_cells_by_script(ScriptType::Lock, lock_script.into(), Source::Output)?;
-                if cells.len() != 1 {
-                    if need_pad {
-                        // If the IncomeCell needs capacity padding, and the records should be transferred are not transferred at all,
-                        // we think it must be used for padding with all its capacity.
-                        records_used_for_pad.push((item.0, item.1, false));
-                        continue;
-                    } else {
-                        // The length maybe 0, so do not use "Outputs[{}]" here.
-                        warn!(
-                        "There should be only one cell for the {}th record(records_should_transfer[{}]), but {} cells are found.",
-                        i,
-                        i,
-                        cells.len()
-                    );
-                        return Err(Error::IncomeCellTransferError);
-                    }
-                }
+            debug!("Check if consolidate as expected.");
 
-                let capacity_transferred = high_level::load_cell_capacity(cells[0], Source::Output)?;
-                let mut capacity_should_be_transferred =
-                    item.1 / RATE_BASE * (RATE_BASE - income_consolidate_profit_rate);
-
-                // If the record belongs to a IncomeCell creator, keeper should not take fee from it.
-                let mut belong_to_creator = false;
-                for creator in creators.iter() {
-                    if util::is_entity_eq(&item.0, creator) {
-                        belong_to_creator = true;
-                        capacity_should_be_transferred = item.1;
+            income_planner::verify_consolidation(&income_config, &creators, &records, &output_records, &transfers)
+                .map_err(|err| {
+                    warn!("The IncomeCells are not consolidated as expected.(error: {:?})", err);
+                    match err {
+                        income_planner::PlanError::IncomeCellMissing => Error::InvalidTransactionStructure,
+                        income_planner::PlanError::ConsolidateError => Error::IncomeCellConsolidateError,
+                        income_planner::PlanError::ConsolidateWaste => Error::IncomeCellConsolidateWaste,
+                        income_planner::PlanError::TransferError => Error::IncomeCellTransferError,
+                        income_planner::PlanError::PayoutConflict => Error::IncomeCellPayoutError,
+                        _ => Error::IncomeCellConsolidateConditionNotSatisfied,
                     }
-                }
+                })?;
+        }
+        b"merge_income" => {
+            debug!("Find out IncomeCells ...");
 
-                debug!(
-                "records_should_transfer[{}] {{ output_index: {}, args: {}, total: {}, capacity_transferred: {}, capacity_should_be_transferred: {} }}",
-                i,
-                cells[0],
-                item.0.args(),
-                item.1,
-                capacity_transferred,
-                capacity_should_be_transferred
+            let (input_cells, output_cells) = util::load_self_cells_in_inputs_and_outputs()?;
+
+            assert!(
+                input_cells.len() >= 2 && !output_cells.is_empty() && output_cells.len() < input_cells.len(),
+                Error::IncomeCellMergeError,
+                "There should be at least 2 IncomeCells in inputs and fewer IncomeCells in outputs.(inputs: {}, outputs: {})",
+                input_cells.len(),
+                output_cells.len()
             );
 
-                if capacity_transferred < capacity_should_be_transferred {
-                    if need_pad {
-                        let capacity_should_transferred_with_fee = if belong_to_creator {
-                            capacity_transferred
-                        } else {
-                            capacity_transferred / (RATE_BASE - income_consolidate_profit_rate) * RATE_BASE
-                        };
-                        let capacity_should_remain_for_pad = item.1 - capacity_should_transferred_with_fee;
-
-                        debug!("  Outputs[{}] recalculate fee from transferred part: {{ capacity_transferred: {}, final_fee: {} }}",
-                        cells[0], capacity_transferred, capacity_should_transferred_with_fee - capacity_transferred
-                    );
+            parser.parse_cell()?;
 
-                        // If the IncomeCell needs capacity padding, and the records should be transferred are transferred parts of its capacity,
-                        // we think the remain parts of capacity must be used for padding.
-                        records_used_for_pad.push((item.0, capacity_should_remain_for_pad, false));
-                    } else {
-                        warn!("Outputs[{}] The transferred capacity is less than expected. (capacity_in_record: {}, capacity_should_be_transferred: {}, capacity_transferred: {})",
-                        cells[0], item.1, capacity_should_be_transferred, capacity_transferred
-                    );
-                        return Err(Error::IncomeCellTransferError);
+            let config_income = parser.configs.income()?;
+
+            debug!("Merge all income records in inputs into unique script to capacity pairs.");
+
+            // The creators are free from the consolidating fee, so only the IncomeCells of the same creator can be
+            // merged, otherwise the creators which are not kept in outputs will lose their exemption.
+            let mut creator_opt: Option<Script> = None;
+            let mut input_records = Vec::new();
+            for index in input_cells {
+                let income_cell_witness = util::parse_income_cell_witness(&parser, index, Source::Input)?;
+
+                #[cfg(debug_assertions)]
+                das_core::inspect::income_cell(Source::Input, index, None, Some(income_cell_witness.as_reader()));
+
+                let records = income_cell_witness.records();
+                let payouts = verifiers::income_cell::verify_payouts(index, Source::Input, records.len())?;
+                input_records.push(to_planner_records(records, payouts));
+
+                let creator = income_cell_witness.creator();
+                match creator_opt.as_ref() {
+                    Some(expected) => {
+                        assert!(
+                            util::is_entity_eq(expected, &creator),
+                            Error::IncomeCellMergeError,
+                            "Inputs[{}] Only the IncomeCells of the same creator can be merged.",
+                            index
+                        );
                     }
-                    // The capacity of transfer must be less than which in the records.
-                } else if capacity_transferred > capacity_should_be_transferred {
-                    warn!(
-                    "Outputs[{}] The transferred capacity is more than expected. (capacity_in_record: {}, expected: {}, current: {})",
-                    cells[0], item.1, capacity_should_be_transferred, capacity_transferred
-                );
-                    return Err(Error::IncomeCellTransferError);
+                    None => creator_opt = Some(creator),
                 }
+            }
+            let creator = creator_opt.unwrap();
 
-                verify_das_lock_and_balance_type(
-                    das_lock_reader.into(),
-                    type_id_table.balance_cell(),
-                    cells[0],
+            let mut output_records = Vec::new();
+            for index in output_cells {
+                let income_cell_witness = util::parse_income_cell_witness(&parser, index, Source::Output)?;
+                let income_cell_witness_reader = income_cell_witness.as_reader();
+
+                #[cfg(debug_assertions)]
+                das_core::inspect::income_cell(Source::Output, index, None, Some(income_cell_witness_reader));
+
+                verifiers::misc::verify_always_success_lock(index, Source::Output)?;
+                verifiers::income_cell::verify_records_limit(config_income, income_cell_witness_reader)?;
+                verifiers::income_cell::verify_cell_capacity_with_records_capacity(
+                    config_income,
+                    index,
                     Source::Output,
+                    income_cell_witness_reader,
                 )?;
+
+                assert!(
+                    util::is_reader_eq(creator.as_reader(), income_cell_witness_reader.creator()),
+                    Error::IncomeCellMergeError,
+                    "Outputs[{}] The creator of the IncomeCell should be the same as the creator of the IncomeCells in inputs.",
+                    index
+                );
+
+                let records = income_cell_witness.records();
+                let payouts = verifiers::income_cell::verify_payouts(index, Source::Output, records.len())?;
+                let records = to_planner_records(records, payouts);
+                let compacted_records =
+                    income_planner::merge_records(&[records.clone()]).map_err(|_| Error::IncomeCellPayoutError)?;
+                assert!(
+                    records.len() == compacted_records.len(),
+                    Error::IncomeCellMergeError,
+                    "Outputs[{}] The records of the same lock should be compacted into one in the IncomeCell.",
+                    index
+                );
+
+                output_records.push(records);
+            }
+
+            debug!("Verify if every lock has the same capacity and payout in inputs and outputs.");
+
+            let merged_input_records =
+                income_planner::merge_records(&input_records).map_err(|_| Error::IncomeCellPayoutError)?;
+            let merged_output_records =
+                income_planner::merge_records(&output_records).map_err(|_| Error::IncomeCellPayoutError)?;
+
+            assert!(
+                merged_input_records.len() == merged_output_records.len(),
+                Error::IncomeCellMergeError,
+                "The number of locks in inputs and outputs should be the same.(inputs: {}, outputs: {})",
+                merged_input_records.len(),
+                merged_output_records.len()
+            );
+
+            for expected in merged_input_records.iter() {
+                let current_opt = merged_output_records
+                    .iter()
+                    .find(|item| item.belong_to == expected.belong_to);
+                assert!(
+                    current_opt == Some(expected),
+                    Error::IncomeCellMergeError,
+                    "The records of some lock is not the same in inputs and outputs.(expected: {:?}, current: {:?})",
+                    expected,
+                    current_opt
+                );
             }
+        }
+        b"withdraw_income" => {
+            parser.parse_cell()?;
+
+            verify_eip712_hashes(&parser, withdraw_income_to_semantic)?;
+
+            let config_income = parser.configs.income()?;
+            let income_cell_basic_capacity = u64::from(config_income.basic_capacity());
 
+            let (input_cells, output_cells) = util::load_self_cells_in_inputs_and_outputs()?;
+            assert!(
+                input_cells.len() == 1 && output_cells.len() == 1,
+                Error::InvalidTransactionStructure,
+                "There should be only one IncomeCell in inputs and outputs."
+            );
+
+            let (_, _, input_entity) =
+                parser.verify_and_get(DataType::IncomeCellData, input_cells[0], Source::Input)?;
+            let input_witness = IncomeCellData::from_slice(input_entity.as_reader().raw_data())
+                .map_err(|_| Error::WitnessEntityDecodingError)?;
+            let (_, _, output_entity) =
+                parser.verify_and_get(DataType::IncomeCellData, output_cells[0], Source::Output)?;
+            let output_witness = IncomeCellData::from_slice(output_entity.as_reader().raw_data())
+                .map_err(|_| Error::WitnessEntityDecodingError)?;
+
+            #[cfg(debug_assertions)]
+            das_core::inspect::income_cell(Source::Input, input_cells[0], None, Some(input_witness.as_reader()));
             #[cfg(debug_assertions)]
-            inspect_records(
-                "Records should be used to pad IncomeCell capacity:",
-                &records_used_for_pad,
+            das_core::inspect::income_cell(Source::Output, output_cells[0], None, Some(output_witness.as_reader()));
+
+            assert!(
+                util::is_entity_eq(&input_witness.creator(), &output_witness.creator()),
+                Error::IncomeCellWithdrawError,
+                "The IncomeCell.witness.creator should not be modified."
             );
 
-            debug!("Check if consolidate as expected.");
+            debug!("Verify if the records in outputs are the records in inputs without the records of the withdrawer.");
 
-            for record in output_records {
-                let mut is_exist = false;
-                // Check if record exists in the records_should_keep.
-                for expected_record in records_should_keep.iter_mut() {
-                    if util::is_entity_eq(&record.0, &expected_record.0) {
-                        assert!(
-                            record.1 == expected_record.1,
-                            Error::IncomeCellConsolidateError,
-                            "The capacity of some records in the outputs is incorrect. (belong_to: {}, expected: {}, current: {})",
-                            record.0,
-                            expected_record.1,
-                            record.1
-                        );
-                        expected_record.2 = true;
-                        is_exist = true;
-                    }
-                }
+            let (withdrawer, withdrawn_capacity, kept_indexes) =
+                find_withdrawn_records(input_witness.records(), output_witness.records())?;
 
-                if !is_exist {
-                    // Check if record exists in the records_used_for_pad.
-                    for expected_record in records_used_for_pad.iter_mut() {
-                        if util::is_entity_eq(&record.0, &expected_record.0) {
-                            assert!(
-                                record.1 == expected_record.1,
-                                Error::IncomeCellConsolidateError,
-                                "The record should be transferred is not transferred completely, so we think parts of its capacity should be used for padding capacity, BUT the capacity used for padding is incorrect. (belong_to: {}, expected: {}, current: {})",
-                                record.0,
-                                expected_record.1,
-                                record.1
-                            );
-
-                            expected_record.2 = true;
-                            is_exist = true;
-                        }
-                    }
-                }
+            debug!(
+                "Found the withdrawer {} withdraws {} shannon.",
+                withdrawer.args(),
+                withdrawn_capacity
+            );
+
+            let cells = util::find_cells_by_script(ScriptType::Lock, withdrawer.as_reader().into(), Source::Input)?;
+            assert!(
+                cells.len() > 0,
+                Error::IncomeCellWithdrawError,
+                "The withdrawer should provide at least one cell with their lock in inputs to prove their signature."
+            );
 
+            debug!("Verify if the payouts of the records kept in outputs are not modified.");
+
+            let input_payouts =
+                verifiers::income_cell::verify_payouts(input_cells[0], Source::Input, input_witness.records().len())?;
+            let output_payouts = verifiers::income_cell::verify_payouts(
+                output_cells[0],
+                Source::Output,
+                output_witness.records().len(),
+            )?;
+            for (output_payout, input_index) in output_payouts.iter().zip(kept_indexes.iter()) {
                 assert!(
-                    is_exist,
-                    Error::IncomeCellConsolidateError,
-                    "Missing expected record in outputs. (expected: {:?})", record
+                    output_payout == &input_payouts[*input_index],
+                    Error::IncomeCellPayoutError,
+                    "The payout of the record inputs[{}].witness.records[{}] should not be modified.",
+                    input_cells[0],
+                    input_index
                 );
             }
 
-            for record in records_should_keep.iter() {
-                assert!(
-                    record.2,
-                    Error::IncomeCellConsolidateError,
-                    "The record should be kept is missing in outputs. (belong_to: {}, wasted: {})", record.0, record.1
-                );
+            debug!("Verify if the capacity of the IncomeCell is correct.");
+
+            let mut records_total_capacity = 0;
+            for record in output_witness.records().into_iter() {
+                records_total_capacity += u64::from(record.capacity());
             }
 
-            for record in records_used_for_pad.iter() {
+            let input_capacity = high_level::load_cell_capacity(input_cells[0], Source::Input)?;
+            let output_capacity = high_level::load_cell_capacity(output_cells[0], Source::Output)?;
+            assert!(
+                output_capacity == records_total_capacity && input_capacity == output_capacity + withdrawn_capacity,
+                Error::IncomeCellCapacityError,
+                "The IncomeCell.capacity should be always equal to the total capacity of its records. (expected: {}, current: {})",
+                records_total_capacity,
+                output_capacity
+            );
+            assert!(
+                output_capacity >= income_cell_basic_capacity,
+                Error::IncomeCellCapacityError,
+                "The IncomeCell.capacity should be always greater than or equal to {} shannon.",
+                income_cell_basic_capacity
+            );
+
+            // The withdrawer has signed the transaction, so there is no need to verify where the withdrawn capacity goes.
+        }
+        b"edit_income_payout" => {
+            parser.parse_cell()?;
+
+            verify_eip712_hashes(&parser, edit_income_payout_to_semantic)?;
+
+            let (input_cells, output_cells) = util::load_self_cells_in_inputs_and_outputs()?;
+            assert!(
+                input_cells.len() == 1 && output_cells.len() == 1,
+                Error::InvalidTransactionStructure,
+                "There should be only one IncomeCell in inputs and outputs."
+            );
+
+            let (_, _, input_entity) =
+                parser.verify_and_get(DataType::IncomeCellData, input_cells[0], Source::Input)?;
+            let input_witness = IncomeCellData::from_slice(input_entity.as_reader().raw_data())
+                .map_err(|_| Error::WitnessEntityDecodingError)?;
+            let (_, _, output_entity) =
+                parser.verify_and_get(DataType::IncomeCellData, output_cells[0], Source::Output)?;
+            let output_witness = IncomeCellData::from_slice(output_entity.as_reader().raw_data())
+                .map_err(|_| Error::WitnessEntityDecodingError)?;
+
+            #[cfg(debug_assertions)]
+            das_core::inspect::income_cell(Source::Input, input_cells[0], None, Some(input_witness.as_reader()));
+            #[cfg(debug_assertions)]
+            das_core::inspect::income_cell(Source::Output, output_cells[0], None, Some(output_witness.as_reader()));
+
+            verifiers::misc::verify_always_success_lock(output_cells[0], Source::Output)?;
+
+            assert!(
+                util::is_entity_eq(&input_witness, &output_witness),
+                Error::IncomeCellPayoutError,
+                "The IncomeCell.witness should not be modified, only the payouts in the IncomeCell.data can be modified."
+            );
+
+            let input_capacity = high_level::load_cell_capacity(input_cells[0], Source::Input)?;
+            let output_capacity = high_level::load_cell_capacity(output_cells[0], Source::Output)?;
+            assert!(
+                input_capacity == output_capacity,
+                Error::IncomeCellCapacityError,
+                "The IncomeCell.capacity should not be modified. (expected: {}, current: {})",
+                input_capacity,
+                output_capacity
+            );
+
+            debug!("Verify if every payout modified is signed by the payee of the record.");
+
+            let records = output_witness.records();
+            let input_payouts = verifiers::income_cell::verify_payouts(input_cells[0], Source::Input, records.len())?;
+            let output_payouts =
+                verifiers::income_cell::verify_payouts(output_cells[0], Source::Output, records.len())?;
+
+            let mut modified_count = 0;
+            for (i, record) in records.into_iter().enumerate() {
+                if input_payouts[i] == output_payouts[i] {
+                    continue;
+                }
+
+                let cells =
+                    util::find_cells_by_script(ScriptType::Lock, record.belong_to().as_reader(), Source::Input)?;
                 assert!(
-                    record.2,
-                    Error::IncomeCellConsolidateWaste,
-                    "The record should be transferred is not transferred completely, so we think parts of its capacity should be used for padding capacity, BUT the capacity is not used for padding. (belong_to: {}, wasted: {})",
-                    record.0,
-                    record.1
+                    cells.len() > 0,
+                    Error::IncomeCellPayoutError,
+                    "The payee of the record outputs[{}].witness.records[{}] should provide at least one cell with their lock in inputs to prove their signature.",
+                    output_cells[0],
+                    i
                 );
+
+                modified_count += 1;
             }
+
+            assert!(
+                modified_count > 0,
+                Error::IncomeCellPayoutError,
+                "There should be at least one payout modified."
+            );
         }
         b"confirm_proposal" => {
             util::require_type_script(
@@ -397,6 +571,14 @@ pub fn main() -> Result<(), Error> {
                 Error::InvalidTransactionStructure,
             )?;
         }
+        b"register_account" => {
+            util::require_type_script(
+                &parser,
+                TypeScript::PreAccountCellType,
+                Source::Input,
+                Error::InvalidTransactionStructure,
+            )?;
+        }
         b"buy_account" => {
             util::require_type_script(
                 &parser,
@@ -405,7 +587,7 @@ pub fn main() -> Result<(), Error> {
                 Error::InvalidTransactionStructure,
             )?;
         }
-        b"accept_offer" => {
+        b"accept_offer" | b"accept_counter_offer" => {
             util::require_type_script(
                 &parser,
                 TypeScript::OfferCellType,
@@ -438,78 +620,150 @@ pub fn main() -> Result<(), Error> {
     Ok(())
 }
 
-fn merge_record(mut input_records: Vec<(Script, u64)>, record: IncomeRecord) -> Vec<(Script, u64)> {
-    for exist_record in input_records.iter_mut() {
-        if util::is_entity_eq(&exist_record.0, &record.belong_to()) {
-            exist_record.1 += u64::from(record.capacity());
-            return input_records;
+fn to_planner_records(records: IncomeRecords, payouts: Vec<u8>) -> Vec<income_planner::IncomeRecord> {
+    records
+        .into_iter()
+        .zip(payouts.into_iter())
+        .map(|(record, payout)| {
+            income_planner::IncomeRecord::new(record.belong_to().as_slice().to_vec(), u64::from(record.capacity()))
+                .with_payout(payout)
+        })
+        .collect()
+}
+
+/// Find the records which are removed from the IncomeCell, all of them should belong to the same lock.
+///
+/// The records in outputs should keep the same order as they are in inputs, return the lock of the withdrawer, the total
+/// capacity withdrawn and the indexes of the records kept in outputs.
+fn find_withdrawn_records(
+    input_records: IncomeRecords,
+    output_records: IncomeRecords,
+) -> Result<(Script, u64, Vec<usize>), Error> {
+    let mut withdrawer_opt: Option<Script> = None;
+    let mut withdrawn_capacity = 0;
+    let mut kept_indexes = Vec::new();
+    let mut output_iter = output_records.into_iter().peekable();
+
+    for (i, record) in input_records.into_iter().enumerate() {
+        if let Some(output_record) = output_iter.peek() {
+            if util::is_entity_eq(&record, output_record) {
+                output_iter.next();
+                kept_indexes.push(i);
+                continue;
+            }
         }
+
+        match &withdrawer_opt {
+            Some(withdrawer) => {
+                assert!(
+                    util::is_entity_eq(withdrawer, &record.belong_to()),
+                    Error::IncomeCellWithdrawError,
+                    "Only the records belong to one lock can be withdrawn in one transaction.(expected: {}, current: {})",
+                    withdrawer,
+                    record.belong_to()
+                );
+            }
+            None => withdrawer_opt = Some(record.belong_to()),
+        }
+        withdrawn_capacity += u64::from(record.capacity());
     }
 
-    input_records.push((record.belong_to(), u64::from(record.capacity())));
-    input_records
-}
+    assert!(
+        output_iter.next().is_none(),
+        Error::IncomeCellWithdrawError,
+        "The records in outputs should be the same as the records in inputs except the withdrawn records."
+    );
 
-fn calc_total_records_capacity(records: Iter<(Script, u64, bool)>) -> u64 {
-    // There is no reduce method here, so we use for...in instead.
-    let mut total = 0;
-    for record in records {
-        total += record.1;
+    match withdrawer_opt {
+        Some(withdrawer) => Ok((withdrawer, withdrawn_capacity, kept_indexes)),
+        None => {
+            warn!("There should be at least one record withdrawn from the IncomeCell.");
+            Err(Error::IncomeCellWithdrawError)
+        }
     }
-
-    total
 }
 
-fn classify_income_records(
-    income_consolidate_profit_rate: u64,
-    income_cell_max_records: usize,
-    income_cell_basic_capacity: u64,
-    income_cell_min_transfer_capacity: u64,
-    input_records: Vec<(Script, u64)>,
-) -> (Vec<(Script, u64, bool)>, Vec<(Script, u64, bool)>, bool) {
-    let mut records_should_transfer = Vec::new();
-    let mut records_should_keep = Vec::new();
-
-    for record in input_records.into_iter() {
-        let capacity_after_fee_paid = record.1 / RATE_BASE * (RATE_BASE - income_consolidate_profit_rate);
+fn withdraw_income_to_semantic(_parser: &WitnessesParser) -> Result<String, Error> {
+    let (input_cells, output_cells) = util::load_self_cells_in_inputs_and_outputs()?;
+    let input_capacity = high_level::load_cell_capacity(input_cells[0], Source::Input)?;
+    let output_capacity = high_level::load_cell_capacity(output_cells[0], Source::Output)?;
 
-        debug!(
-            "  {{ args: {}, capacity_after_fee_paid: {} = {}(record.capacity) - {}(record.capacity) * {}(income_consolidate_profit_rate) / {}(RATE_BASE) }}",
-            record.0.args(),
-            capacity_after_fee_paid,
-            record.1,
-            record.1,
-            income_consolidate_profit_rate,
-            RATE_BASE
-        );
+    Ok(format!(
+        "WITHDRAW {} FROM INCOME",
+        to_semantic_capacity(input_capacity.saturating_sub(output_capacity))
+    ))
+}
 
-        if capacity_after_fee_paid >= income_cell_min_transfer_capacity {
-            records_should_transfer.push((record.0, record.1, false));
-        } else {
-            records_should_keep.push((record.0, record.1, false));
-        }
+fn edit_income_payout_to_semantic(_parser: &WitnessesParser) -> Result<String, Error> {
+    let (_, output_cells) = util::load_self_cells_in_inputs_and_outputs()?;
+    let data = high_level::load_cell_data(output_cells[0], Source::Output)?;
+
+    // The payouts are placed after the hash of the witness, they are all AUTO if omitted.
+    let payouts = data
+        .iter()
+        .skip(32)
+        .map(|payout| match *payout {
+            x if x == IncomePayoutType::Plain as u8 => "PLAIN",
+            x if x == IncomePayoutType::BalanceType as u8 => "BALANCE TYPE",
+            x if x == IncomePayoutType::DaoDeposit as u8 => "DAO DEPOSIT",
+            _ => "AUTO",
+        })
+        .collect::<Vec<_>>();
+
+    if payouts.is_empty() {
+        return Ok(String::from("EDIT INCOME PAYOUTS TO AUTO"));
     }
 
-    let remain_capacity = calc_total_records_capacity(records_should_keep.iter());
+    Ok(format!("EDIT INCOME PAYOUTS TO {}", payouts.join(", ")))
+}
 
-    // Calculate how many IncomeCell is required in outputs.
-    let mut output_income_cell_count: u64;
-    if records_should_keep.len() <= 0 {
-        output_income_cell_count = 0;
+fn verify_payout_cell(
+    payout: u8,
+    das_lock_reader: ScriptReader,
+    balance_cell_type_id: HashReader,
+    index: usize,
+) -> Result<(), Error> {
+    let type_opt = high_level::load_cell_type(index, Source::Output)?;
+
+    if payout == IncomePayoutType::Plain as u8 {
+        assert!(
+            type_opt.is_none(),
+            Error::IncomeCellPayoutError,
+            "Outputs[{}] The payout of the record is plain, so the cell should not have any type script.",
+            index
+        );
+    } else if payout == IncomePayoutType::BalanceType as u8 {
+        let is_balance_cell = match type_opt {
+            Some(type_) => {
+                util::is_reader_eq(type_.as_reader().code_hash(), balance_cell_type_id)
+                    && type_.as_reader().hash_type().as_slice()[0] == ScriptHashType::Type as u8
+            }
+            None => false,
+        };
+        assert!(
+            is_balance_cell,
+            Error::IncomeCellPayoutError,
+            "Outputs[{}] The payout of the record requires the cell to have balance-cell-type in its type field.",
+            index
+        );
+    } else if payout == IncomePayoutType::DaoDeposit as u8 {
+        let dao_type_script = dao_type_script();
+        let is_dao_cell = match type_opt {
+            Some(type_) => util::is_entity_eq(&type_, &dao_type_script),
+            None => false,
+        };
+        let data = util::load_cell_data(index, Source::Output)?;
+        assert!(
+            is_dao_cell && data == [0u8; 8],
+            Error::IncomeCellPayoutError,
+            "Outputs[{}] The payout of the record requires the cell to be a NervosDAO deposit cell.",
+            index
+        );
     } else {
-        output_income_cell_count = (records_should_keep.len() / income_cell_max_records) as u64;
-        if records_should_keep.len() % income_cell_max_records != 0 {
-            output_income_cell_count += 1
-        }
+        verify_das_lock_and_balance_type(das_lock_reader, balance_cell_type_id, index, Source::Output)?;
     }
 
-    (
-        records_should_transfer,
-        records_should_keep,
-        // If the total capacity remains in IncomeCell is not enough, that means the IncomeCell needs padding.
-        // If the total capacity remains 0, that means no IncomeCell is needed is outputs.
-        remain_capacity != 0 && remain_capacity < (income_cell_basic_capacity * output_income_cell_count),
-    )
+    Ok(())
 }
 
 fn verify_das_lock_and_balance_type(
@@ -557,15 +811,16 @@ fn verify_das_lock_and_balance_type(
 }
 
 #[cfg(debug_assertions)]
-fn inspect_records(title: &str, records: &Vec<(Script, u64, bool)>) {
+fn inspect_records(title: &str, records: &[income_planner::IncomeRecord]) {
     debug!("{} {} total", title, records.len());
 
     for (i, record) in records.iter().enumerate() {
         debug!(
-            "  {{ index: {}, belong_to.args: {}, capacity: {} }}",
+            "  {{ index: {}, belong_to: 0x{}, capacity: {}, payout: {} }}",
             i,
-            record.0.args(),
-            record.1
+            util::hex_string(&record.belong_to),
+            record.capacity,
+            record.payout
         );
     }
 }
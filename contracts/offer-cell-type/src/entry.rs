@@ -1,4 +1,4 @@
-use alloc::{boxed::Box, format, string::String};
+use alloc::{boxed::Box, format, string::String, vec::Vec};
 use ckb_std::{ckb_constants::Source, high_level};
 use core::result::Result;
 use das_core::{
@@ -12,8 +12,8 @@ use das_core::{
 };
 use das_map::{map::Map, util as map_util};
 use das_types::{
-    constants::{AccountStatus, DataType},
-    mixer::AccountCellDataMixer,
+    constants::{AccountStatus, DataType, CHAR_SET_LENGTH},
+    mixer::{AccountCellDataMixer, AccountCellDataReaderMixer},
     packed::*,
     prelude::*,
 };
@@ -111,11 +111,26 @@ pub fn main() -> Result<(), Error> {
                 OfferCellData
             );
 
+            let output_offer_cell_data = high_level::load_cell_data(output_cells[0], Source::Output)?;
+            let collection_type = data_parser::offer_cell::get_collection_type(&output_offer_cell_data);
+
+            assert!(
+                data_parser::offer_cell::is_data_length_valid(&output_offer_cell_data),
+                Error::InvalidCellData,
+                "The OfferCell.data should be the hash of witness, optionally followed by expired_at and the collection."
+            );
+
             if action == b"make_offer" {
                 verify_eip712_hashes(&parser, make_offer_to_semantic)?;
 
                 debug!("Verify if the fields of the OfferCell is set correctly.");
 
+                if collection_type.is_some() {
+                    verify_collection(&output_offer_cell_data, output_offer_cell_witness_reader)?;
+                }
+
+                verify_expired_at(&output_offer_cell_data)?;
+
                 verify_price(
                     config_second_market,
                     output_offer_cell_witness_reader,
@@ -141,6 +156,22 @@ pub fn main() -> Result<(), Error> {
 
                 debug!("Verify if the fields of the OfferCell is modified propoerly.");
 
+                assert!(
+                    collection_type != Some(OfferCollectionType::Counter as u8),
+                    Error::OfferCellCounterOfferError,
+                    "The counter offer can not be edited, please cancel it and make a new one."
+                );
+
+                // The legacy OfferCells has no expired_at, so the missing collection should be treated as empty, otherwise
+                // expired_at can not be added to or removed from them.
+                let input_offer_cell_data = high_level::load_cell_data(input_cells[0], Source::Input)?;
+                assert!(
+                    input_offer_cell_data.get(40..).unwrap_or_default()
+                        == output_offer_cell_data.get(40..).unwrap_or_default(),
+                    Error::OfferCellFieldCanNotModified,
+                    "The collection of accounts which the OfferCell is bidding on can not be modified."
+                );
+
                 assert!(
                     util::is_reader_eq(
                         input_offer_cell_witness_reader.account(),
@@ -197,6 +228,13 @@ pub fn main() -> Result<(), Error> {
                 )?;
 
                 let mut changed = false;
+                if data_parser::offer_cell::get_expired_at(&input_offer_cell_data)
+                    != data_parser::offer_cell::get_expired_at(&output_offer_cell_data)
+                {
+                    // The offerer may extend or shorten the expiration time, as long as it is still in the future.
+                    verify_expired_at(&output_offer_cell_data)?;
+                    changed = true;
+                }
                 if !util::is_reader_eq(
                     input_offer_cell_witness_reader.price(),
                     output_offer_cell_witness_reader.price(),
@@ -218,9 +256,11 @@ pub fn main() -> Result<(), Error> {
                 );
             }
 
-            let account = output_offer_cell_witness_reader.account().raw_data();
-            let account_without_suffix = &account[0..account.len() - 4];
-            verifiers::account_cell::verify_unavailable_accounts(&parser, account_without_suffix)?;
+            if collection_type.is_none() {
+                let account = output_offer_cell_witness_reader.account().raw_data();
+                let account_without_suffix = &account[0..account.len() - 4];
+                verifiers::account_cell::verify_unavailable_accounts(&parser, account_without_suffix)?;
+            }
         }
         b"cancel_offer" => {
             parser.parse_cell()?;
@@ -235,23 +275,84 @@ pub fn main() -> Result<(), Error> {
                 "There should be at least 1 OfferCell in inputs."
             );
 
-            // Stop transaction builder to spend users other cells in this transaction.
-            verifiers::misc::verify_no_more_cells(&input_cells, Source::Input)?;
-
-            debug!("Verify if all OfferCells in inputs has the same lock script with the first OfferCell.");
+            let account_cell_type_id = config_main.type_id_table().account_cell();
+            let (input_account_cells, output_account_cells) =
+                util::find_cells_by_type_id_in_inputs_and_outputs(ScriptType::Type, account_cell_type_id)?;
 
-            let expected_lock_hash = high_level::load_cell_lock_hash(input_cells[0], Source::Input)?;
             let mut total_input_capacity = 0;
-            for i in input_cells.iter() {
-                let lock_hash = high_level::load_cell_lock_hash(*i, Source::Input)?;
+            if input_account_cells.is_empty() {
+                // Stop transaction builder to spend users other cells in this transaction.
+                verifiers::misc::verify_no_more_cells(&input_cells, Source::Input)?;
+
+                debug!("Verify if all OfferCells in inputs has the same lock script with the first OfferCell.");
+
+                let expected_lock_hash = high_level::load_cell_lock_hash(input_cells[0], Source::Input)?;
+                for i in input_cells.iter() {
+                    let lock_hash = high_level::load_cell_lock_hash(*i, Source::Input)?;
+                    assert!(
+                        expected_lock_hash == lock_hash,
+                        Error::InvalidTransactionStructure,
+                        "Inputs[{}] The OfferCell should has the same lock script with others.",
+                        i
+                    );
+
+                    let data = high_level::load_cell_data(*i, Source::Input)?;
+                    assert!(
+                        data_parser::offer_cell::get_collection_type(&data) != Some(OfferCollectionType::Counter as u8),
+                        Error::OfferCellCounterOfferError,
+                        "Inputs[{}] The counter offer should be canceled together with the AccountCell it locked.",
+                        i
+                    );
+
+                    total_input_capacity += high_level::load_cell_capacity(*i, Source::Input)?;
+                }
+            } else {
                 assert!(
-                    expected_lock_hash == lock_hash,
+                    input_account_cells.len() == 1
+                        && input_account_cells[0] == 0
+                        && output_account_cells.len() == 1
+                        && output_account_cells[0] == 0
+                        && input_cells.len() == 1
+                        && input_cells[0] == 1,
                     Error::InvalidTransactionStructure,
-                    "Inputs[{}] The OfferCell should has the same lock script with others.",
-                    i
+                    "There should be the AccountCell at inputs[0] and outputs[0], and the counter offer at inputs[1]."
                 );
 
-                total_input_capacity += high_level::load_cell_capacity(*i, Source::Input)?;
+                let all_input_cells = [input_account_cells.clone(), input_cells.clone()].concat();
+                verifiers::misc::verify_no_more_cells(&all_input_cells, Source::Input)?;
+
+                verify_counter_offer_of_account_cell(&parser, input_cells[0], input_account_cells[0])?;
+
+                debug!("Verify if the AccountCell is unlocked from the counter offer.");
+
+                let input_account_cell_witness =
+                    util::parse_account_cell_witness(&parser, input_account_cells[0], Source::Input)?;
+                let input_account_cell_witness_reader = input_account_cell_witness.as_reader();
+                let output_account_cell_witness =
+                    util::parse_account_cell_witness(&parser, output_account_cells[0], Source::Output)?;
+                let output_account_cell_witness_reader = output_account_cell_witness.as_reader();
+
+                verifiers::account_cell::verify_account_capacity_not_decrease(
+                    input_account_cells[0],
+                    output_account_cells[0],
+                )?;
+                verifiers::account_cell::verify_account_cell_consistent_with_exception(
+                    input_account_cells[0],
+                    output_account_cells[0],
+                    &input_account_cell_witness_reader,
+                    &output_account_cell_witness_reader,
+                    None,
+                    vec![],
+                    vec!["status"],
+                )?;
+                verifiers::account_cell::verify_account_cell_status_update_correctly(
+                    &input_account_cell_witness_reader,
+                    &output_account_cell_witness_reader,
+                    AccountStatus::Selling,
+                    AccountStatus::Normal,
+                )?;
+
+                total_input_capacity += high_level::load_cell_capacity(input_cells[0], Source::Input)?;
             }
 
             debug!("Verify if all capacity have been refund to user correctly.");
@@ -264,22 +365,22 @@ pub fn main() -> Result<(), Error> {
                 total_input_capacity - common_fee,
             )?;
         }
-        b"accept_offer" => {
+        b"counter_offer" => {
             let timestamp = util::load_oracle_data(OracleCellType::Time)?;
 
             parser.parse_cell()?;
-
-            verify_eip712_hashes(&parser, accept_offer_to_semantic)?;
-
             let config_main = parser.configs.main()?;
             let config_account = parser.configs.account()?;
-            let config_secondary_market = parser.configs.secondary_market()?;
+            let config_second_market = parser.configs.secondary_market()?;
 
-            verifiers::common::verify_removed_cell_in_correct_position(
+            verify_eip712_hashes(&parser, counter_offer_to_semantic)?;
+
+            // The seller locks the AccountCell at inputs[0] when making a counter offer, so the counter offer follows it.
+            verifiers::common::verify_created_cell_in_correct_position(
                 "OfferCell",
                 &input_cells,
                 &output_cells,
-                Some(0),
+                Some(1),
             )?;
 
             let account_cell_type_id = config_main.type_id_table().account_cell();
@@ -292,9 +393,331 @@ pub fn main() -> Result<(), Error> {
                 "There should be 1 AccountCell in both inputs and outputs."
             );
             assert!(
-                input_account_cells[0] == 1 && output_account_cells[0] == 0,
+                input_account_cells[0] == 0 && output_account_cells[0] == 0,
+                Error::InvalidTransactionStructure,
+                "The AccountCell should only appear in inputs[0] and outputs[0]."
+            );
+
+            let sender_lock = util::derive_owner_lock_from_cell(input_account_cells[0], Source::Input)?;
+            let balance_cells = util::find_balance_cells(config_main, sender_lock.as_reader(), Source::Input)?;
+            let all_input_cells = [input_account_cells.clone(), balance_cells.clone()].concat();
+            verifiers::misc::verify_no_more_cells(&all_input_cells, Source::Input)?;
+
+            debug!("Verify if the change is transferred back to the sender properly.");
+
+            let total_input_capacity = util::load_cells_capacity(&balance_cells, Source::Input)?;
+            let counter_offer_capacity = high_level::load_cell_capacity(output_cells[0], Source::Output)?;
+            let common_fee = u64::from(config_second_market.common_fee());
+            if total_input_capacity > counter_offer_capacity + common_fee {
+                verifiers::misc::verify_user_get_change(
+                    config_main,
+                    sender_lock.as_reader(),
+                    total_input_capacity - counter_offer_capacity - common_fee,
+                )?;
+            }
+
+            debug!("Verify if the AccountCell is locked for the counter offer.");
+
+            let input_account_cell_witness =
+                util::parse_account_cell_witness(&parser, input_account_cells[0], Source::Input)?;
+            let input_account_cell_witness_reader = input_account_cell_witness.as_reader();
+            let output_account_cell_witness =
+                util::parse_account_cell_witness(&parser, output_account_cells[0], Source::Output)?;
+            let output_account_cell_witness_reader = output_account_cell_witness.as_reader();
+
+            // The AccountCell is turned into the Selling status, so the seller can not transfer it or make another counter
+            // offer before the counter offer is accepted or canceled, and the buyer can accept it without the seller's signature.
+            verifiers::account_cell::verify_account_expiration(config_account, input_account_cells[0], timestamp)?;
+            verifiers::account_cell::verify_account_capacity_not_decrease(
+                input_account_cells[0],
+                output_account_cells[0],
+            )?;
+            verifiers::account_cell::verify_account_cell_consistent_with_exception(
+                input_account_cells[0],
+                output_account_cells[0],
+                &input_account_cell_witness_reader,
+                &output_account_cell_witness_reader,
+                None,
+                vec![],
+                vec!["status"],
+            )?;
+            verifiers::account_cell::verify_account_cell_status_update_correctly(
+                &input_account_cell_witness_reader,
+                &output_account_cell_witness_reader,
+                AccountStatus::Normal,
+                AccountStatus::Selling,
+            )?;
+
+            debug!("Verify if the counter offer is owned by the owner of the AccountCell.");
+
+            let current_lock = high_level::load_cell_lock(output_cells[0], Source::Output)?;
+            assert!(
+                util::is_entity_eq(&sender_lock, &current_lock),
+                Error::OfferCellLockError,
+                "The counter offer should be owned by the owner of the AccountCell.(expected: {}, current: {})",
+                sender_lock,
+                current_lock
+            );
+
+            debug!("Verify if the fields of the counter offer is set correctly.");
+
+            let output_offer_cell_witness;
+            let output_offer_cell_witness_reader;
+            parse_witness!(
+                output_offer_cell_witness,
+                output_offer_cell_witness_reader,
+                parser,
+                output_cells[0],
+                Source::Output,
+                DataType::OfferCellData,
+                OfferCellData
+            );
+
+            let account_cell_data = high_level::load_cell_data(input_account_cells[0], Source::Input)?;
+            let current_account = data_parser::account_cell::get_account(&account_cell_data);
+            let counter_offer_account = output_offer_cell_witness_reader.account().raw_data();
+            assert!(
+                counter_offer_account == current_account,
+                Error::OfferCellAccountMismatch,
+                "The account of the counter offer should be {}, but {} found.",
+                String::from_utf8(current_account.to_vec()).unwrap(),
+                String::from_utf8(counter_offer_account.to_vec()).unwrap()
+            );
+
+            let data = high_level::load_cell_data(output_cells[0], Source::Output)?;
+            let collection_type = data_parser::offer_cell::get_collection_type(&data);
+            let args = data_parser::offer_cell::get_collection_args(&data).unwrap_or_default();
+            assert!(
+                collection_type == Some(OfferCollectionType::Counter as u8) && args.len() == 36,
+                Error::OfferCellCounterOfferError,
+                "The counter offer should have the out point of an OfferCell in its data."
+            );
+
+            verify_expired_at(&data)?;
+            verify_min_price(config_second_market, output_offer_cell_witness_reader)?;
+            verify_message_length(config_second_market, output_offer_cell_witness_reader)?;
+
+            // The price of a counter offer is paid by the buyer when it is accepted, so the counter offer only locks the basic
+            // capacity and the prepared fee.
+            let expected_capacity = u64::from(config_second_market.offer_cell_basic_capacity())
+                + u64::from(config_second_market.offer_cell_prepared_fee_capacity());
+            assert!(
+                counter_offer_capacity == expected_capacity,
+                Error::OfferCellCapacityError,
+                "The counter offer should contain the basic capacity and the prepared fee.(expected: {}, current: {})",
+                expected_capacity,
+                counter_offer_capacity
+            );
+
+            debug!("Verify if the OfferCell being countered is live and bidding on the account.");
+
+            let offer_cell = match util::find_cell_dep_by_out_point(args)? {
+                Some(index) => index,
+                None => {
+                    warn!(
+                        "The OfferCell being countered should be put in cell_deps.(out_point: 0x{})",
+                        util::hex_string(args)
+                    );
+                    return Err(Error::OfferCellCounterOfferError);
+                }
+            };
+            let offer_cell_type_id = config_main.type_id_table().offer_cell();
+            let dep_offer_cells = util::find_cells_by_type_id(ScriptType::Type, offer_cell_type_id, Source::CellDep)?;
+            assert!(
+                dep_offer_cells.contains(&offer_cell),
+                Error::OfferCellCounterOfferError,
+                "CellDeps[{}] The cell being countered should be an OfferCell.",
+                offer_cell
+            );
+
+            let offer_cell_witness;
+            let offer_cell_witness_reader;
+            parse_witness!(
+                offer_cell_witness,
+                offer_cell_witness_reader,
+                parser,
+                offer_cell,
+                Source::CellDep,
+                DataType::OfferCellData,
+                OfferCellData
+            );
+
+            let offer_cell_data = high_level::load_cell_data(offer_cell, Source::CellDep)?;
+            let offer_collection_type = data_parser::offer_cell::get_collection_type(&offer_cell_data);
+            assert!(
+                offer_collection_type != Some(OfferCollectionType::Counter as u8),
+                Error::OfferCellCounterOfferError,
+                "CellDeps[{}] A counter offer can not be countered.",
+                offer_cell
+            );
+
+            let offer_expired_at = data_parser::offer_cell::get_expired_at(&offer_cell_data);
+            assert!(
+                offer_expired_at == 0 || timestamp <= offer_expired_at,
+                Error::OfferCellExpirationError,
+                "CellDeps[{}] The OfferCell being countered has been expired.(expired_at: {}, current: {})",
+                offer_cell,
+                offer_expired_at,
+                timestamp
+            );
+
+            if offer_collection_type.is_some() {
+                verify_account_in_collection(
+                    &parser,
+                    &offer_cell_data,
+                    &input_account_cell_witness_reader,
+                    &account_cell_data,
+                )?;
+            } else {
+                let expected_account = offer_cell_witness_reader.account().raw_data();
+                assert!(
+                    expected_account == current_account,
+                    Error::OfferCellAccountMismatch,
+                    "The OfferCell being countered should bid on {}, but {} found.",
+                    String::from_utf8(current_account.to_vec()).unwrap(),
+                    String::from_utf8(expected_account.to_vec()).unwrap()
+                );
+            }
+        }
+        b"recycle_expired_offer_by_keeper" => {
+            // CAREFUL! This action is intentionally ignoring EIP712 verification, because it can be pushed by anyone.
+
+            let timestamp = util::load_oracle_data(OracleCellType::Time)?;
+
+            parser.parse_cell()?;
+            let config_main = parser.configs.main()?;
+            let config_second_market = parser.configs.secondary_market()?;
+
+            assert!(
+                input_cells.len() >= 1 && output_cells.len() == 0,
+                Error::InvalidTransactionStructure,
+                "There should be at least 1 OfferCell in inputs."
+            );
+
+            debug!("Verify if all OfferCells in inputs are expired and have the same lock script with the first OfferCell.");
+
+            let expected_lock_hash = high_level::load_cell_lock_hash(input_cells[0], Source::Input)?;
+            let prepared_fee = u64::from(config_second_market.offer_cell_prepared_fee_capacity());
+            let mut expected_refund = 0;
+            for i in input_cells.iter() {
+                let lock_hash = high_level::load_cell_lock_hash(*i, Source::Input)?;
+                assert!(
+                    expected_lock_hash == lock_hash,
+                    Error::InvalidTransactionStructure,
+                    "Inputs[{}] The OfferCell should has the same lock script with others.",
+                    i
+                );
+
+                let data = high_level::load_cell_data(*i, Source::Input)?;
+                // The counter offer locks an AccountCell of its owner, so it can only be canceled by the owner or recycled
+                // together with the AccountCell when the account is expired.
+                assert!(
+                    data_parser::offer_cell::get_collection_type(&data) != Some(OfferCollectionType::Counter as u8),
+                    Error::OfferCellCounterOfferError,
+                    "Inputs[{}] The counter offer can not be recycled by keepers.",
+                    i
+                );
+
+                let expired_at = data_parser::offer_cell::get_expired_at(&data);
+                assert!(
+                    expired_at != 0 && timestamp > expired_at,
+                    Error::OfferCellExpirationError,
+                    "Inputs[{}] The OfferCell is still not expired.(expired_at: {}, current: {})",
+                    i,
+                    expired_at,
+                    timestamp
+                );
+
+                let witness;
+                let witness_reader;
+                parse_witness!(
+                    witness,
+                    witness_reader,
+                    parser,
+                    *i,
+                    Source::Input,
+                    DataType::OfferCellData,
+                    OfferCellData
+                );
+
+                // The keeper can only take the fee left in the OfferCell, and no more than the prepared fee.
+                let capacity = high_level::load_cell_capacity(*i, Source::Input)?;
+                let locked_capacity = u64::from(witness_reader.price());
+                let fee_left = match capacity.checked_sub(locked_capacity) {
+                    Some(fee_left) => fee_left,
+                    None => {
+                        warn!(
+                            "Inputs[{}] The OfferCell.capacity should be more than or equal to the capacity it locked.(capacity: {}, locked: {})",
+                            i,
+                            capacity,
+                            locked_capacity
+                        );
+                        return Err(Error::OfferCellCapacityError);
+                    }
+                };
+                let fee = if fee_left > prepared_fee {
+                    prepared_fee
+                } else {
+                    fee_left
+                };
+                expected_refund += capacity - fee;
+            }
+
+            debug!("Verify if all capacity have been refund to the offerer correctly.");
+
+            let expected_lock = high_level::load_cell_lock(input_cells[0], Source::Input)?;
+            verifiers::misc::verify_user_get_change(config_main, expected_lock.as_reader(), expected_refund)?;
+        }
+        b"accept_offer" | b"accept_counter_offer" => {
+            let timestamp = util::load_oracle_data(OracleCellType::Time)?;
+
+            parser.parse_cell()?;
+
+            if action == b"accept_offer" {
+                verify_eip712_hashes(&parser, accept_offer_to_semantic)?;
+            } else {
+                verify_eip712_hashes(&parser, accept_counter_offer_to_semantic)?;
+            }
+
+            let config_main = parser.configs.main()?;
+            let config_account = parser.configs.account()?;
+            let config_secondary_market = parser.configs.secondary_market()?;
+
+            // When accepting an offer, the seller sends the transaction, so the OfferCell comes first. When accepting a counter offer,
+            // the buyer sends the transaction, so the AccountCell and the counter offer which are belong to the seller comes first.
+            let (offer_cell, counter_offer_cell_opt, expected_account_cell) = if action == b"accept_offer" {
+                verifiers::common::verify_removed_cell_in_correct_position(
+                    "OfferCell",
+                    &input_cells,
+                    &output_cells,
+                    Some(0),
+                )?;
+
+                (input_cells[0], None, 1)
+            } else {
+                assert!(
+                    input_cells.len() == 2 && input_cells[0] == 1 && input_cells[1] == 2 && output_cells.is_empty(),
+                    Error::InvalidTransactionStructure,
+                    "There should be the counter offer at inputs[1] and the OfferCell at inputs[2]."
+                );
+
+                (input_cells[1], Some(input_cells[0]), 0)
+            };
+
+            let account_cell_type_id = config_main.type_id_table().account_cell();
+            let (input_account_cells, output_account_cells) =
+                util::find_cells_by_type_id_in_inputs_and_outputs(ScriptType::Type, account_cell_type_id)?;
+
+            assert!(
+                input_account_cells.len() == 1 && output_account_cells.len() == 1,
+                Error::InvalidTransactionStructure,
+                "There should be 1 AccountCell in both inputs and outputs."
+            );
+            assert!(
+                input_account_cells[0] == expected_account_cell && output_account_cells[0] == 0,
                 Error::InvalidTransactionStructure,
-                "The AccountCell should only appear in inputs[1] and outputs[0]."
+                "The AccountCell should only appear in inputs[{}] and outputs[0].",
+                expected_account_cell
             );
 
             let input_account_cell_witness: Box<dyn AccountCellDataMixer>;
@@ -317,23 +740,27 @@ pub fn main() -> Result<(), Error> {
                 Source::Output
             );
 
-            let buyer_lock = high_level::load_cell_lock(input_cells[0], Source::Input)?;
+            let buyer_lock = high_level::load_cell_lock(offer_cell, Source::Input)?;
             let seller_lock = util::derive_owner_lock_from_cell(input_account_cells[0], Source::Input)?;
 
-            let cells = [input_cells.clone(), input_account_cells.clone()].concat();
+            // Only the buyer who accepts a counter offer needs to pay more than the OfferCell.
+            let buyer_balance_cells = if counter_offer_cell_opt.is_some() {
+                util::find_balance_cells(config_main, buyer_lock.as_reader(), Source::Input)?
+            } else {
+                Vec::new()
+            };
+            let cells = [
+                input_cells.clone(),
+                input_account_cells.clone(),
+                buyer_balance_cells.clone(),
+            ]
+            .concat();
             verifiers::misc::verify_no_more_cells_with_same_lock(buyer_lock.as_reader(), &cells, Source::Input)?;
             verifiers::misc::verify_no_more_cells_with_same_lock(seller_lock.as_reader(), &cells, Source::Input)?;
 
             debug!("Verify if the AccountCell is transferred properly.");
 
             verifiers::account_cell::verify_account_expiration(config_account, input_account_cells[0], timestamp)?;
-            verifiers::account_cell::verify_account_cell_status(
-                &input_account_cell_witness_reader,
-                AccountStatus::Normal,
-                input_account_cells[0],
-                Source::Input,
-            )?;
-
             verifiers::account_cell::verify_account_capacity_not_decrease(
                 input_account_cells[0],
                 output_account_cells[0],
@@ -343,13 +770,37 @@ pub fn main() -> Result<(), Error> {
                 output_account_cells[0],
                 vec![],
             )?;
-            verifiers::account_cell::verify_account_witness_consistent(
-                input_account_cells[0],
-                output_account_cells[0],
-                &input_account_cell_witness_reader,
-                &output_account_cell_witness_reader,
-                vec![],
-            )?;
+
+            if counter_offer_cell_opt.is_some() {
+                // The AccountCell has been locked in the Selling status by the counter offer, it is unlocked when transferred.
+                verifiers::account_cell::verify_account_witness_consistent(
+                    input_account_cells[0],
+                    output_account_cells[0],
+                    &input_account_cell_witness_reader,
+                    &output_account_cell_witness_reader,
+                    vec!["status"],
+                )?;
+                verifiers::account_cell::verify_account_cell_status_update_correctly(
+                    &input_account_cell_witness_reader,
+                    &output_account_cell_witness_reader,
+                    AccountStatus::Selling,
+                    AccountStatus::Normal,
+                )?;
+            } else {
+                verifiers::account_cell::verify_account_cell_status(
+                    &input_account_cell_witness_reader,
+                    AccountStatus::Normal,
+                    input_account_cells[0],
+                    Source::Input,
+                )?;
+                verifiers::account_cell::verify_account_witness_consistent(
+                    input_account_cells[0],
+                    output_account_cells[0],
+                    &input_account_cell_witness_reader,
+                    &output_account_cell_witness_reader,
+                    vec![],
+                )?;
+            }
 
             let new_owner_lock = high_level::load_cell_lock(output_account_cells[0], Source::Output)?;
             assert!(
@@ -371,29 +822,108 @@ pub fn main() -> Result<(), Error> {
                 input_offer_cell_witness,
                 input_offer_cell_witness_reader,
                 parser,
-                input_cells[0],
+                offer_cell,
                 Source::Input,
                 DataType::OfferCellData,
                 OfferCellData
             );
 
-            let expected_account = input_offer_cell_witness_reader.account().raw_data();
-
+            let input_offer_cell_data = high_level::load_cell_data(offer_cell, Source::Input)?;
+            let expired_at = data_parser::offer_cell::get_expired_at(&input_offer_cell_data);
             assert!(
-                expected_account == current_account,
-                Error::OfferCellAccountMismatch,
-                "The account should be {}, but {} found.",
-                String::from_utf8(expected_account.to_vec()).unwrap(),
-                String::from_utf8(current_account.to_vec()).unwrap()
+                expired_at == 0 || timestamp <= expired_at,
+                Error::OfferCellExpirationError,
+                "The OfferCell has been expired.(expired_at: {}, current: {})",
+                expired_at,
+                timestamp
             );
 
-            debug!("Verify if the profit is distribute correctly.");
+            if data_parser::offer_cell::get_collection_type(&input_offer_cell_data).is_some() {
+                verify_account_in_collection(
+                    &parser,
+                    &input_offer_cell_data,
+                    &input_account_cell_witness_reader,
+                    &account_cell_data,
+                )?;
+            } else {
+                let expected_account = input_offer_cell_witness_reader.account().raw_data();
+
+                assert!(
+                    expected_account == current_account,
+                    Error::OfferCellAccountMismatch,
+                    "The account should be {}, but {} found.",
+                    String::from_utf8(expected_account.to_vec()).unwrap(),
+                    String::from_utf8(current_account.to_vec()).unwrap()
+                );
+            }
 
             let inviter_lock = input_offer_cell_witness_reader.inviter_lock();
             let channel_lock = input_offer_cell_witness_reader.channel_lock();
-            let price = u64::from(input_offer_cell_witness_reader.price());
-            let offer_cell_capacity = high_level::load_cell_capacity(input_cells[0], Source::Input)?;
             let common_fee = u64::from(config_secondary_market.common_fee());
+            let mut price = u64::from(input_offer_cell_witness_reader.price());
+            let mut offer_cell_capacity = high_level::load_cell_capacity(offer_cell, Source::Input)?;
+            let mut seller_refund = 0;
+
+            if let Some(counter_offer_cell) = counter_offer_cell_opt {
+                debug!("Verify if the counter offer is made by the seller for the OfferCell.");
+
+                verify_counter_offer_of_account_cell(&parser, counter_offer_cell, input_account_cells[0])?;
+
+                let counter_offer_data = high_level::load_cell_data(counter_offer_cell, Source::Input)?;
+                let offer_cell_out_point = high_level::load_input_out_point(offer_cell, Source::Input)?;
+                assert!(
+                    data_parser::offer_cell::get_collection_args(&counter_offer_data)
+                        == Some(offer_cell_out_point.as_slice()),
+                    Error::OfferCellCounterOfferError,
+                    "The counter offer should be made for the OfferCell at inputs[{}].",
+                    offer_cell
+                );
+
+                let expired_at = data_parser::offer_cell::get_expired_at(&counter_offer_data);
+                assert!(
+                    expired_at == 0 || timestamp <= expired_at,
+                    Error::OfferCellExpirationError,
+                    "The counter offer has been expired.(expired_at: {}, current: {})",
+                    expired_at,
+                    timestamp
+                );
+
+                let counter_offer_witness;
+                let counter_offer_witness_reader;
+                parse_witness!(
+                    counter_offer_witness,
+                    counter_offer_witness_reader,
+                    parser,
+                    counter_offer_cell,
+                    Source::Input,
+                    DataType::OfferCellData,
+                    OfferCellData
+                );
+
+                debug!("Verify if the buyer get their change properly.");
+
+                price = u64::from(counter_offer_witness_reader.price());
+                offer_cell_capacity += util::load_cells_capacity(&buyer_balance_cells, Source::Input)?;
+                seller_refund = high_level::load_cell_capacity(counter_offer_cell, Source::Input)?;
+
+                assert!(
+                    offer_cell_capacity >= price,
+                    Error::OfferCellCapacityError,
+                    "The buyer should pay at least {} shannon for the counter offer.(current: {})",
+                    price,
+                    offer_cell_capacity
+                );
+
+                if offer_cell_capacity > price + common_fee {
+                    verifiers::misc::verify_user_get_change(
+                        config_main,
+                        buyer_lock.as_reader(),
+                        offer_cell_capacity - price - common_fee,
+                    )?;
+                }
+            }
+
+            debug!("Verify if the profit is distribute correctly.");
 
             verify_profit_distribution(
                 &parser,
@@ -404,14 +934,89 @@ pub fn main() -> Result<(), Error> {
                 price,
                 common_fee,
                 offer_cell_capacity,
+                seller_refund,
             )?;
         }
+        b"force_recover_account_status" => {
+            // CAREFUL! This action is intentionally ignoring EIP712 verification, because it can be pushed by anyone.
+
+            util::require_type_script(
+                &parser,
+                TypeScript::AccountCellType,
+                Source::Input,
+                Error::InvalidTransactionStructure,
+            )?;
+
+            assert!(
+                input_cells.len() == 1 && output_cells.len() == 0,
+                Error::InvalidTransactionStructure,
+                "There should be only 1 counter offer in inputs."
+            );
+
+            // The account-cell-type will verify if the counter offer belongs to the expired AccountCell and the refund.
+            let data = high_level::load_cell_data(input_cells[0], Source::Input)?;
+            assert!(
+                data_parser::offer_cell::get_collection_type(&data) == Some(OfferCollectionType::Counter as u8),
+                Error::OfferCellCounterOfferError,
+                "Inputs[{}] Only the counter offer can be recycled with the AccountCell.",
+                input_cells[0]
+            );
+        }
         _ => return Err(Error::ActionNotSupported),
     }
 
     Ok(())
 }
 
+fn verify_counter_offer_of_account_cell(
+    parser: &WitnessesParser,
+    counter_offer_cell: usize,
+    account_cell: usize,
+) -> Result<(), Error> {
+    let counter_offer_data = high_level::load_cell_data(counter_offer_cell, Source::Input)?;
+    assert!(
+        data_parser::offer_cell::get_collection_type(&counter_offer_data) == Some(OfferCollectionType::Counter as u8),
+        Error::OfferCellCounterOfferError,
+        "Inputs[{}] The cell should be a counter offer.",
+        counter_offer_cell
+    );
+
+    let seller_lock = util::derive_owner_lock_from_cell(account_cell, Source::Input)?;
+    let counter_offer_lock = high_level::load_cell_lock(counter_offer_cell, Source::Input)?;
+    assert!(
+        util::is_entity_eq(&seller_lock, &counter_offer_lock),
+        Error::OfferCellCounterOfferError,
+        "The counter offer should be made by the owner of the AccountCell.(expected: {}, current: {})",
+        seller_lock,
+        counter_offer_lock
+    );
+
+    let counter_offer_witness;
+    let counter_offer_witness_reader;
+    parse_witness!(
+        counter_offer_witness,
+        counter_offer_witness_reader,
+        parser,
+        counter_offer_cell,
+        Source::Input,
+        DataType::OfferCellData,
+        OfferCellData
+    );
+
+    let account_cell_data = high_level::load_cell_data(account_cell, Source::Input)?;
+    let current_account = data_parser::account_cell::get_account(&account_cell_data);
+    let counter_offer_account = counter_offer_witness_reader.account().raw_data();
+    assert!(
+        counter_offer_account == current_account,
+        Error::OfferCellAccountMismatch,
+        "The account of the counter offer should be {}, but {} found.",
+        String::from_utf8(current_account.to_vec()).unwrap(),
+        String::from_utf8(counter_offer_account.to_vec()).unwrap()
+    );
+
+    Ok(())
+}
+
 fn verify_message_length(
     config_second_market: ConfigCellSecondaryMarketReader,
     offer_cell_witness: OfferCellDataReader,
@@ -429,6 +1034,148 @@ fn verify_message_length(
     Ok(())
 }
 
+fn verify_expired_at(offer_cell_data: &[u8]) -> Result<(), Error> {
+    let expired_at = data_parser::offer_cell::get_expired_at(offer_cell_data);
+    if expired_at == 0 {
+        debug!("The OfferCell will never expire, skip verifying expired_at.");
+        return Ok(());
+    }
+
+    let timestamp = util::load_oracle_data(OracleCellType::Time)?;
+    assert!(
+        expired_at > timestamp,
+        Error::OfferCellExpirationError,
+        "The OfferCell.data.expired_at should be in the future.(expired_at: {}, current: {})",
+        expired_at,
+        timestamp
+    );
+
+    Ok(())
+}
+
+fn verify_collection(offer_cell_data: &[u8], offer_cell_witness: OfferCellDataReader) -> Result<(), Error> {
+    debug!("Verify if the collection of accounts which the OfferCell is bidding on is valid.");
+
+    assert!(
+        offer_cell_witness.account().is_empty(),
+        Error::OfferCellCollectionError,
+        "The OfferCell.witness.account should be empty when the OfferCell is bidding on a collection of accounts."
+    );
+
+    let collection_type = data_parser::offer_cell::get_collection_type(offer_cell_data).unwrap();
+    let args = data_parser::offer_cell::get_collection_args(offer_cell_data).unwrap();
+    if collection_type == OfferCollectionType::Pattern as u8 {
+        assert!(
+            args.len() == 2,
+            Error::OfferCellCollectionError,
+            "The args of OfferCollectionType::Pattern should be 2 bytes.(current: {})",
+            args.len()
+        );
+
+        let char_set = args[0];
+        assert!(
+            char_set == OFFER_COLLECTION_ANY_CHAR_SET || (char_set as usize) < CHAR_SET_LENGTH,
+            Error::OfferCellCollectionError,
+            "The char set of OfferCollectionType::Pattern is undefined.(current: {})",
+            char_set
+        );
+    } else if collection_type == OfferCollectionType::MerkleRoot as u8 {
+        assert!(
+            args.len() == 32,
+            Error::OfferCellCollectionError,
+            "The args of OfferCollectionType::MerkleRoot should be 32 bytes.(current: {})",
+            args.len()
+        );
+    } else {
+        warn!(
+            "The collection type of OfferCell is undefined.(current: {})",
+            collection_type
+        );
+        return Err(Error::OfferCellCollectionError);
+    }
+
+    Ok(())
+}
+
+fn verify_account_in_collection<'a>(
+    parser: &WitnessesParser,
+    offer_cell_data: &[u8],
+    account_cell_witness_reader: &Box<dyn AccountCellDataReaderMixer + 'a>,
+    account_cell_data: &[u8],
+) -> Result<(), Error> {
+    let collection_type = data_parser::offer_cell::get_collection_type(offer_cell_data).unwrap();
+    let args = data_parser::offer_cell::get_collection_args(offer_cell_data).unwrap();
+
+    if collection_type == OfferCollectionType::Pattern as u8 {
+        let account_chars = account_cell_witness_reader.account();
+        let (char_set, length) = (args[0], args[1]);
+
+        assert!(
+            length == OFFER_COLLECTION_ANY_LENGTH || account_chars.len() == length as usize,
+            Error::OfferCellAccountMismatch,
+            "The account should have {} characters, but {} found.",
+            length,
+            account_chars.len()
+        );
+
+        if char_set != OFFER_COLLECTION_ANY_CHAR_SET {
+            for account_char in account_chars.iter() {
+                let current_char_set = u32::from(account_char.char_set_name());
+                assert!(
+                    current_char_set == char_set as u32,
+                    Error::OfferCellAccountMismatch,
+                    "The account should only contain characters of CharSet[{}], but CharSet[{}] found.",
+                    char_set,
+                    current_char_set
+                );
+            }
+        }
+    } else if collection_type == OfferCollectionType::MerkleRoot as u8 {
+        // The proof is optional in params, because it is empty when the tree has only one leaf.
+        let proof: &[u8] = if parser.params.len() > 1 {
+            parser.params[0].raw_data()
+        } else {
+            &[]
+        };
+        let account_id = data_parser::account_cell::get_id(account_cell_data);
+        let leaf = util::blake2b_256(account_id);
+
+        assert!(
+            util::is_leaf_in_merkle_tree(args, leaf, proof),
+            Error::OfferCellAccountMismatch,
+            "The account ID 0x{} can not be proved to be in the collection.(root: 0x{})",
+            util::hex_string(account_id),
+            util::hex_string(args)
+        );
+    } else {
+        warn!(
+            "The OfferCell is not bidding on a collection of accounts.(type: {})",
+            collection_type
+        );
+        return Err(Error::OfferCellCollectionError);
+    }
+
+    Ok(())
+}
+
+fn verify_min_price(
+    config_second_market: ConfigCellSecondaryMarketReader,
+    offer_cell_witness: OfferCellDataReader,
+) -> Result<(), Error> {
+    let basic_capacity = u64::from(config_second_market.offer_cell_basic_capacity());
+    let current_price = u64::from(offer_cell_witness.price());
+
+    assert!(
+        current_price >= basic_capacity,
+        Error::OfferCellCapacityError,
+        "The OfferCell.price should be more than or equal to the basic capacity.(current_price: {}, basic_capacity: {})",
+        current_price,
+        basic_capacity
+    );
+
+    Ok(())
+}
+
 fn verify_price(
     config_second_market: ConfigCellSecondaryMarketReader,
     offer_cell_witness: OfferCellDataReader,
@@ -436,7 +1183,6 @@ fn verify_price(
     source: Source,
     exist_fee: Option<u64>,
 ) -> Result<(), Error> {
-    let basic_capacity = u64::from(config_second_market.offer_cell_basic_capacity());
     let fee = if let Some(exist_fee) = exist_fee {
         exist_fee
     } else {
@@ -446,13 +1192,8 @@ fn verify_price(
     let current_price = u64::from(offer_cell_witness.price());
     let current_capacity = high_level::load_cell_capacity(index, source)?;
 
-    assert!(
-        current_price >= basic_capacity,
-        Error::OfferCellCapacityError,
-        "The OfferCell.price should be more than or equal to the basic capacity.(current_price: {}, basic_capacity: {})",
-        current_price,
-        basic_capacity
-    );
+    verify_min_price(config_second_market, offer_cell_witness)?;
+
     assert!(
         current_capacity == current_price + fee,
         Error::OfferCellCapacityError,
@@ -473,6 +1214,7 @@ fn verify_profit_distribution(
     price: u64,
     common_fee: u64,
     offer_cell_capacity: u64,
+    seller_refund: u64,
 ) -> Result<(), Error> {
     let config_profit_rate = parser.configs.profit_rate()?;
     let default_script = Script::default();
@@ -522,7 +1264,7 @@ fn verify_profit_distribution(
     } else {
         // If the OfferCell does not contain any fee, the seller should get their profit with a bit of fee has been took.
         profit_of_seller - common_fee
-    };
+    } + seller_refund;
     verifiers::misc::verify_user_get_change(config_main, seller_lock_reader.into(), expected_capacity)?;
 
     verifiers::income_cell::verify_income_cells(parser, profit_map)?;
@@ -552,15 +1294,37 @@ fn offer_to_semantic(parser: &WitnessesParser, source: Source) -> Result<(String
         OfferCellData
     );
 
-    let account = String::from_utf8(witness_reader.account().raw_data().to_vec()).map_err(|_| {
-        warn!("EIP712 decoding OfferCellData failed");
-        Error::WitnessEntityDecodingError
-    })?;
+    let data = util::load_cell_data(offer_cells[0], source)?;
+    let account = match data_parser::offer_cell::get_collection_type(&data) {
+        Some(collection_type) if collection_type != OfferCollectionType::Counter as u8 => {
+            let args = data_parser::offer_cell::get_collection_args(&data).unwrap();
+            collection_to_semantic(collection_type, args)
+        }
+        _ => String::from_utf8(witness_reader.account().raw_data().to_vec()).map_err(|_| {
+            warn!("EIP712 decoding OfferCellData failed");
+            Error::WitnessEntityDecodingError
+        })?,
+    };
     let amount = to_semantic_capacity(u64::from(witness_reader.price()));
 
     Ok((account, amount))
 }
 
+fn collection_to_semantic(collection_type: u8, args: &[u8]) -> String {
+    if collection_type == OfferCollectionType::Pattern as u8 && args.len() == 2 {
+        let mut ret = String::from("ANY ACCOUNT");
+        if args[1] != OFFER_COLLECTION_ANY_LENGTH {
+            ret += &format!(" OF {} CHARACTERS", args[1]);
+        }
+        if args[0] != OFFER_COLLECTION_ANY_CHAR_SET {
+            ret += &format!(" IN CHARSET {}", args[0]);
+        }
+        ret
+    } else {
+        format!("ANY ACCOUNT IN 0x{}", util::hex_string(args))
+    }
+}
+
 fn make_offer_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
     let (account, amount) = offer_to_semantic(parser, Source::Output)?;
     Ok(format!("MAKE AN OFFER ON {} WITH {}", account, amount))
@@ -586,3 +1350,14 @@ fn accept_offer_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
     let (account, amount) = offer_to_semantic(parser, Source::Input)?;
     Ok(format!("ACCEPT THE OFFER ON {} WITH {}", account, amount))
 }
+
+fn counter_offer_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
+    let (account, amount) = offer_to_semantic(parser, Source::Output)?;
+    Ok(format!("MAKE A COUNTER OFFER ON {} WITH {}", account, amount))
+}
+
+fn accept_counter_offer_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
+    // The counter offer is always before the OfferCell, so it will be found first.
+    let (account, amount) = offer_to_semantic(parser, Source::Input)?;
+    Ok(format!("ACCEPT THE COUNTER OFFER ON {} WITH {}", account, amount))
+}
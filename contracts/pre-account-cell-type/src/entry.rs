@@ -2,9 +2,10 @@ use alloc::{collections::BTreeMap, string::String};
 use ckb_std::{ckb_constants::Source, high_level};
 use core::{convert::TryInto, result::Result};
 use das_core::{
-    assert, constants::*, data_parser, debug, error::Error, util, verifiers, witness_parser::WitnessesParser,
+    assert, constants::*, data_parser, debug, error::Error, util, verifiers, warn, witness_parser::WitnessesParser,
 };
-use das_types::{packed::*, prelude::*};
+use das_map::map::Map;
+use das_types::{mixer::AccountCellDataMixer, packed::*, prelude::*};
 
 pub fn main() -> Result<(), Error> {
     debug!("====== Running pre-account-cell-type ======");
@@ -121,6 +122,10 @@ pub fn main() -> Result<(), Error> {
             let timestamp = util::load_oracle_data(OracleCellType::Time)?;
             verify_created_at(timestamp, pre_account_cell_witness_reader)?;
 
+            if let Some(sponsor_lock) = data_parser::pre_account_cell::get_sponsor_lock(&data) {
+                verify_sponsor(&parser, pre_account_cell_witness_reader, sponsor_lock, capacity)?;
+            }
+
             debug!("Verify if account is available for registration for now ...");
 
             let cells_with_super_lock =
@@ -162,6 +167,229 @@ pub fn main() -> Result<(), Error> {
             let chars_reader = pre_account_cell_witness_reader.account();
             verifiers::account_cell::verify_account_chars(&parser, chars_reader)?;
             verifiers::account_cell::verify_account_chars_max_length(&parser, chars_reader)?;
+            verifiers::account_cell::verify_account_not_confusable(&parser, chars_reader, &[])?;
+        }
+        b"register_account" => {
+            parser.parse_cell()?;
+            let config_account = parser.configs.account()?;
+            let config_main = parser.configs.main()?;
+            let config_profit_rate = parser.configs.profit_rate()?;
+
+            let timestamp = util::load_oracle_data(OracleCellType::Time)?;
+            let (input_cells, output_cells) = util::load_self_cells_in_inputs_and_outputs()?;
+
+            assert!(
+                input_cells.len() == 1 && output_cells.len() == 0,
+                Error::InvalidTransactionStructure,
+                "There should be only 1 PreAccountCell in inputs and none in outputs.(in_inputs: {}, in_outputs: {})",
+                input_cells.len(),
+                output_cells.len()
+            );
+
+            let (input_account_cells, output_account_cells) = util::find_cells_by_type_id_in_inputs_and_outputs(
+                ScriptType::Type,
+                config_main.type_id_table().account_cell(),
+            )?;
+
+            assert!(
+                input_account_cells.len() == 1 && output_account_cells.len() == 2,
+                Error::InvalidTransactionStructure,
+                "There should be 1 AccountCell in inputs and 2 AccountCells in outputs.(in_inputs: {}, in_outputs: {})",
+                input_account_cells.len(),
+                output_account_cells.len()
+            );
+
+            // The previous AccountCell is kept at the same position, the new AccountCell follows it.
+            let pre_account_cell_index = input_cells[0];
+            let prev_input_index = input_account_cells[0];
+            let prev_output_index = output_account_cells[0];
+            let new_output_index = output_account_cells[1];
+
+            let pre_account_cell_data = util::load_cell_data(pre_account_cell_index, Source::Input)?;
+            let pre_account_cell_witness =
+                util::parse_pre_account_cell_witness(&parser, pre_account_cell_index, Source::Input)?;
+            let pre_account_cell_witness_reader = pre_account_cell_witness.as_reader();
+
+            // For protecting register, do not allow PreAccountCell exists longer than PRE_ACCOUNT_CELL_TIMEOUT to be
+            // registered.
+            let created_at = u64::from(pre_account_cell_witness_reader.created_at());
+            assert!(
+                timestamp <= created_at + PRE_ACCOUNT_CELL_TIMEOUT,
+                Error::PreRegisterPreAccountCellExpired,
+                "The PreAccountCell has been expired.(created_at: {}, expired_at: {})",
+                created_at,
+                created_at + PRE_ACCOUNT_CELL_TIMEOUT
+            );
+
+            let account_id = data_parser::pre_account_cell::get_id(&pre_account_cell_data);
+
+            debug!("Check if there is no older PreAccountCell which wants the same account in cell_deps.");
+
+            // Just like in proposals, the keeper should not skip any older PreAccountCell which wants the same account.
+            // CAREFUL! The cell_deps are chosen by the keeper, so an older PreAccountCell which is omitted can not be
+            // detected here.
+            let competing_cells = util::find_cells_by_type_id(
+                ScriptType::Type,
+                config_main.type_id_table().pre_account_cell(),
+                Source::CellDep,
+            )?;
+            for competing_cell in competing_cells {
+                let competing_cell_data = util::load_cell_data(competing_cell, Source::CellDep)?;
+                let competing_account_id = data_parser::pre_account_cell::get_id(&competing_cell_data);
+                assert!(
+                    competing_account_id == account_id,
+                    Error::InvalidTransactionStructure,
+                    "The competing PreAccountCell should want the same account. (competing_cell: {:?}[{}], account_id: 0x{})",
+                    Source::CellDep,
+                    competing_cell,
+                    util::hex_string(competing_account_id)
+                );
+
+                let competing_witness = util::parse_pre_account_cell_witness(&parser, competing_cell, Source::CellDep)?;
+                let competing_created_at = u64::from(competing_witness.as_reader().created_at());
+                if timestamp > competing_created_at + PRE_ACCOUNT_CELL_TIMEOUT {
+                    debug!(
+                        "The competing PreAccountCell has been expired, skip it. (competing_cell: {:?}[{}])",
+                        Source::CellDep,
+                        competing_cell
+                    );
+                    continue;
+                }
+
+                assert!(
+                    created_at <= competing_created_at,
+                    Error::PreRegisterPreAccountCellNotEarliest,
+                    "The PreAccountCell is not the earliest one of its account.(created_at: {}, competing_cell: {:?}[{}], created_at: {})",
+                    created_at,
+                    Source::CellDep,
+                    competing_cell,
+                    competing_created_at
+                );
+            }
+
+            debug!("Check if no account visually identical to the new account has been registered since pre_register.");
+
+            verifiers::account_cell::verify_account_not_confusable(
+                &parser,
+                pre_account_cell_witness_reader.account(),
+                &[],
+            )?;
+
+            debug!("Check if the new account can be inserted right after the previous AccountCell.");
+
+            let prev_input_data = util::load_cell_data(prev_input_index, Source::Input)?;
+            let prev_account_id = data_parser::account_cell::get_id(&prev_input_data);
+            let original_next = data_parser::account_cell::get_next(&prev_input_data);
+
+            assert!(
+                prev_account_id < account_id && account_id < original_next,
+                Error::PreRegisterPrevAccountCellError,
+                "The account ID should be between the id and the next of inputs[{}].(id: 0x{}, account_id: 0x{}, next: 0x{})",
+                prev_input_index,
+                util::hex_string(prev_account_id),
+                util::hex_string(account_id),
+                util::hex_string(original_next)
+            );
+
+            debug!("Check if the previous AccountCell only links to the new AccountCell.");
+
+            let prev_output_data = util::load_cell_data(prev_output_index, Source::Output)?;
+
+            util::is_cell_capacity_equal((prev_input_index, Source::Input), (prev_output_index, Source::Output))?;
+            util::is_cell_lock_equal((prev_input_index, Source::Input), (prev_output_index, Source::Output))?;
+            verifiers::pre_account_cell::is_old_account_cell_data_consistent(0, &prev_output_data, &prev_input_data)?;
+            verifiers::pre_account_cell::is_next_correct(0, &prev_output_data, account_id)?;
+
+            let prev_input_witness = util::parse_account_cell_witness(&parser, prev_input_index, Source::Input)?;
+            let prev_input_witness_reader = prev_input_witness.as_reader();
+            let prev_output_witness = util::parse_account_cell_witness(&parser, prev_output_index, Source::Output)?;
+            let prev_output_witness_reader = prev_output_witness.as_reader();
+
+            verifiers::account_cell::verify_account_witness_consistent(
+                prev_input_index,
+                prev_output_index,
+                &prev_input_witness_reader,
+                &prev_output_witness_reader,
+                vec![],
+            )?;
+
+            debug!("Check if the PreAccountCell is converted to the new AccountCell correctly.");
+
+            let new_output_data = util::load_cell_data(new_output_index, Source::Output)?;
+            let new_output_witness = util::parse_account_cell_witness(&parser, new_output_index, Source::Output)?;
+            let new_output_witness_reader = if let Ok(reader) = new_output_witness.as_reader().try_into_latest() {
+                reader
+            } else {
+                warn!("The AccouneCell in outputs is required to be latest data structure.");
+                return Err(Error::InvalidTransactionStructure);
+            };
+
+            let account_name_storage = data_parser::account_cell::get_account(&new_output_data).len() as u64;
+            let total_capacity = high_level::load_cell_capacity(pre_account_cell_index, Source::Input)?;
+            let lock = high_level::load_cell_lock(new_output_index, Source::Output)?;
+            let storage_capacity = util::calc_account_storage_capacity(
+                config_account,
+                account_name_storage,
+                lock.args().as_reader().into(),
+            );
+            let profit = match total_capacity.checked_sub(storage_capacity) {
+                Some(profit) => profit,
+                None => {
+                    warn!(
+                        "The capacity of the PreAccountCell is not enough for the storage of the AccountCell.(total_capacity: {}, storage_capacity: {})",
+                        total_capacity,
+                        storage_capacity
+                    );
+                    return Err(Error::PreRegisterCKBInsufficient);
+                }
+            };
+
+            debug!(
+                "The profit in PreAccountCell is: {}(profit) = {}(total_capacity) - {}(storage_capacity)",
+                profit, total_capacity, storage_capacity
+            );
+
+            verifiers::pre_account_cell::is_cell_capacity_correct(0, new_output_index, storage_capacity)?;
+            verifiers::pre_account_cell::is_new_account_cell_lock_correct(
+                0,
+                pre_account_cell_index,
+                pre_account_cell_witness_reader,
+                new_output_index,
+            )?;
+
+            verifiers::pre_account_cell::is_id_correct(0, &new_output_data, &pre_account_cell_data)?;
+            verifiers::pre_account_cell::is_account_correct(0, &new_output_data)?;
+            verifiers::pre_account_cell::is_next_correct(0, &new_output_data, original_next)?;
+            verifiers::pre_account_cell::is_expired_at_correct(
+                0,
+                profit,
+                timestamp,
+                &new_output_data,
+                pre_account_cell_witness_reader,
+            )?;
+
+            verifiers::pre_account_cell::verify_witness_id(0, &new_output_data, new_output_witness_reader)?;
+            verifiers::pre_account_cell::verify_witness_account(0, &new_output_data, new_output_witness_reader)?;
+            verifiers::pre_account_cell::verify_witness_registered_at(0, timestamp, new_output_witness_reader)?;
+            verifiers::pre_account_cell::verify_witness_throttle_fields(0, new_output_witness_reader)?;
+            verifiers::pre_account_cell::verify_witness_status(0, new_output_witness_reader)?;
+            verifiers::pre_account_cell::verify_witness_sub_account_fields(0, new_output_witness_reader)?;
+
+            debug!("Check if the profit is allocated to the IncomeCell correctly.");
+
+            // There is no proposer in this transaction, so the keeper who registers the account takes the profit of
+            // proposing and confirming freely.
+            let mut profit_map = Map::new();
+            verifiers::pre_account_cell::allocate_profit(
+                0,
+                &mut profit_map,
+                config_profit_rate,
+                pre_account_cell_witness_reader,
+                None,
+                profit,
+            )?;
+
+            verifiers::income_cell::verify_income_cells(&parser, profit_map)?;
         }
         b"refund_pre_register" => {
             parser.parse_cell()?;
@@ -182,6 +410,7 @@ pub fn main() -> Result<(), Error> {
             let mut refund_map = BTreeMap::new();
             for index in input_cells {
                 let pre_account_cell_witness = util::parse_pre_account_cell_witness(&parser, index, Source::Input)?;
+                let data = high_level::load_cell_data(index, Source::Input)?;
                 let capacity = high_level::load_cell_capacity(index, Source::Input)?;
                 let created_at = u64::from(pre_account_cell_witness.created_at());
 
@@ -194,11 +423,12 @@ pub fn main() -> Result<(), Error> {
                     PRE_ACCOUNT_CELL_TIMEOUT
                 );
 
-                util::map_add(
-                    &mut refund_map,
-                    pre_account_cell_witness.refund_lock().as_slice().to_vec(),
-                    capacity,
-                );
+                // The refund of a sponsored registration always goes back to the sponsor.
+                let refund_lock = match data_parser::pre_account_cell::get_sponsor_lock(&data) {
+                    Some(sponsor_lock) => sponsor_lock.to_vec(),
+                    None => pre_account_cell_witness.refund_lock().as_slice().to_vec(),
+                };
+                util::map_add(&mut refund_map, refund_lock, capacity);
             }
 
             debug!("Verify if every refund lock get its capacity properly ...");
@@ -471,6 +701,70 @@ fn verify_price_and_capacity(
     Ok(())
 }
 
+fn verify_sponsor(
+    parser: &WitnessesParser,
+    reader: PreAccountCellDataReader,
+    sponsor_lock: &[u8],
+    capacity: u64,
+) -> Result<(), Error> {
+    debug!("Check if the sponsor of the PreAccountCell has paid for the registration.");
+
+    let sponsor_lock_reader = match ScriptReader::from_slice(sponsor_lock) {
+        Ok(reader) => reader,
+        Err(_) => {
+            warn!("The sponsor_lock in outputs_data of the PreAccountCell should be a valid lock script.");
+            return Err(Error::PreRegisterSponsorError);
+        }
+    };
+
+    // Only the locks which require a signature can prove that the sponsor agreed to pay for this registration.
+    assert!(
+        matches!(
+            parser.get_lock_script_type(sponsor_lock_reader),
+            Some(LockScript::DasLock)
+                | Some(LockScript::Secp256k1Blake160SignhashLock)
+                | Some(LockScript::Secp256k1Blake160MultisigLock)
+        ),
+        Error::PreRegisterSponsorError,
+        "The sponsor_lock should be a lock which requires signature.(sponsor_lock: {})",
+        sponsor_lock_reader
+    );
+
+    assert!(
+        util::is_reader_eq(reader.refund_lock(), sponsor_lock_reader),
+        Error::PreRegisterSponsorError,
+        "The PreAccountCell.witness.refund_lock should be the sponsor_lock.(expected: {}, current: {})",
+        sponsor_lock_reader,
+        reader.refund_lock()
+    );
+
+    // The sponsor signs the whole transaction by unlocking its cells, so the signature is bound to the owner_lock_args
+    // and the account in the witness of the PreAccountCell.
+    let input_cells = util::find_cells_by_script(ScriptType::Lock, sponsor_lock_reader, Source::Input)?;
+    let output_cells = util::find_cells_by_script(ScriptType::Lock, sponsor_lock_reader, Source::Output)?;
+
+    assert!(
+        input_cells.len() > 0,
+        Error::PreRegisterSponsorError,
+        "The sponsor should have at least 1 cell in inputs to sign the registration.(sponsor_lock: {})",
+        sponsor_lock_reader
+    );
+
+    let input_capacity = util::load_cells_capacity(&input_cells, Source::Input)?;
+    let output_capacity = util::load_cells_capacity(&output_cells, Source::Output)?;
+    let paid = input_capacity.saturating_sub(output_capacity);
+
+    assert!(
+        paid >= capacity,
+        Error::PreRegisterSponsorError,
+        "The sponsor should pay for the whole capacity of the PreAccountCell.(expected: {}, current: {})",
+        capacity,
+        paid
+    );
+
+    Ok(())
+}
+
 fn verify_account_length_and_years(reader: PreAccountCellDataReader, current_timestamp: u64) -> Result<(), Error> {
     use chrono::{DateTime, NaiveDateTime, Utc};
 
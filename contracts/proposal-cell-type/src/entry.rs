@@ -1,19 +1,29 @@
 use alloc::{borrow::ToOwned, boxed::Box};
 use ckb_std::{
     ckb_constants::Source,
-    high_level::{self, load_cell_capacity, load_cell_lock, load_cell_type, load_script},
+    high_level::{self, load_cell_capacity, load_cell_type, load_script},
 };
-use core::{convert::TryFrom, result::Result};
+use core::{cmp, convert::TryFrom, result::Result};
 use das_core::{
     assert,
     constants::*,
-    data_parser::{account_cell, pre_account_cell},
+    data_parser::{account_cell, pre_account_cell, proposal_cell},
     debug,
     error::Error,
-    util, verifiers, warn,
+    util,
+    verifiers::{
+        self,
+        pre_account_cell::{
+            is_account_correct, is_cell_capacity_correct, is_expired_at_correct, is_id_correct,
+            is_new_account_cell_lock_correct, is_next_correct, is_old_account_cell_data_consistent,
+            verify_witness_account, verify_witness_id, verify_witness_registered_at, verify_witness_status,
+            verify_witness_sub_account_fields, verify_witness_throttle_fields,
+        },
+    },
+    warn,
     witness_parser::WitnessesParser,
 };
-use das_map::{map::Map, util as map_util};
+use das_map::map::Map;
 use das_sorted_list::DasSortedList;
 use das_types::{constants::*, mixer::AccountCellDataMixer, packed::*, prelude::*};
 
@@ -71,40 +81,73 @@ pub fn main() -> Result<(), Error> {
             )?;
             verifiers::misc::verify_always_success_lock(output_cells[0], Source::Output)?;
 
+            let capacity = load_cell_capacity(output_cells[0], Source::Output)?;
+            assert!(
+                capacity >= PROPOSAL_BOND_CAPACITY,
+                Error::ProposalBondCapacityError,
+                "The ProposalCell should lock at least {} shannon as the bond of the keeper.(current: {})",
+                PROPOSAL_BOND_CAPACITY,
+                capacity
+            );
+
             let dep_cell_witness;
             let dep_cell_witness_reader;
             let mut prev_slices_reader_opt = None;
             if action == b"extend_proposal" {
                 dep_cell_witness = util::parse_proposal_cell_witness(&parser, dep_cells[0], Source::CellDep)?;
                 dep_cell_witness_reader = dep_cell_witness.as_reader();
+
+                // The cells of the confirmed slices have been changed, so the proposal can not be extended anymore.
+                let dep_cell_data = util::load_cell_data(dep_cells[0], Source::CellDep)?;
+                assert!(
+                    (0..dep_cell_witness_reader.slices().len())
+                        .all(|i| !proposal_cell::is_slice_confirmed(&dep_cell_data, i)),
+                    Error::ProposalSliceConfirmError,
+                    "The ProposalCell in cell_deps has been partially confirmed, so it can not be extended."
+                );
+
                 prev_slices_reader_opt = Some(dep_cell_witness_reader.slices());
             }
 
             let output_cell_witness = util::parse_proposal_cell_witness(&parser, output_cells[0], Source::Output)?;
             let output_cell_witness_reader = output_cell_witness.as_reader();
 
+            let output_cell_data = util::load_cell_data(output_cells[0], Source::Output)?;
+            assert!(
+                output_cell_data.len() == 32,
+                Error::ProposalSliceConfirmError,
+                "The newly created ProposalCell should not have any confirmed slices."
+            );
+
             let required_cells_count = verify_slices(config_proposal, output_cell_witness_reader.slices())?;
-            let dep_related_cells = find_proposal_related_cells(config_main, Source::CellDep)?;
+            let mut dep_related_cells = find_proposal_related_cells(config_main, Source::CellDep)?;
 
             #[cfg(debug_assertions)]
             inspect_slices(output_cell_witness_reader.slices())?;
             #[cfg(debug_assertions)]
             inspect_related_cells(&parser, config_main, dep_related_cells.clone(), Source::CellDep)?;
 
+            // The cells following the proposal relevant cells are competing PreAccountCells, they are used to prove that
+            // the PreAccountCells in the proposal are the earliest ones of their account IDs. Their count is derived from
+            // the slices, and each of them is verified to be a PreAccountCell wanting an account in the proposal. The
+            // AccountCells among them are the proofs of the new accounts not being confusable.
             assert!(
-                required_cells_count == dep_related_cells.len(),
+                required_cells_count <= dep_related_cells.len(),
                 Error::ProposalSliceRelatedCellMissing,
                 "Some of the proposal relevant cells are missing. (expected: {}, current: {})",
                 required_cells_count,
                 dep_related_cells.len()
             );
 
+            let competing_cells = dep_related_cells.split_off(required_cells_count);
+
             verify_slices_relevant_cells(
                 &parser,
                 timestamp,
                 config_main,
                 output_cell_witness_reader.slices(),
                 dep_related_cells,
+                competing_cells,
                 prev_slices_reader_opt,
             )?;
         }
@@ -117,15 +160,33 @@ pub fn main() -> Result<(), Error> {
             let config_profit_rate = parser.configs.profit_rate()?;
             let config_proposal_reader = parser.configs.proposal()?;
 
-            verifiers::common::verify_removed_cell_in_correct_position(
-                "ProposalCell",
-                &input_cells,
-                &output_cells,
-                None,
-            )?;
+            assert!(
+                input_cells.len() == 1 && output_cells.len() <= 1,
+                Error::InvalidTransactionStructure,
+                "There should be 1 ProposalCell in inputs and 0 or 1 ProposalCell in outputs.(inputs: {}, outputs: {})",
+                input_cells.len(),
+                output_cells.len()
+            );
 
             let input_cell_witness = util::parse_proposal_cell_witness(&parser, input_cells[0], Source::Input)?;
             let input_cell_witness_reader = input_cell_witness.as_reader();
+            let input_cell_data = util::load_cell_data(input_cells[0], Source::Input)?;
+            let slices_len = input_cell_witness_reader.slices().len();
+
+            assert!(
+                proposal_cell::is_data_length_valid(&input_cell_data, slices_len),
+                Error::InvalidCellData,
+                "The outputs_data of the ProposalCell is invalid."
+            );
+
+            debug!("Find out the slices which should be confirmed in this transaction.");
+
+            let slices_to_confirm = find_slices_to_confirm(&parser.params, &input_cell_data, slices_len)?;
+            verify_slices_to_confirm_limit(
+                config_proposal_reader,
+                input_cell_witness_reader.slices(),
+                &slices_to_confirm,
+            )?;
 
             debug!("Check if the ProposalCell is able to be confirmed.");
 
@@ -149,9 +210,47 @@ pub fn main() -> Result<(), Error> {
                 config_profit_rate,
                 timestamp,
                 input_cell_witness_reader,
+                &slices_to_confirm,
             )?;
 
-            verify_refund_correct(input_cells[0], input_cell_witness_reader, 0)?;
+            let mut confirmed_slices: Vec<usize> = (0..slices_len)
+                .filter(|i| proposal_cell::is_slice_confirmed(&input_cell_data, *i))
+                .collect();
+            confirmed_slices.extend(slices_to_confirm.iter());
+
+            if confirmed_slices.len() == slices_len {
+                debug!("All slices are confirmed, so the ProposalCell should be removed and refunded.");
+
+                assert!(
+                    output_cells.is_empty(),
+                    Error::InvalidTransactionStructure,
+                    "The ProposalCell should be removed when all of its slices are confirmed."
+                );
+
+                verify_refund_correct(input_cells[0], input_cell_witness_reader, 0)?;
+            } else {
+                debug!("Some slices are still not confirmed, so the ProposalCell should be kept with its progress.");
+
+                assert!(
+                    output_cells.len() == 1,
+                    Error::InvalidTransactionStructure,
+                    "The ProposalCell should be kept in outputs until all of its slices are confirmed."
+                );
+
+                util::is_cell_capacity_equal((input_cells[0], Source::Input), (output_cells[0], Source::Output))?;
+                util::is_cell_lock_equal((input_cells[0], Source::Input), (output_cells[0], Source::Output))?;
+
+                let mut expected_data = input_cell_data.get(..32).unwrap().to_vec();
+                expected_data.extend(proposal_cell::gen_confirmed_slices(slices_len, &confirmed_slices));
+                let output_cell_data = util::load_cell_data(output_cells[0], Source::Output)?;
+                assert!(
+                    output_cell_data == expected_data,
+                    Error::ProposalSliceConfirmError,
+                    "The outputs_data of the ProposalCell should be the same witness hash with the confirmed slices.(expected: 0x{}, current: 0x{})",
+                    util::hex_string(&expected_data),
+                    util::hex_string(&output_cell_data)
+                );
+            }
         }
         b"recycle_proposal" => {
             parser.parse_cell()?;
@@ -180,7 +279,32 @@ pub fn main() -> Result<(), Error> {
                 created_at_height + proposal_min_recycle_interval - height
             );
 
-            verify_refund_correct(input_cells[0], input_cell_witness_reader, 10000)?;
+            // A part of the bond is slashed when the ProposalCell is recycled, so the keepers are incentivized to
+            // confirm their proposals in time. The slashed part is paid to the DAS wallet whoever recycles, because the
+            // proposer could recycle with any other lock and take it back if it was paid to the recycler.
+            let capacity = load_cell_capacity(input_cells[0], Source::Input)?;
+            let slashed = cmp::min(capacity, PROPOSAL_BOND_CAPACITY) / RATE_BASE * PROPOSAL_RECYCLE_SLASH_RATE;
+
+            debug!(
+                "The slashed bond is {} shannon, the rest should be refunded to the proposer.",
+                slashed
+            );
+
+            let das_wallet_lock = das_wallet_lock();
+            let (input_wallet_cells, output_wallet_cells) =
+                util::find_cells_by_script_in_inputs_and_outputs(ScriptType::Lock, das_wallet_lock.as_reader())?;
+            let input_wallet_capacity = util::load_cells_capacity(&input_wallet_cells, Source::Input)?;
+            let output_wallet_capacity = util::load_cells_capacity(&output_wallet_cells, Source::Output)?;
+            assert!(
+                output_wallet_capacity >= input_wallet_capacity + slashed,
+                Error::ProposalConfirmRefundError,
+                "The DAS wallet should receive the slashed bond of {} shannon.(inputs: {}, outputs: {})",
+                slashed,
+                input_wallet_capacity,
+                output_wallet_capacity
+            );
+
+            verify_refund_correct(input_cells[0], input_cell_witness_reader, 10000 + slashed)?;
         }
         _ => return Err(Error::ActionNotSupported),
     }
@@ -379,21 +503,31 @@ fn verify_slices(config: ConfigCellProposalReader, slices_reader: SliceListReade
         "The order of items in slices is incorrect."
     );
 
+    // The caps limit the cells changed by one confirm_proposal transaction. A proposal exceeding them can only be
+    // confirmed slice by slice, so every slice should fit in the caps and be addressable by the 1 byte param.
     let max_account_cell_count = u32::from(config.proposal_max_account_affect());
-    assert!(
-        account_cell_contained <= max_account_cell_count,
-        Error::InvalidTransactionStructure,
-        "The proposal should not contains more than {} AccountCells.",
-        max_account_cell_count
-    );
-
     let max_pre_account_cell_count = u32::from(config.proposal_max_pre_account_contain());
-    assert!(
-        pre_account_cell_contained <= max_pre_account_cell_count,
-        Error::InvalidTransactionStructure,
-        "The proposal should not contains more than {} PreAccountCells.",
-        max_pre_account_cell_count
-    );
+    if account_cell_contained > max_account_cell_count || pre_account_cell_contained > max_pre_account_cell_count {
+        debug!("The proposal exceeds the caps of one transaction, so it should be confirmed slice by slice.");
+
+        assert!(
+            slices_reader.len() <= u8::MAX as usize + 1,
+            Error::InvalidTransactionStructure,
+            "The proposal confirmed slice by slice should not contains more than {} slices.",
+            u8::MAX as usize + 1
+        );
+    }
+
+    for (sl_index, sl_reader) in slices_reader.iter().enumerate() {
+        let pre_account_cell_count = (sl_reader.len() - 1) as u32;
+        assert!(
+            pre_account_cell_count <= max_pre_account_cell_count,
+            Error::InvalidTransactionStructure,
+            "Slice[{}] should not contains more than {} PreAccountCells.",
+            sl_index,
+            max_pre_account_cell_count
+        );
+    }
 
     Ok(required_cells_count)
 }
@@ -478,10 +612,25 @@ fn verify_slices_relevant_cells(
     config: ConfigCellMainReader,
     slices_reader: SliceListReader,
     relevant_cells: Vec<usize>,
+    competing_cells: Vec<usize>,
     prev_slices_reader_opt: Option<SliceListReader>,
 ) -> Result<(), Error> {
     debug!("Check the proposal slices relevant cells are real exist and in correct status.");
 
+    let competitors = parse_competing_pre_account_cells(parser, timestamp, config, slices_reader, competing_cells)?;
+
+    // The accounts in the proposal chain are not registered yet, so they can not be proved by AccountCells.
+    let mut pending_account_ids = Vec::new();
+    for slices in [Some(slices_reader), prev_slices_reader_opt].iter().flatten() {
+        for sl_reader in slices.iter() {
+            for item in sl_reader.iter() {
+                if u8::from(item.item_type()) != ProposalSliceItemType::Exist as u8 {
+                    pending_account_ids.push(item.account_id().raw_data().to_vec());
+                }
+            }
+        }
+    }
+
     let mut i = 0;
     for (_sl_index, sl_reader) in slices_reader.iter().enumerate() {
         debug!("Check slice {} ...", _sl_index);
@@ -532,6 +681,35 @@ fn verify_slices_relevant_cells(
                     created_at,
                     created_at + PRE_ACCOUNT_CELL_TIMEOUT
                 );
+
+                // For fairness, the keeper should not skip any older PreAccountCell which wants the same account.
+                for (competitor_index, competitor_account_id, competitor_created_at) in competitors.iter() {
+                    if competitor_account_id.as_slice() != item_account_id.raw_data() {
+                        continue;
+                    }
+
+                    assert!(
+                        created_at <= *competitor_created_at,
+                        Error::ProposalPreAccountCellNotEarliest,
+                        "  Item[{}] The PreAccountCell is not the earliest one of its account.(related_cell: {:?}[{}], created_at: {}, competing_cell: {:?}[{}], created_at: {})",
+                        item_index,
+                        Source::CellDep,
+                        cell_index,
+                        created_at,
+                        Source::CellDep,
+                        competitor_index,
+                        competitor_created_at
+                    );
+                }
+
+                // An account visually identical to the new account may be registered or proposed after pre_register.
+                if item_type == ProposalSliceItemType::New as u8 {
+                    verifiers::account_cell::verify_account_not_confusable(
+                        parser,
+                        pre_account_cell_witness_reader.account(),
+                        &pending_account_ids,
+                    )?;
+                }
             };
 
             // ⚠️ The first item is very very important, its "next" must be correct so that
@@ -587,6 +765,86 @@ fn verify_slices_relevant_cells(
     Ok(())
 }
 
+/// Parse the competing PreAccountCells into (cell_index, account_id, created_at) tuples.
+///
+/// The AccountCells following the proposal relevant cells are skipped, they are the proofs required by
+/// verify_account_not_confusable.
+///
+/// Every competing PreAccountCell must want one of the accounts which is registered by PreAccountCell in the proposal,
+/// the expired ones are ignored because they can not be registered anymore.
+///
+/// CAREFUL! The competing PreAccountCells are chosen by the keeper who pushes the transaction, so this check can only
+/// reject the competitors which are put in cell_deps, a keeper can still bypass it by omitting the earlier ones.
+fn parse_competing_pre_account_cells(
+    parser: &WitnessesParser,
+    timestamp: u64,
+    config: ConfigCellMainReader,
+    slices_reader: SliceListReader,
+    competing_cells: Vec<usize>,
+) -> Result<Vec<(usize, Vec<u8>, u64)>, Error> {
+    debug!(
+        "Check the competing PreAccountCells in cell_deps: {:?}",
+        competing_cells
+    );
+
+    let expected_type_id = config.type_id_table().pre_account_cell();
+    let account_cell_type_id = config.type_id_table().account_cell();
+    let mut competitors = Vec::new();
+    for cell_index in competing_cells {
+        let cell_type_id = load_cell_type(cell_index, Source::CellDep)?
+            .map(|script| script.code_hash())
+            .ok_or(Error::ProposalSliceRelatedCellMissing)?;
+        if cell_type_id.as_reader().raw_data() == account_cell_type_id.raw_data() {
+            debug!(
+                "The AccountCell is a proof for verify_account_not_confusable, skip it. (cell: {:?}[{}])",
+                Source::CellDep,
+                cell_index
+            );
+            continue;
+        }
+
+        assert!(
+            cell_type_id.as_reader().raw_data() == expected_type_id.raw_data(),
+            Error::ProposalSliceRelatedCellMissing,
+            "The cells following the proposal relevant cells should be competing PreAccountCells. (competing_cell: {:?}[{}])",
+            Source::CellDep,
+            cell_index
+        );
+
+        let cell_data = util::load_cell_data(cell_index, Source::CellDep)?;
+        let account_id = pre_account_cell::get_id(&cell_data).to_vec();
+        let is_proposed = slices_reader.iter().any(|sl_reader| {
+            sl_reader.iter().any(|item| {
+                u8::from(item.item_type()) != ProposalSliceItemType::Exist as u8
+                    && item.account_id().raw_data() == account_id.as_slice()
+            })
+        });
+        assert!(
+            is_proposed,
+            Error::ProposalSliceRelatedCellMissing,
+            "The competing PreAccountCell should want an account in the proposal. (competing_cell: {:?}[{}], account_id: 0x{})",
+            Source::CellDep,
+            cell_index,
+            util::hex_string(&account_id)
+        );
+
+        let witness = util::parse_pre_account_cell_witness(&parser, cell_index, Source::CellDep)?;
+        let created_at = u64::from(witness.as_reader().created_at());
+        if timestamp > created_at + PRE_ACCOUNT_CELL_TIMEOUT {
+            debug!(
+                "The competing PreAccountCell has been expired, skip it. (competing_cell: {:?}[{}])",
+                Source::CellDep,
+                cell_index
+            );
+            continue;
+        }
+
+        competitors.push((cell_index, account_id, created_at));
+    }
+
+    Ok(competitors)
+}
+
 fn find_item_contains_account_id(
     prev_slices_reader: &SliceListReader,
     account_id: &AccountIdReader,
@@ -610,13 +868,13 @@ fn verify_proposal_execution_result(
     config_profit_rate: ConfigCellProfitRateReader,
     timestamp: u64,
     proposal_cell_data_reader: ProposalCellDataReader,
+    slices_to_confirm: &[usize],
 ) -> Result<(), Error> {
     debug!("Check that all AccountCells/PreAccountCells have been converted according to the proposal.");
 
     #[cfg(debug_assertions)]
     inspect_slices(proposal_cell_data_reader.slices())?;
 
-    let das_wallet_lock = das_wallet_lock();
     let proposer_lock_reader = proposal_cell_data_reader.proposer_lock();
     let slices_reader = proposal_cell_data_reader.slices();
 
@@ -631,17 +889,12 @@ fn verify_proposal_execution_result(
     inspect_related_cells(&parser, config_main, output_account_cells.clone(), Source::Output)?;
 
     let mut profit_map = Map::new();
-    let inviter_profit_rate = u32::from(config_profit_rate.inviter()) as u64;
-    let channel_profit_rate = u32::from(config_profit_rate.channel()) as u64;
-    let proposal_create_profit_rate = u32::from(config_profit_rate.proposal_create()) as u64;
-    let proposal_confirm_profit_rate = u32::from(config_profit_rate.proposal_confirm()) as u64;
-
-    let default_lock = Script::default();
-    let default_lock_reader = default_lock.as_reader();
 
     let mut i = 0;
-    for (_sl_index, sl_reader) in slices_reader.iter().enumerate() {
-        debug!("Check Slice[{}] ...", _sl_index);
+    for sl_index in slices_to_confirm.iter() {
+        let sl_reader = slices_reader.get(*sl_index).unwrap();
+
+        debug!("Check Slice[{}] ...", sl_index);
 
         let last_item = sl_reader.get(sl_reader.len() - 1).unwrap();
         let original_next_of_account_cell = last_item.next().raw_data();
@@ -704,7 +957,7 @@ fn verify_proposal_execution_result(
                 // For the existing AccountCell, only the next field in data can be modified.
                 // No need to check the witness of AccountCells here, because we check their hash instead.
                 is_old_account_cell_data_consistent(item_index, &output_cell_data, &input_cell_data)?;
-                is_next_correct(item_index, &output_cell_data, item_next)?;
+                is_next_correct(item_index, &output_cell_data, item_next.raw_data())?;
 
                 let input_cell_witness: Box<dyn AccountCellDataMixer> =
                     util::parse_account_cell_witness(&parser, input_related_cells[i], Source::Input)?;
@@ -801,7 +1054,7 @@ fn verify_proposal_execution_result(
                 // Check all fields in the data of new AccountCell.
                 is_id_correct(item_index, &output_cell_data, &input_cell_data)?;
                 is_account_correct(item_index, &output_cell_data)?;
-                is_next_correct(item_index, &output_cell_data, item_next)?;
+                is_next_correct(item_index, &output_cell_data, item_next.raw_data())?;
                 is_expired_at_correct(
                     item_index,
                     profit,
@@ -818,69 +1071,14 @@ fn verify_proposal_execution_result(
                 verify_witness_status(item_index, output_cell_witness_reader)?;
                 verify_witness_sub_account_fields(item_index, output_cell_witness_reader)?;
 
-                let mut inviter_profit = 0;
-                if input_cell_witness_reader.inviter_lock().is_some() {
-                    let inviter_lock_reader = input_cell_witness_reader.inviter_lock().to_opt().unwrap();
-                    // Skip default value for supporting transactions treat default value as None.
-                    if !util::is_reader_eq(default_lock_reader, inviter_lock_reader) {
-                        inviter_profit = profit * inviter_profit_rate / RATE_BASE;
-                        debug!(
-                            "  Item[{}] lock.args[{}]: {}(inviter_profit) = {}(profit) * {}(inviter_profit_rate) / {}(RATE_BASE)",
-                            item_index, inviter_lock_reader.args(), inviter_profit, profit, inviter_profit_rate, RATE_BASE
-                        );
-                        map_util::add(&mut profit_map, inviter_lock_reader.as_slice().to_vec(), inviter_profit);
-                    }
-                };
-
-                let mut channel_profit = 0;
-                if input_cell_witness_reader.channel_lock().is_some() {
-                    let channel_lock_reader = input_cell_witness_reader.channel_lock().to_opt().unwrap();
-                    // Skip default value for supporting transactions treat default value as None.
-                    if !util::is_reader_eq(default_lock_reader, channel_lock_reader) {
-                        channel_profit = profit * channel_profit_rate / RATE_BASE;
-                        debug!(
-                            "  Item[{}] lock.args[{}]: {}(channel_profit) = {}(profit) * {}(channel_profit_rate) / {}(RATE_BASE)",
-                            item_index, channel_lock_reader.args(), channel_profit, profit, channel_profit_rate, RATE_BASE
-                        );
-                        map_util::add(&mut profit_map, channel_lock_reader.as_slice().to_vec(), channel_profit);
-                    }
-                };
-
-                let proposal_create_profit = profit * proposal_create_profit_rate / RATE_BASE;
-                debug!(
-                    "  Item[{}] lock.args[{}]: {}(proposal_create_profit) = {}(profit) * {}(proposal_create_profit_rate) / {}(RATE_BASE)",
+                verifiers::pre_account_cell::allocate_profit(
                     item_index,
-                    proposer_lock_reader.args(),
-                    proposal_create_profit,
-                    profit,
-                    proposal_create_profit_rate,
-                    RATE_BASE
-                );
-                map_util::add(
-                    &mut profit_map,
-                    proposer_lock_reader.as_slice().to_vec(),
-                    proposal_create_profit,
-                );
-
-                let proposal_confirm_profit = profit * proposal_confirm_profit_rate / RATE_BASE;
-                debug!(
-                    "  Item[{}] {}(proposal_confirm_profit) = {}(profit) * {}(proposal_confirm_profit_rate) / {}(RATE_BASE) (! not included in IncomeCell)",
-                    item_index, proposal_confirm_profit, profit, proposal_confirm_profit_rate, RATE_BASE
-                );
-                // No need to record proposal confirm profit, bacause the transaction creator can take its profit freely and this script do not know which lock script the transaction creator will use.
-
-                let das_profit =
-                    profit - inviter_profit - channel_profit - proposal_create_profit - proposal_confirm_profit;
-                map_util::add(
                     &mut profit_map,
-                    das_wallet_lock.as_reader().as_slice().to_vec(),
-                    das_profit,
-                );
-
-                debug!(
-                    "  Item[{}] lock.args[{}]: {}(das_profit) = {}(profit) - {}(inviter_profit) - {}(channel_profit) - {}(proposal_create_profit) - {}(proposal_confirm_profit)",
-                    item_index, das_wallet_lock.as_reader().args(), das_profit, profit, inviter_profit, channel_profit, proposal_create_profit, proposal_confirm_profit
-                );
+                    config_profit_rate,
+                    input_cell_witness_reader,
+                    Some(proposer_lock_reader),
+                    profit,
+                )?;
             }
 
             i += 1;
@@ -892,6 +1090,68 @@ fn verify_proposal_execution_result(
     Ok(())
 }
 
+fn find_slices_to_confirm(params: &[Bytes], proposal_cell_data: &[u8], slices_len: usize) -> Result<Vec<usize>, Error> {
+    if params.is_empty() {
+        // Without any param, all the slices which are not confirmed yet should be confirmed at once.
+        let slices_to_confirm: Vec<usize> = (0..slices_len)
+            .filter(|i| !proposal_cell::is_slice_confirmed(proposal_cell_data, *i))
+            .collect();
+
+        debug!("Confirm all the remaining slices: {:?}", slices_to_confirm);
+
+        return Ok(slices_to_confirm);
+    }
+
+    let param = params[0].raw_data();
+    assert!(
+        param.len() == 1,
+        Error::ParamsDecodingError,
+        "The param of confirm_proposal should be the index of the slice in 1 byte."
+    );
+
+    let sl_index = param[0] as usize;
+    assert!(
+        sl_index < slices_len && !proposal_cell::is_slice_confirmed(proposal_cell_data, sl_index),
+        Error::ProposalSliceConfirmError,
+        "The slice {} does not exist or has been confirmed.",
+        sl_index
+    );
+
+    debug!("Confirm only the slice: {}", sl_index);
+
+    Ok(vec![sl_index])
+}
+
+fn verify_slices_to_confirm_limit(
+    config: ConfigCellProposalReader,
+    slices_reader: SliceListReader,
+    slices_to_confirm: &[usize],
+) -> Result<(), Error> {
+    let account_cell_count = slices_to_confirm.len() as u32;
+    let pre_account_cell_count: u32 = slices_to_confirm
+        .iter()
+        .map(|i| (slices_reader.get(*i).unwrap().len() - 1) as u32)
+        .sum();
+
+    let max_account_cell_count = u32::from(config.proposal_max_account_affect());
+    assert!(
+        account_cell_count <= max_account_cell_count,
+        Error::InvalidTransactionStructure,
+        "The confirm_proposal transaction should not affect more than {} AccountCells, please confirm the proposal slice by slice.",
+        max_account_cell_count
+    );
+
+    let max_pre_account_cell_count = u32::from(config.proposal_max_pre_account_contain());
+    assert!(
+        pre_account_cell_count <= max_pre_account_cell_count,
+        Error::InvalidTransactionStructure,
+        "The confirm_proposal transaction should not contains more than {} PreAccountCells, please confirm the proposal slice by slice.",
+        max_pre_account_cell_count
+    );
+
+    Ok(())
+}
+
 fn verify_cell_type_id(
     item_index: usize,
     cell_index: usize,
@@ -980,287 +1240,6 @@ fn verify_pre_account_cell_account_id(
     Ok(())
 }
 
-fn is_new_account_cell_lock_correct(
-    item_index: usize,
-    input_cell_index: usize,
-    input_cell_witness_reader: PreAccountCellDataReader,
-    output_cell_index: usize,
-) -> Result<(), Error> {
-    debug!(
-        "  Item[{}] Check if the lock script of new AccountCells is das-lock.",
-        item_index
-    );
-
-    let das_lock = das_lock();
-    let owner_lock_args = input_cell_witness_reader.owner_lock_args().raw_data().to_owned();
-    let output_cell_lock = load_cell_lock(output_cell_index, Source::Output)?;
-
-    let expected_lock = das_lock.as_builder().args(Bytes::from(owner_lock_args).into()).build();
-
-    assert!(
-        util::is_entity_eq(&expected_lock, &output_cell_lock),
-        Error::ProposalConfirmAccountLockArgsIsInvalid,
-        "  Item[{}] The outputs[{}].lock should come from the owner_lock_args of inputs[{}]. (expected: {}, current: {})",
-        item_index,
-        output_cell_index,
-        input_cell_index,
-        expected_lock,
-        output_cell_lock
-    );
-
-    Ok(())
-}
-
-fn is_bytes_eq(
-    item_index: usize,
-    field: &str,
-    current_bytes: &[u8],
-    expected_bytes: &[u8],
-    error_code: Error,
-) -> Result<(), Error> {
-    assert!(
-        current_bytes == expected_bytes,
-        error_code,
-        "  Item[{}] The AccountCell.{} should be consist in inputs and outputs.(expected: {}, current: {})",
-        item_index,
-        field,
-        util::hex_string(expected_bytes),
-        util::hex_string(current_bytes)
-    );
-
-    Ok(())
-}
-
-fn is_old_account_cell_data_consistent(
-    item_index: usize,
-    output_cell_data: &Vec<u8>,
-    input_cell_data: &Vec<u8>,
-) -> Result<(), Error> {
-    is_bytes_eq(
-        item_index,
-        "id",
-        account_cell::get_id(output_cell_data),
-        account_cell::get_id(input_cell_data),
-        Error::ProposalFieldCanNotBeModified,
-    )?;
-    is_bytes_eq(
-        item_index,
-        "account",
-        account_cell::get_account(output_cell_data),
-        account_cell::get_account(input_cell_data),
-        Error::ProposalFieldCanNotBeModified,
-    )?;
-    is_bytes_eq(
-        item_index,
-        "expired_at",
-        &account_cell::get_expired_at(output_cell_data).to_le_bytes(),
-        &account_cell::get_expired_at(input_cell_data).to_le_bytes(),
-        Error::ProposalFieldCanNotBeModified,
-    )?;
-
-    Ok(())
-}
-
-fn is_id_correct(item_index: usize, output_cell_data: &Vec<u8>, input_cell_data: &Vec<u8>) -> Result<(), Error> {
-    is_bytes_eq(
-        item_index,
-        "id",
-        account_cell::get_id(output_cell_data),
-        account_cell::get_id(input_cell_data),
-        Error::ProposalConfirmNewAccountCellDataError,
-    )
-}
-
-fn is_next_correct(item_index: usize, output_cell_data: &Vec<u8>, proposed_next: AccountIdReader) -> Result<(), Error> {
-    let expected_next = proposed_next.raw_data();
-
-    is_bytes_eq(
-        item_index,
-        "next",
-        account_cell::get_next(output_cell_data),
-        expected_next,
-        Error::ProposalConfirmNewAccountCellDataError,
-    )
-}
-
-fn is_expired_at_correct(
-    item_index: usize,
-    profit: u64,
-    current_timestamp: u64,
-    output_cell_data: &Vec<u8>,
-    pre_account_cell_witness: PreAccountCellDataReader,
-) -> Result<(), Error> {
-    let price = u64::from(pre_account_cell_witness.price().new());
-    let quote = u64::from(pre_account_cell_witness.quote());
-    let discount = u32::from(pre_account_cell_witness.invited_discount());
-    let duration = util::calc_duration_from_paid(profit, price, quote, discount);
-    let expired_at = account_cell::get_expired_at(output_cell_data);
-    let calculated_expired_at = current_timestamp + duration;
-
-    debug!(
-        "  Item[{}] Params of expired_at calculation: --profit={} --price={} --quote={} --discount={} --current={}",
-        item_index, profit, price, quote, discount, current_timestamp
-    );
-    debug!(
-        "  Item[{}] Critical value of expired_at calculation process: duration={}, calculated_expired_at={}",
-        item_index, duration, calculated_expired_at
-    );
-
-    assert!(
-        calculated_expired_at == expired_at,
-        Error::ProposalConfirmNewAccountCellDataError,
-        "  Item[{}] The AccountCell.expired_at should be {}, but {} found.",
-        item_index,
-        calculated_expired_at,
-        expired_at
-    );
-
-    Ok(())
-}
-
-fn is_account_correct(item_index: usize, output_cell_data: &Vec<u8>) -> Result<(), Error> {
-    let expected_account_id = account_cell::get_id(output_cell_data);
-    let account = account_cell::get_account(output_cell_data);
-
-    let hash = util::blake2b_256(account);
-    let account_id = hash.get(..ACCOUNT_ID_LENGTH).unwrap();
-
-    is_bytes_eq(
-        item_index,
-        "account",
-        account_id,
-        expected_account_id,
-        Error::ProposalConfirmNewAccountCellDataError,
-    )
-}
-
-fn is_cell_capacity_correct(item_index: usize, cell_index: usize, expected_capacity: u64) -> Result<(), Error> {
-    let cell_capacity = load_cell_capacity(cell_index, Source::Output)?;
-
-    assert!(
-        expected_capacity == cell_capacity,
-        Error::ProposalConfirmNewAccountCellCapacityError,
-        "  Item[{}] The AccountCell.capacity should be {}, but {} found.",
-        item_index,
-        expected_capacity,
-        cell_capacity
-    );
-
-    Ok(())
-}
-
-fn verify_witness_id(
-    item_index: usize,
-    output_cell_data: &Vec<u8>,
-    output_cell_witness_reader: AccountCellDataReader,
-) -> Result<(), Error> {
-    let account_id = output_cell_witness_reader.id().raw_data();
-    let expected_account_id = account_cell::get_id(output_cell_data);
-
-    is_bytes_eq(
-        item_index,
-        "witness.id",
-        account_id,
-        expected_account_id,
-        Error::ProposalConfirmNewAccountWitnessError,
-    )
-}
-
-fn verify_witness_account(
-    item_index: usize,
-    output_cell_data: &Vec<u8>,
-    output_cell_witness_reader: AccountCellDataReader,
-) -> Result<(), Error> {
-    let mut account = output_cell_witness_reader.account().as_readable();
-    account.append(&mut ACCOUNT_SUFFIX.as_bytes().to_vec());
-    let expected_account = account_cell::get_account(output_cell_data);
-
-    is_bytes_eq(
-        item_index,
-        "witness.account",
-        account.as_slice(),
-        expected_account,
-        Error::ProposalConfirmNewAccountWitnessError,
-    )
-}
-
-fn verify_witness_registered_at(
-    item_index: usize,
-    timestamp: u64,
-    output_cell_witness_reader: AccountCellDataReader,
-) -> Result<(), Error> {
-    let registered_at = u64::from(output_cell_witness_reader.registered_at());
-
-    assert!(
-        registered_at == timestamp,
-        Error::ProposalConfirmNewAccountWitnessError,
-        "  Item[{}] The AccountCell.registered_at should be the same as the timestamp in TimeCell.(expected: {}, current: {})",
-        item_index,
-        timestamp,
-        registered_at
-    );
-
-    Ok(())
-}
-
-fn verify_witness_throttle_fields(
-    item_index: usize,
-    output_cell_witness_reader: AccountCellDataReader,
-) -> Result<(), Error> {
-    let last_transfer_account_at = u64::from(output_cell_witness_reader.last_transfer_account_at());
-    let last_edit_manager_at = u64::from(output_cell_witness_reader.last_edit_manager_at());
-    let last_edit_records_at = u64::from(output_cell_witness_reader.last_edit_records_at());
-
-    assert!(
-        last_transfer_account_at == 0 && last_edit_manager_at == 0 && last_edit_records_at == 0,
-        Error::ProposalConfirmNewAccountWitnessError,
-        "  Item[{}] The AccountCell.last_transfer_account_at/last_edit_manager_at/last_edit_records_at should be 0 .",
-        item_index
-    );
-
-    Ok(())
-}
-
-fn verify_witness_status(item_index: usize, output_cell_witness_reader: AccountCellDataReader) -> Result<(), Error> {
-    let status = u8::from(output_cell_witness_reader.status());
-
-    assert!(
-        status == AccountStatus::Normal as u8,
-        Error::ProposalConfirmNewAccountWitnessError,
-        "  Item[{}] The AccountCell.status should be normal. (expected: 0, current: {})",
-        item_index,
-        status
-    );
-
-    Ok(())
-}
-
-fn verify_witness_sub_account_fields(
-    item_index: usize,
-    output_cell_witness_reader: AccountCellDataReader,
-) -> Result<(), Error> {
-    let enable_sub_account = u8::from(output_cell_witness_reader.enable_sub_account());
-    let renew_sub_account_price = u64::from(output_cell_witness_reader.renew_sub_account_price());
-
-    assert!(
-        enable_sub_account == SubAccountEnableStatus::Off as u8,
-        Error::ProposalConfirmNewAccountWitnessError,
-        "  Item[{}] The AccountCell.enable_sub_account should be off. (expected: 0, current: {})",
-        item_index,
-        enable_sub_account
-    );
-
-    assert!(
-        renew_sub_account_price == 0,
-        Error::ProposalConfirmNewAccountWitnessError,
-        "  Item[{}] The AccountCell.renew_sub_account_price should be 0. (expected: 0, current: {})",
-        item_index,
-        renew_sub_account_price
-    );
-
-    Ok(())
-}
-
 fn verify_refund_correct(
     proposal_cell_index: usize,
     proposal_cell_data_reader: ProposalCellDataReader,
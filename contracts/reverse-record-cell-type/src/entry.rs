@@ -1,13 +1,13 @@
-use alloc::{format, string::String};
+use alloc::{format, string::String, vec::Vec};
 use ckb_std::{ckb_constants::Source, high_level};
 use core::result::Result;
 use das_core::{
     assert, assert_lock_equal,
-    constants::{das_lock, ScriptType},
-    debug,
+    constants::{das_lock, OracleCellType, ScriptType},
+    data_parser, debug,
     eip712::{to_semantic_address, verify_eip712_hashes_if_has_das_lock},
     error::Error,
-    util, verifiers,
+    util, verifiers, warn,
     witness_parser::WitnessesParser,
 };
 use das_types::{constants::LockRole, packed::*};
@@ -93,10 +93,109 @@ pub fn main() -> Result<(), Error> {
                 Error::ReverseRecordCellLockError,
                 "The ReverseRecordCell.lock should be the das-lock."
             );
+
+            verify_reverse_record_accounts(&mut parser, &output_cells[..1])?;
+        }
+        b"batch_declare_reverse_record" => {
+            verify_eip712_hashes_if_has_das_lock(&parser, batch_declare_reverse_record_to_semantic)?;
+
+            let config_main = parser.configs.main()?;
+            let config_reverse_resolution = parser.configs.reverse_resolution()?;
+
+            assert!(
+                input_cells.is_empty() && !output_cells.is_empty(),
+                Error::InvalidTransactionStructure,
+                "There should be at least 1 ReverseRecordCell in outputs and none in inputs."
+            );
+
+            let sponsor_lock = high_level::load_cell_lock(0, Source::Input)?;
+            let sponsor_lock_hash = high_level::load_cell_lock_hash(0, Source::Input)?;
+            let sponsor_cells = util::find_balance_cells(config_main, sponsor_lock.as_reader(), Source::Input)?;
+            let reverse_record_cell_capacity = u64::from(config_reverse_resolution.record_basic_capacity())
+                + u64::from(config_reverse_resolution.record_prepared_fee_capacity());
+
+            let expected_lock = das_lock();
+            let mut all_cells = sponsor_cells.clone();
+            let mut declarer_lock_hashes = Vec::new();
+            let mut total_reverse_record_capacity = 0;
+            for i in output_cells.iter() {
+                debug!(
+                    "outputs[{}] Verify if the ReverseRecordCell is declared by its lock.",
+                    i
+                );
+
+                let lock = high_level::load_cell_lock(*i, Source::Output)?;
+                let lock_hash = high_level::load_cell_lock_hash(*i, Source::Output)?;
+                assert!(
+                    util::is_type_id_equal(expected_lock.as_reader(), lock.as_reader()),
+                    Error::ReverseRecordCellLockError,
+                    "outputs[{}] The ReverseRecordCell.lock should be the das-lock.",
+                    i
+                );
+                assert!(
+                    lock_hash != sponsor_lock_hash && !declarer_lock_hashes.contains(&lock_hash),
+                    Error::ReverseRecordCellLockError,
+                    "outputs[{}] The ReverseRecordCells should have different locks from each other and the sponsor.",
+                    i
+                );
+                declarer_lock_hashes.push(lock_hash);
+
+                let capacity = high_level::load_cell_capacity(*i, Source::Output)?;
+                assert!(
+                    capacity >= reverse_record_cell_capacity,
+                    Error::ReverseRecordCellCapacityError,
+                    "outputs[{}] The ReverseRecordCell.capacity should be at least {} shannon.(current: {})",
+                    i,
+                    reverse_record_cell_capacity,
+                    capacity
+                );
+                total_reverse_record_capacity += capacity;
+
+                // The cells of the declarer in inputs make its lock verify its signature, and they should be
+                // transferred back to the declarer because the sponsor pays for everything.
+                let declarer_cells = util::find_balance_cells(config_main, lock.as_reader(), Source::Input)?;
+                assert!(
+                    !declarer_cells.is_empty(),
+                    Error::ReverseRecordCellLockError,
+                    "outputs[{}] The declarer of the ReverseRecordCell should have at least 1 BalanceCell in inputs.",
+                    i
+                );
+
+                let declarer_capacity = util::load_cells_capacity(&declarer_cells, Source::Input)?;
+                verifiers::misc::verify_user_get_change(config_main, lock.as_reader(), declarer_capacity)?;
+
+                all_cells.extend(declarer_cells);
+            }
+
+            // Stop transaction builder to spend other cells which belong to neither the sponsor nor the declarers.
+            all_cells.sort_unstable();
+            verifiers::misc::verify_no_more_cells(&all_cells, Source::Input)?;
+
+            debug!("Verify if the change is transferred back to the sponsor properly.");
+
+            let common_fee = u64::from(config_reverse_resolution.common_fee()) * output_cells.len() as u64;
+            let total_input_capacity = util::load_cells_capacity(&sponsor_cells, Source::Input)?;
+            assert!(
+                total_input_capacity >= total_reverse_record_capacity,
+                Error::ReverseRecordCellCapacityError,
+                "The sponsor should pay for all the ReverseRecordCells.(expected: {}, current: {})",
+                total_reverse_record_capacity,
+                total_input_capacity
+            );
+            if total_input_capacity > total_reverse_record_capacity + common_fee {
+                verifiers::misc::verify_user_get_change(
+                    config_main,
+                    sponsor_lock.as_reader(),
+                    total_input_capacity - total_reverse_record_capacity - common_fee,
+                )?;
+            }
+
+            verify_reverse_record_accounts(&mut parser, &output_cells)?;
         }
         b"redeclare_reverse_record" => {
             verify_eip712_hashes_if_has_das_lock(&parser, redeclare_reverse_record_to_semantic)?;
 
+            let config_main = parser.configs.main()?;
             let config_reverse_resolution = parser.configs.reverse_resolution()?;
 
             assert!(
@@ -110,22 +209,48 @@ pub fn main() -> Result<(), Error> {
                 "The ReverseRecordCells should only exist at inputs[0] and outputs[0]."
             );
 
-            // Stop transaction builder to spend users other cells in this transaction.
-            // TODO Support extra cells to pay for transaction fees.
-            verifiers::misc::verify_no_more_cells(&input_cells, Source::Input)?;
-
-            debug!("Verify if the fee paied by ReverseRecordCell.capacity is not out of limitation.");
+            // Stop transaction builder to spend users other cells in this transaction, except the BalanceCells of the
+            // same lock which may pay for the fee and top up the ReverseRecordCell.
+            let sender_lock = high_level::load_cell_lock(input_cells[0], Source::Input)?;
+            let balance_cells = util::find_balance_cells(config_main, sender_lock.as_reader(), Source::Input)?;
+            let mut all_cells = input_cells.clone();
+            all_cells.extend(balance_cells.iter());
+            verifiers::misc::verify_no_more_cells(&all_cells, Source::Input)?;
 
             let expected_fee = u64::from(config_reverse_resolution.common_fee());
             let input_capacity = high_level::load_cell_capacity(0, Source::Input)?;
             let output_capacity = high_level::load_cell_capacity(0, Source::Output)?;
-            assert!(
-                output_capacity >= input_capacity - expected_fee,
-                Error::ReverseRecordCellCapacityError,
-                "The ReverseRecordCell.capacity should remain equal to or more than {} shannon.(available_fee: {})",
-                input_capacity - expected_fee,
-                expected_fee
-            );
+            if balance_cells.is_empty() {
+                debug!("Verify if the fee paied by ReverseRecordCell.capacity is not out of limitation.");
+
+                assert!(
+                    output_capacity >= input_capacity - expected_fee,
+                    Error::ReverseRecordCellCapacityError,
+                    "The ReverseRecordCell.capacity should remain equal to or more than {} shannon.(available_fee: {})",
+                    input_capacity - expected_fee,
+                    expected_fee
+                );
+            } else {
+                debug!("Verify if the fee is paid by the BalanceCells and the change is transferred back properly.");
+
+                assert!(
+                    output_capacity >= input_capacity,
+                    Error::ReverseRecordCellCapacityError,
+                    "The ReverseRecordCell.capacity should not decrease when the fee is paid by BalanceCells.(input: {}, output: {})",
+                    input_capacity,
+                    output_capacity
+                );
+
+                let total_input_capacity = input_capacity + util::load_cells_capacity(&balance_cells, Source::Input)?;
+                // Allow the transaction builder to pay for the user, or something like that.
+                if total_input_capacity > output_capacity + expected_fee {
+                    verifiers::misc::verify_user_get_change(
+                        config_main,
+                        sender_lock.as_reader(),
+                        total_input_capacity - output_capacity - expected_fee,
+                    )?;
+                }
+            }
 
             debug!("Verify if the ReverseRecordCell.lock is consistent.");
 
@@ -136,15 +261,21 @@ pub fn main() -> Result<(), Error> {
                 "The ReverseRecordCell.lock should be consistent in inputs and outputs."
             );
 
-            debug!("Verify if the ReverseRecordCell.data.account has been modified.");
+            debug!(
+                "Verify if the ReverseRecordCell.data.account has been modified or the ReverseRecordCell is topped up."
+            );
 
             let input_account = high_level::load_cell_data(input_cells[0], Source::Input)?;
             let output_account = high_level::load_cell_data(output_cells[0], Source::Output)?;
-            assert!(
-                input_account != output_account,
-                Error::InvalidTransactionStructure,
-                "The ReverseRecordCell.data.account should be modified."
-            );
+            if input_account != output_account {
+                verify_reverse_record_accounts(&mut parser, &output_cells[..1])?;
+            } else {
+                assert!(
+                    output_capacity > input_capacity,
+                    Error::InvalidTransactionStructure,
+                    "The ReverseRecordCell.data.account should be modified, unless the ReverseRecordCell is topped up."
+                );
+            }
         }
         b"retract_reverse_record" => {
             verify_eip712_hashes_if_has_das_lock(&parser, retract_reverse_record_to_semantic)?;
@@ -195,6 +326,39 @@ pub fn main() -> Result<(), Error> {
     Ok(())
 }
 
+fn verify_reverse_record_accounts(parser: &mut WitnessesParser, reverse_record_indexes: &[usize]) -> Result<(), Error> {
+    debug!("Verify if the ReverseRecordCell.data is valid.");
+
+    let mut verified_indexes = Vec::new();
+    for i in reverse_record_indexes.iter() {
+        let data = high_level::load_cell_data(*i, Source::Output)?;
+        match data_parser::reverse_record_cell::is_verified(&data) {
+            Some(true) => verified_indexes.push(*i),
+            Some(false) => {}
+            None => {
+                warn!(
+                    "outputs[{}] The ReverseRecordCell.data should be an account optionally followed by the verified flag.",
+                    i
+                );
+                return Err(Error::ReverseRecordCellAccountError);
+            }
+        }
+    }
+
+    if verified_indexes.is_empty() {
+        return Ok(());
+    }
+
+    // The cells in cell_deps are only needed for proving the ownership, so they are parsed only once and only if needed.
+    parser.parse_cell()?;
+    let timestamp = util::load_oracle_data(OracleCellType::Time)?;
+    for i in verified_indexes {
+        verifiers::reverse_record_cell::verify_reverse_record_ownership(parser, i, Source::Output, timestamp)?;
+    }
+
+    Ok(())
+}
+
 fn reverse_record_to_semantic(parser: &WitnessesParser, source: Source) -> Result<(String, String), Error> {
     let type_id_table_reader = parser.configs.main()?.type_id_table();
     let reverse_record_cells =
@@ -210,7 +374,8 @@ fn reverse_record_to_semantic(parser: &WitnessesParser, source: Source) -> Resul
     );
 
     let data = high_level::load_cell_data(reverse_record_cells[0], source).map_err(Error::from)?;
-    let account = String::from_utf8(data).map_err(|_| Error::EIP712SerializationError)?;
+    let account = String::from_utf8(data_parser::reverse_record_cell::get_account(&data).to_vec())
+        .map_err(|_| Error::EIP712SerializationError)?;
     let lock = Script::from(high_level::load_cell_lock(reverse_record_cells[0], source).map_err(Error::from)?);
     let address = to_semantic_address(parser, lock.as_reader(), LockRole::Owner)?;
 
@@ -222,6 +387,28 @@ fn declare_reverse_record_to_semantic(parser: &WitnessesParser) -> Result<String
     Ok(format!("DECLARE A REVERSE RECORD FROM {} TO {}", address, account))
 }
 
+fn batch_declare_reverse_record_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
+    let type_id_table_reader = parser.configs.main()?.type_id_table();
+    let reverse_record_cells = util::find_cells_by_type_id(
+        ScriptType::Type,
+        type_id_table_reader.reverse_record_cell(),
+        Source::Output,
+    )?;
+
+    // Every declarer signs the same message, so all the pairs are listed for each of them to check its own.
+    let mut pairs = Vec::new();
+    for i in reverse_record_cells {
+        let data = high_level::load_cell_data(i, Source::Output).map_err(Error::from)?;
+        let account = String::from_utf8(data_parser::reverse_record_cell::get_account(&data).to_vec())
+            .map_err(|_| Error::EIP712SerializationError)?;
+        let lock = Script::from(high_level::load_cell_lock(i, Source::Output).map_err(Error::from)?);
+        let address = to_semantic_address(parser, lock.as_reader(), LockRole::Owner)?;
+        pairs.push(format!("FROM {} TO {}", address, account));
+    }
+
+    Ok(format!("DECLARE REVERSE RECORDS: {}", pairs.join(", ")))
+}
+
 fn redeclare_reverse_record_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
     let (address, account) = reverse_record_to_semantic(parser, Source::Output)?;
     Ok(format!("REDECLARE A REVERSE RECORD FROM {} TO {}", address, account))
@@ -5,6 +5,7 @@ use das_core::{
     assert,
     constants::*,
     data_parser, debug,
+    eip712::{sub_account_to_semantic, verify_eip712_hashes},
     error::Error,
     sub_account_witness_parser::{SubAccountEditValue, SubAccountWitness, SubAccountWitnessesParser},
     util::{self, blake2b_256},
@@ -332,6 +333,16 @@ pub fn main() -> Result<(), Error> {
                                     }
                                     SubAccountEditValue::Records(records) => {
                                         verifiers::account_cell::verify_records_keys(&parser, records.as_reader())?;
+
+                                        let changed_records = verifiers::account_cell::get_changed_records(
+                                            witness.sub_account.as_reader().records(),
+                                            records.as_reader(),
+                                        );
+                                        verifiers::account_cell::verify_records_ttl(
+                                            &parser,
+                                            records.as_reader(),
+                                            &changed_records,
+                                        )?;
                                     }
                                     SubAccountEditValue::ExpiredAt(_) => {
                                         warn!("witnesses[{}] Can not edit witness.sub_account.expired_at in this transaction.", witness.index);
@@ -372,6 +383,8 @@ pub fn main() -> Result<(), Error> {
                         output_sub_account_cells[0],
                         expected_register_fee,
                     )?;
+
+                    verify_eip712_hashes(&parser, sub_account_to_semantic)?;
                 }
                 _ => {
                     verify_transaction_profit_not_change_and_fee_spent_correctly(
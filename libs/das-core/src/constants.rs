@@ -2,6 +2,7 @@ use super::types::ScriptLiteral;
 use super::util;
 use alloc::{vec, vec::Vec};
 use ckb_std::ckb_types::packed::*;
+use das_types::constants::CharSetType;
 
 pub use das_dynamic_libs::constants::DasLockType;
 
@@ -50,17 +51,79 @@ pub enum OracleCellType {
     Height = 2,
 }
 
+#[derive(Debug, PartialEq, Copy, Clone)]
+#[repr(u8)]
+pub enum OfferCollectionType {
+    // Any account matching the char set and the length, args: [char_set: u8, length: u8]
+    Pattern = 1,
+    // Any account whose ID is a leaf of the merkle tree, args: [root: 32 bytes]
+    MerkleRoot = 2,
+    // Not a collection, but a counter offer from the owner of an account to an OfferCell, args: [out_point: 36 bytes]
+    Counter = 3,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+#[repr(u8)]
+pub enum IncomePayoutType {
+    // Transfer to a cell with the lock only, but das-lock type 5 requires the balance-cell-type, this is the default.
+    Auto = 0,
+    // Transfer to a cell with the lock only and without any type script.
+    Plain = 1,
+    // Transfer to a cell with the lock and the balance-cell-type.
+    BalanceType = 2,
+    // Transfer to a NervosDAO deposit cell with the lock.
+    DaoDeposit = 3,
+}
+
 pub const CKB_HASH_DIGEST: usize = 32;
 pub const CKB_HASH_PERSONALIZATION: &[u8] = b"ckb-default-hash";
 
 pub const CELL_BASIC_CAPACITY: u64 = 6_100_000_000;
 
+// The dep_type of CellDep, 0 is code and 1 is dep group.
+pub const DEP_TYPE_CODE: u8 = 0;
+
 pub const RATE_BASE: u64 = 10_000;
 
 pub const ACCOUNT_ID_LENGTH: usize = 20;
 pub const ACCOUNT_SUFFIX: &str = ".bit";
 pub const ACCOUNT_MAX_PRICED_LENGTH: u8 = 8;
 
+// The AccountStatus in das-types has no status for leasing, so it is defined here. It takes the last value of u8 because
+// das-types allocates new statuses upward from the existing ones, it should be moved to das-types when it is supported.
+pub const ACCOUNT_STATUS_LEASED: u8 = u8::MAX;
+// The separator between the account and the tail of AccountCell.data, the tail contains the time when the lease ends and
+// the time when each record is updated.
+pub const ACCOUNT_DATA_TAIL_SEPARATOR: u8 = 0;
+
+// The maximum number of accounts which can be sold together in one AccountSaleCell.
+pub const ACCOUNT_SALE_MAX_BUNDLE_SIZE: usize = 10;
+
+// The classes of visually identical characters with the char sets they belong to, they are picked from the confusables of
+// Unicode Technical Standard #39. There is no DataType of ConfigCell for them in das-types yet, so they are defined here,
+// the characters which are not in the ConfigCell of their char set are skipped when the ConfigCell is provided.
+pub const ACCOUNT_CONFUSABLES: &[&[(&str, CharSetType)]] = &[
+    &[
+        ("o", CharSetType::En),
+        ("0", CharSetType::Digit),
+        ("〇", CharSetType::ZhHans),
+    ],
+    &[
+        ("l", CharSetType::En),
+        ("1", CharSetType::Digit),
+        ("丨", CharSetType::ZhHans),
+    ],
+];
+
+// The placeholders in OfferCollectionType::Pattern which means there is no limitation on char set or length.
+pub const OFFER_COLLECTION_ANY_CHAR_SET: u8 = u8::MAX;
+pub const OFFER_COLLECTION_ANY_LENGTH: u8 = 0;
+
+// The flag after the account in ReverseRecordCell.data which means the declarer has been verified as the owner of an
+// unexpired account in normal status. The ownership can only change by transferring the account, so the flag holds as
+// long as the ReverseRecordCell is newer than the last transfer of the account.
+pub const REVERSE_RECORD_VERIFIED: u8 = 1;
+
 pub const CUSTOM_KEYS_NAMESPACE: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz_";
 
 pub const SECP_SIGNATURE_SIZE: usize = 65;
@@ -73,6 +136,13 @@ pub const YEAR_SEC: u64 = DAY_SEC * DAYS_OF_YEAR;
 
 pub const PRE_ACCOUNT_CELL_TIMEOUT: u64 = DAY_SEC;
 
+// The bond which a keeper must lock in the ProposalCell, it is refunded with the capacity of the ProposalCell when the
+// proposal is confirmed. There is no field for it in ConfigCellProposal of das-types yet, so it is defined here.
+pub const PROPOSAL_BOND_CAPACITY: u64 = 20_000_000_000;
+// The rate of the bond which is slashed to the DAS wallet when a stale ProposalCell is recycled, the rest is refunded to
+// the proposer.
+pub const PROPOSAL_RECYCLE_SLASH_RATE: u64 = 5_000;
+
 pub fn super_lock() -> Script {
     #[cfg(feature = "dev")]
     let super_lock = ScriptLiteral {
@@ -211,6 +281,31 @@ pub fn das_lock() -> Script {
     util::script_literal_to_script(das_lock)
 }
 
+pub fn dao_type_script() -> Script {
+    #[cfg(feature = "dev")]
+    let dao_type_script = ScriptLiteral {
+        code_hash: [
+            104, 104, 64, 112, 25, 61, 89, 15, 108, 155, 156, 206, 150, 58, 220, 185, 69, 98, 193, 218, 168, 98, 90,
+            186, 156, 240, 219, 165, 242, 100, 110, 111,
+        ],
+        hash_type: ScriptHashType::Type,
+        args: Vec::new(),
+    };
+
+    // The type ID of the NervosDAO is the same in all chains, because it is calculated from the genesis block.
+    #[cfg(any(feature = "local", feature = "testnet", feature = "mainnet"))]
+    let dao_type_script = ScriptLiteral {
+        code_hash: [
+            130, 215, 109, 27, 117, 254, 47, 217, 162, 125, 251, 170, 101, 160, 57, 34, 26, 56, 13, 118, 201, 38, 243,
+            120, 211, 248, 28, 243, 231, 225, 63, 46,
+        ],
+        hash_type: ScriptHashType::Type,
+        args: Vec::new(),
+    };
+
+    util::script_literal_to_script(dao_type_script)
+}
+
 pub fn time_cell_type() -> Script {
     #[cfg(feature = "dev")]
     let time_cell_type = ScriptLiteral {
@@ -1,4 +1,5 @@
-use crate::constants::ACCOUNT_ID_LENGTH;
+use crate::constants::{ACCOUNT_DATA_TAIL_SEPARATOR, ACCOUNT_ID_LENGTH};
+use alloc::vec::Vec;
 use core::convert::TryInto;
 
 pub fn get_id(data: &[u8]) -> &[u8] {
@@ -23,6 +24,52 @@ pub fn get_expired_at(data: &[u8]) -> u64 {
 
 pub fn get_account(data: &[u8]) -> &[u8] {
     let start = 32 + ACCOUNT_ID_LENGTH * 2 + 8;
-    data.get(start..)
-        .expect("AccountCell should have some bytes after the leading 80 bytes for account.")
+    let bytes = data
+        .get(start..)
+        .expect("AccountCell should have some bytes after the leading 80 bytes for account.");
+    match bytes.iter().position(|byte| *byte == ACCOUNT_DATA_TAIL_SEPARATOR) {
+        Some(end) => &bytes[..end],
+        None => bytes,
+    }
+}
+
+/// The tail of the AccountCell.data is like `account | 0x00 | lease_end | records_updated_at`, an account never contains
+/// 0x00 so the tail can be told apart from the account. The tail is optional, it is omitted when all of its fields are
+/// empty.
+fn get_tail(data: &[u8]) -> Option<&[u8]> {
+    let start = 32 + ACCOUNT_ID_LENGTH * 2 + 8 + get_account(data).len();
+    data.get((start + 1)..)
+}
+
+/// The lease_end is 0 when the AccountCell is not leased.
+pub fn get_lease_end(data: &[u8]) -> Option<u64> {
+    get_tail(data).and_then(|tail| {
+        let bytes = tail
+            .get(..8)
+            .and_then(|bytes| bytes.try_into().ok())
+            .expect("AccountCell.data should have 8 bytes of lease_end after the separator.");
+        match u64::from_le_bytes(bytes) {
+            0 => None,
+            lease_end => Some(lease_end),
+        }
+    })
+}
+
+/// The records_updated_at contains a u64 for each record in the witness in the same order. The AccountCells whose records
+/// have not been edited since it was introduced may not have it.
+pub fn get_records_updated_at(data: &[u8]) -> Vec<u64> {
+    match get_tail(data).and_then(|tail| tail.get(8..)) {
+        Some(bytes) => {
+            assert_eq!(
+                bytes.len() % 8,
+                0,
+                "AccountCell.data should have 8 bytes for each records_updated_at."
+            );
+            bytes
+                .chunks(8)
+                .map(|item| u64::from_le_bytes(item.try_into().unwrap()))
+                .collect()
+        }
+        None => Vec::new(),
+    }
 }
@@ -0,0 +1,15 @@
+use crate::constants::ACCOUNT_ID_LENGTH;
+use alloc::vec::Vec;
+
+/// Get the IDs of the extra accounts sold together with the account in AccountSaleCell.witness.
+///
+/// The outputs_data of AccountSaleCell is the hash of its witness, followed by the account IDs of other accounts in the
+/// bundle if there are any. Return None if the bytes after the hash can not be split into account IDs.
+pub fn get_bundle_account_ids(data: &[u8]) -> Option<Vec<&[u8]>> {
+    let bytes = data.get(32..)?;
+    if bytes.len() % ACCOUNT_ID_LENGTH != 0 {
+        return None;
+    }
+
+    Some(bytes.chunks(ACCOUNT_ID_LENGTH).collect())
+}
@@ -0,0 +1,22 @@
+use crate::constants::IncomePayoutType;
+use alloc::borrow::ToOwned;
+
+// The outputs_data of IncomeCell is the hash of its witness, which may be followed by:
+//
+// - payouts: 1 byte for each record in the same order as IncomeCellData.records, see `IncomePayoutType`. If the payouts
+//   are omitted, all records are paid with `IncomePayoutType::Auto`.
+
+pub fn is_data_length_valid(data: &[u8], records_len: usize) -> bool {
+    data.len() == 32 || data.len() == 32 + records_len
+}
+
+pub fn is_payout_valid(payout: u8) -> bool {
+    payout <= IncomePayoutType::DaoDeposit as u8
+}
+
+/// Get the payout descriptor of the record at the index, return `IncomePayoutType::Auto` if the payouts are omitted.
+pub fn get_payout(data: &[u8], index: usize) -> u8 {
+    data.get(32 + index)
+        .map(|v| v.to_owned())
+        .unwrap_or(IncomePayoutType::Auto as u8)
+}
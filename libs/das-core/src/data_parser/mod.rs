@@ -1,5 +1,10 @@
 pub mod account_cell;
+pub mod account_sale_cell;
 pub mod apply_register_cell;
 pub mod das_lock_args;
+pub mod income_cell;
+pub mod offer_cell;
 pub mod pre_account_cell;
+pub mod proposal_cell;
+pub mod reverse_record_cell;
 pub mod sub_account_cell;
@@ -0,0 +1,28 @@
+use alloc::borrow::ToOwned;
+use core::convert::TryInto;
+
+// The outputs_data of OfferCell is the hash of its witness, which may be followed by:
+//
+// - expired_at: 8 bytes in little-endian, 0 means the OfferCell will never expire.
+// - collection_type: 1 byte, exists only when the OfferCell is bidding on a collection of accounts, see `OfferCollectionType`.
+// - collection_args: the rest bytes, the args of the collection.
+
+pub fn is_data_length_valid(data: &[u8]) -> bool {
+    data.len() == 32 || data.len() >= 40
+}
+
+/// Get the expiration time of the OfferCell, return 0 if the OfferCell will never expire.
+pub fn get_expired_at(data: &[u8]) -> u64 {
+    data.get(32..40)
+        .map(|raw| u64::from_le_bytes(raw.try_into().unwrap()))
+        .unwrap_or(0)
+}
+
+/// Get the type of the collection which the OfferCell is bidding on, return None if the offer is on one account.
+pub fn get_collection_type(data: &[u8]) -> Option<u8> {
+    data.get(40).map(|v| v.to_owned())
+}
+
+pub fn get_collection_args(data: &[u8]) -> Option<&[u8]> {
+    data.get(41..)
+}
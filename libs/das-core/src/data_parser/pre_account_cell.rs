@@ -1,4 +1,13 @@
+// The outputs_data of PreAccountCell is the hash of its witness and the account ID, which may be followed by:
+//
+// - sponsor_lock: the rest bytes, the molecule encoded lock script of the sponsor who paid for the registration.
+
 pub fn get_id(data: &[u8]) -> &[u8] {
-    data.get(32..)
-        .expect("PreAccountCell should have 40 bytes of data.")
+    data.get(32..52)
+        .expect("PreAccountCell should have at least 52 bytes of data.")
+}
+
+/// Get the lock of the sponsor who paid for the registration, return None if the registration is paid by the user.
+pub fn get_sponsor_lock(data: &[u8]) -> Option<&[u8]> {
+    data.get(52..).filter(|bytes| !bytes.is_empty())
 }
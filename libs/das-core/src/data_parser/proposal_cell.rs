@@ -0,0 +1,35 @@
+use alloc::{vec, vec::Vec};
+
+// The outputs_data of ProposalCell is the hash of its witness, which may be followed by:
+//
+// - confirmed_slices: a bitmap of the slices which have been confirmed, the slice at index i is confirmed if the bit
+//   (i % 8) of the byte (i / 8) is 1. It only exists when the proposal is confirmed slice by slice.
+
+pub fn get_bitmap_length(slices_len: usize) -> usize {
+    let mut length = slices_len / 8;
+    if slices_len % 8 != 0 {
+        length += 1;
+    }
+
+    length
+}
+
+pub fn is_data_length_valid(data: &[u8], slices_len: usize) -> bool {
+    data.len() == 32 || data.len() == 32 + get_bitmap_length(slices_len)
+}
+
+pub fn is_slice_confirmed(data: &[u8], index: usize) -> bool {
+    data.get(32 + index / 8)
+        .map(|byte| (byte >> (index % 8)) & 1 == 1)
+        .unwrap_or(false)
+}
+
+/// Generate the bitmap of confirmed slices which can be appended to the hash of witness.
+pub fn gen_confirmed_slices(slices_len: usize, confirmed: &[usize]) -> Vec<u8> {
+    let mut bitmap = vec![0u8; get_bitmap_length(slices_len)];
+    for index in confirmed {
+        bitmap[index / 8] |= 1 << (index % 8);
+    }
+
+    bitmap
+}
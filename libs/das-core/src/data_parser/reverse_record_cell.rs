@@ -0,0 +1,23 @@
+use crate::constants::REVERSE_RECORD_VERIFIED;
+
+// The outputs_data of ReverseRecordCell is the account in UTF-8, which may be followed by:
+//
+// - verified flag: a 0 byte which never appears in accounts and then REVERSE_RECORD_VERIFIED, exists only when the
+//   declarer has been verified to own the account at the time of declaration.
+
+pub fn get_account(data: &[u8]) -> &[u8] {
+    match data.iter().position(|c| *c == 0) {
+        Some(i) => &data[..i],
+        None => data,
+    }
+}
+
+/// Return None if there are some bytes after the account but they are not a valid flag.
+pub fn is_verified(data: &[u8]) -> Option<bool> {
+    let account = get_account(data);
+    match &data[account.len()..] {
+        [] => Some(false),
+        [0, REVERSE_RECORD_VERIFIED] => Some(true),
+        _ => None,
+    }
+}
@@ -1,4 +1,7 @@
-use super::{assert, constants::*, data_parser, debug, error::Error, util, warn, witness_parser::WitnessesParser};
+use super::{
+    assert, constants::*, data_parser, debug, error::Error, sub_account_witness_parser::SubAccountWitnessesParser,
+    util, warn, witness_parser::WitnessesParser,
+};
 use alloc::{
     boxed::Box,
     collections::btree_map::BTreeMap,
@@ -45,12 +48,21 @@ pub fn verify_eip712_hashes(
     let das_lock_reader = das_lock.as_reader();
 
     let mut i = match parser.action.as_slice() {
-        // In buy_account transaction, the inputs[0] and inputs[1] is belong to sellers, because buyers have paid enough, so we do not need
-        // their signature here.
-        b"buy_account" => 2,
+        // In buy_account transaction, the AccountCells and the AccountSaleCell after them are belong to sellers, because buyers have paid
+        // enough, so we do not need their signature here.
+        b"buy_account" => {
+            let account_cell_type_id = parser.configs.main()?.type_id_table().account_cell();
+            let input_account_cells =
+                util::find_cells_by_type_id(ScriptType::Type, account_cell_type_id, Source::Input)?;
+            input_account_cells.len() + 1
+        }
         // In accept_offer transaction, the inputs[0] is belong to buyer, because it is seller to send this transaction for accepting offer,
         // so we do not need the buyer's signature here.
         b"accept_offer" => 1,
+        // In accept_counter_offer transaction, the inputs[0] and inputs[1] is belong to seller. Like the AccountCells in buy_account
+        // transaction, the AccountCell has been locked in Selling status by the counter offer, so we do not need the seller's
+        // signature here.
+        b"accept_counter_offer" => 2,
         _ => 0,
     };
     let mut input_groups_idxs: BTreeMap<Vec<u8>, Vec<usize>> = BTreeMap::new();
@@ -110,17 +122,27 @@ pub fn verify_eip712_hashes(
                 util::hex_string(&expected_hash)
             );
 
-            // CAREFUL We need to skip the final verification here because transactions are often change when developing, that will break all tests contains EIP712 verification.
-            if cfg!(not(feature = "dev")) {
-                assert!(
-                    &item.typed_data_hash == expected_hash.as_slice(),
-                    Error::EIP712SignatureError,
-                    "Inputs[{}] The hash of EIP712 typed data is mismatched.(current: 0x{}, expected: 0x{})",
-                    index,
-                    util::hex_string(&item.typed_data_hash),
-                    util::hex_string(&expected_hash)
+            // CAREFUL This is the only exception to the verification, and it is compiled into dev builds only. Only the
+            // fixtures of transfer_account, edit_manager, edit_records, enable_sub_account and create_sub_account call
+            // `set_das_message`, so they are signed by the tests and always verified. The fixtures of the other
+            // actions still carry the empty hash, so the check does not run unconditionally yet. Remove this together
+            // with signing those fixtures.
+            if cfg!(feature = "dev") && item.typed_data_hash == [0u8; 32] {
+                warn!(
+                    "Inputs[{}] Skip verifying the empty hash of EIP712 typed data in development mode.",
+                    index
                 );
+                continue;
             }
+
+            assert!(
+                &item.typed_data_hash == expected_hash.as_slice(),
+                Error::EIP712SignatureError,
+                "Inputs[{}] The hash of EIP712 typed data is mismatched.(current: 0x{}, expected: 0x{})",
+                index,
+                util::hex_string(&item.typed_data_hash),
+                util::hex_string(&expected_hash)
+            );
         }
     }
 
@@ -578,6 +600,86 @@ pub fn to_semantic_capacity(capacity: u64) -> String {
     ret
 }
 
+/// Describe the differences of records, like `ADD address.eth, CHANGE profile.twitter, REMOVE dweb.ipfs`.
+///
+/// The records are identified by their type, key and label, so a record is changed when its value or ttl is changed.
+pub fn to_semantic_records_diff(
+    input_records: das_packed::RecordsReader,
+    output_records: das_packed::RecordsReader,
+) -> Result<String, Error> {
+    fn is_same_record(a: das_packed::RecordReader, b: das_packed::RecordReader) -> bool {
+        a.record_type().raw_data() == b.record_type().raw_data()
+            && a.record_key().raw_data() == b.record_key().raw_data()
+            && a.record_label().raw_data() == b.record_label().raw_data()
+    }
+
+    fn to_record_name(record: das_packed::RecordReader) -> Result<String, Error> {
+        let record_type =
+            String::from_utf8(record.record_type().raw_data().to_vec()).map_err(|_| Error::EIP712SerializationError)?;
+        let record_key =
+            String::from_utf8(record.record_key().raw_data().to_vec()).map_err(|_| Error::EIP712SerializationError)?;
+        Ok(format!("{}.{}", record_type, record_key))
+    }
+
+    let mut changes = Vec::new();
+    for output_record in output_records.iter() {
+        match input_records
+            .iter()
+            .find(|record| is_same_record(*record, output_record))
+        {
+            Some(input_record) => {
+                if input_record.as_slice() != output_record.as_slice() {
+                    changes.push(format!("CHANGE {}", to_record_name(output_record)?));
+                }
+            }
+            None => changes.push(format!("ADD {}", to_record_name(output_record)?)),
+        }
+    }
+    for input_record in input_records.iter() {
+        if !output_records.iter().any(|record| is_same_record(record, input_record)) {
+            changes.push(format!("REMOVE {}", to_record_name(input_record)?));
+        }
+    }
+
+    if changes.is_empty() {
+        Ok(String::from("NOTHING"))
+    } else {
+        Ok(changes.join(", "))
+    }
+}
+
+/// Generate DAS_MESSAGE of create_sub_account from the sub-account witnesses, like
+/// `CREATE SUB-ACCOUNTS a.xxx.bit(1 YEARS, 5 CKB), b.xxx.bit(2 YEARS, 10 CKB)`.
+///
+/// The edit_sub_account is not covered, because it is authorized by the signatures in sub-account witnesses instead of
+/// the signatures of das-lock.
+pub fn sub_account_to_semantic(parser: &WitnessesParser) -> Result<String, Error> {
+    let sub_account_parser = SubAccountWitnessesParser::new()?;
+    let mut items = Vec::new();
+
+    match parser.action.as_slice() {
+        b"create_sub_account" => {
+            let price = u64::from(parser.configs.sub_account()?.new_sub_account_price());
+            for witness_ret in sub_account_parser.iter() {
+                let witness = witness_ret?;
+                let sub_account_reader = witness.sub_account.as_reader();
+                let years = (u64::from(sub_account_reader.expired_at())
+                    - u64::from(sub_account_reader.registered_at()))
+                    / YEAR_SEC;
+                items.push(format!(
+                    "{}({} YEARS, {})",
+                    util::get_sub_account_name_from_reader(sub_account_reader),
+                    years,
+                    to_semantic_capacity(price * years)
+                ));
+            }
+
+            Ok(format!("CREATE SUB-ACCOUNTS {}", items.join(", ")))
+        }
+        _ => Err(Error::ActionNotSupported),
+    }
+}
+
 fn to_typed_script(parser: &WitnessesParser, script_type: ScriptType, script: das_packed::ScriptReader) -> String {
     let code_hash = if script_type == ScriptType::Lock {
         match parser.get_lock_script_type(script) {
@@ -791,4 +893,36 @@ mod test {
         let result = to_semantic_capacity(100_000_000_000);
         assert_eq!(result, expected);
     }
+
+    fn gen_record(type_: &str, key: &str, value: &str) -> das_packed::Record {
+        das_packed::Record::new_builder()
+            .record_type(das_packed::Bytes::from(type_.as_bytes().to_vec()))
+            .record_key(das_packed::Bytes::from(key.as_bytes().to_vec()))
+            .record_label(das_packed::Bytes::default())
+            .record_value(das_packed::Bytes::from(value.as_bytes().to_vec()))
+            .record_ttl(das_packed::Uint32::from(300))
+            .build()
+    }
+
+    #[test]
+    fn test_eip712_to_semantic_records_diff() {
+        let input_records = das_packed::Records::new_builder()
+            .push(gen_record("address", "eth", "0x1111"))
+            .push(gen_record("profile", "twitter", "das"))
+            .push(gen_record("dweb", "ipfs", "QmXxx"))
+            .build();
+        let output_records = das_packed::Records::new_builder()
+            .push(gen_record("address", "eth", "0x1111"))
+            .push(gen_record("profile", "twitter", "dotbit"))
+            .push(gen_record("address", "btc", "1Xxx"))
+            .build();
+
+        let expected = "CHANGE profile.twitter, ADD address.btc, REMOVE dweb.ipfs";
+        let result = to_semantic_records_diff(input_records.as_reader(), output_records.as_reader()).unwrap();
+        assert_eq!(result, expected);
+
+        let expected = "NOTHING";
+        let result = to_semantic_records_diff(input_records.as_reader(), input_records.as_reader()).unwrap();
+        assert_eq!(result, expected);
+    }
 }
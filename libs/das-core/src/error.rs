@@ -74,6 +74,11 @@ pub enum Error {
     PreRegisterOwnerLockArgsIsInvalid,
     PreRegisterIsNotTimeout,
     PreRegisterRefundCapacityError,
+    PreRegisterPrevAccountCellError, // 85
+    PreRegisterSponsorError,
+    PreRegisterAccountIsConfusable,
+    PreRegisterPreAccountCellExpired,
+    PreRegisterPreAccountCellNotEarliest,
     ProposalSliceIsNotSorted = 90,
     ProposalSliceIsDiscontinuity,
     ProposalSliceRelatedCellNotFound,
@@ -98,12 +103,18 @@ pub enum Error {
     ProposalRecycleNeedWaitLonger,
     ProposalRecycleRefundAmountError, // 120
     PrevProposalItemNotFound,
+    ProposalSliceConfirmError,
+    ProposalPreAccountCellNotEarliest,
+    ProposalBondCapacityError,
     IncomeCellConsolidateConditionNotSatisfied = -126,
     IncomeCellConsolidateError,
     IncomeCellConsolidateWaste,
     IncomeCellTransferError,
     IncomeCellCapacityError,
     IncomeCellProfitMismatch,
+    IncomeCellWithdrawError,
+    IncomeCellPayoutError,
+    IncomeCellMergeError, // -118
     AccountCellPermissionDenied = -110,
     AccountCellOwnerLockShouldNotBeModified,
     AccountCellOwnerLockShouldBeModified,
@@ -127,6 +138,10 @@ pub enum Error {
     EIP712SematicError,
     EIP712DecodingWitnessArgsError,
     EIP712SignatureError,
+    AccountCellLeaseError, // -86
+    AccountCellRecordValueInvalid,
+    AccountCellRecordTTLInvalid,
+    AccountCellRecordUpdatedAtError,
     BalanceCellFoundSomeOutputsLackOfType = -80,
     AccountSaleCellCapacityError,
     AccountSaleCellRefundError,
@@ -144,10 +159,15 @@ pub enum Error {
     OfferCellNewOwnerError,
     OfferCellFieldCanNotModified,
     OfferCellAccountMismatch,
+    AccountSaleCellBundleError, // -63
+    OfferCellCollectionError,
+    OfferCellExpirationError,
     ReverseRecordCellLockError = -60,
     ReverseRecordCellCapacityError,
     ReverseRecordCellAccountError,
     ReverseRecordCellChangeError,
+    OfferCellCounterOfferError, // -56
+    ReverseRecordCellOwnershipError,
     SubAccountCellSMTRootError = -50,
     SubAccountWitnessSMTRootError,
     SubAccountCellCapacityError,
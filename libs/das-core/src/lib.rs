@@ -14,3 +14,5 @@ pub mod types;
 pub mod util;
 pub mod verifiers;
 pub mod witness_parser;
+
+pub use das_income_planner as income_planner;
@@ -40,6 +40,49 @@ pub struct CharSet {
     pub name: CharSetType,
     pub global: bool,
     pub data: Vec<u8>,
+    // The ranges of characters in data, sorted by the bytes of characters for binary searching.
+    index: Vec<(usize, usize)>,
+}
+
+impl CharSet {
+    /// Create a CharSet from its zero-separated data, the characters are indexed once here so every lookup is
+    /// logarithmic to the size of the CharSet. The characters are required to be sorted by their bytes in ConfigCells,
+    /// so they are only verified in the same pass instead of being sorted.
+    ///
+    /// CAREFUL! The ConfigCellCharSetXXXX deployed before this requirement are not sorted, they must be redeployed with
+    /// sorted data before upgrading the contracts, otherwise every transaction checking account characters will fail.
+    pub fn new(name: CharSetType, global: bool, data: Vec<u8>) -> Result<Self, Error> {
+        let mut index: Vec<(usize, usize)> = Vec::new();
+        let mut from = 0;
+        for (i, item) in data.iter().enumerate() {
+            if item == &0 {
+                if let Some((prev_from, prev_to)) = index.last() {
+                    assert!(
+                        data[*prev_from..*prev_to] < data[from..i],
+                        Error::ConfigCellWitnessDecodingError,
+                        "The characters of CharSet {:?} should be sorted by bytes without duplication.(char: {:?})",
+                        name,
+                        &data[from..i]
+                    );
+                }
+                index.push((from, i));
+                from = i + 1;
+            }
+        }
+
+        Ok(CharSet {
+            name,
+            global,
+            data,
+            index,
+        })
+    }
+
+    pub fn contains(&self, char_bytes: &[u8]) -> bool {
+        self.index
+            .binary_search_by(|(from, to)| self.data[*from..*to].cmp(char_bytes))
+            .is_ok()
+    }
 }
 
 #[derive(Debug)]
@@ -77,8 +120,7 @@ impl Configs {
             config_witnesses,
             account: OnceCell::new(),
             apply: OnceCell::new(),
-            // Chinese charsets is still not enabled.
-            char_set: vec![OnceCell::new(); CHAR_SET_LENGTH - 2],
+            char_set: vec![OnceCell::new(); CHAR_SET_LENGTH],
             income: OnceCell::new(),
             main: OnceCell::new(),
             price: OnceCell::new(),
@@ -277,12 +319,12 @@ impl Configs {
                     raw.len()
                 );
 
-                let char_set = CharSet {
-                    name: char_set_type,
+                let char_set = CharSet::new(
+                    char_set_type,
                     // skip WITNESS_LENGTH_BYTES bytes length, and the WITNESS_LENGTH_BYTES+1 byte is global flag, then the following bytes is data
-                    global: raw.get(WITNESS_LENGTH_BYTES).unwrap() == &1u8,
-                    data: raw.get((WITNESS_LENGTH_BYTES + 1)..).unwrap().to_vec(),
-                };
+                    raw.get(WITNESS_LENGTH_BYTES).unwrap() == &1u8,
+                    raw.get((WITNESS_LENGTH_BYTES + 1)..).unwrap().to_vec(),
+                )?;
 
                 Ok(char_set)
             });
@@ -168,6 +168,28 @@ pub fn find_only_cell_by_type_id(
     Ok(cells[0])
 }
 
+/// Find the index of a cell in cell_deps by its out point, it is used to prove that the cell is still live.
+///
+/// CAREFUL The index in Source::CellDep is counted after the dep groups are expanded, so the cell should be put before any
+/// dep group, otherwise the index can not be located.
+pub fn find_cell_dep_by_out_point(out_point: &[u8]) -> Result<Option<usize>, Error> {
+    let tx = high_level::load_transaction()?;
+    for (i, cell_dep) in tx.raw().cell_deps().into_iter().enumerate() {
+        if cell_dep.out_point().as_slice() == out_point {
+            return Ok(Some(i));
+        }
+
+        das_assert!(
+            cell_dep.dep_type().as_slice() == &[DEP_TYPE_CODE],
+            Error::InvalidTransactionStructure,
+            "CellDeps[{}] The cell found by out point should be put before any dep group.",
+            i
+        );
+    }
+
+    Ok(None)
+}
+
 pub fn find_cells_by_script(
     script_type: ScriptType,
     script: ScriptReader,
@@ -622,6 +644,31 @@ pub fn is_account_id_in_collection(account_id: &[u8], collection: &[u8]) -> bool
     };
 }
 
+/// Verify if the leaf is in the merkle tree with the given root.
+///
+/// Every pair of nodes is sorted before hashing, so the proof only contains the sibling hashes from bottom to top and no
+/// position is needed.
+pub fn is_leaf_in_merkle_tree(root: &[u8], leaf: [u8; 32], proof: &[u8]) -> bool {
+    if proof.len() % 32 != 0 {
+        return false;
+    }
+
+    let mut node = leaf;
+    for sibling in proof.chunks(32) {
+        let mut pair = Vec::with_capacity(64);
+        if node.as_ref() <= sibling {
+            pair.extend_from_slice(&node);
+            pair.extend_from_slice(sibling);
+        } else {
+            pair.extend_from_slice(sibling);
+            pair.extend_from_slice(&node);
+        }
+        node = blake2b_256(pair);
+    }
+
+    node.as_ref() == root
+}
+
 pub fn calc_account_storage_capacity(
     config_account: das_packed::ConfigCellAccountReader,
     account_name_storage: u64,
@@ -721,11 +768,15 @@ pub fn get_action_required_role(action: &[u8]) -> Option<LockRole> {
         b"edit_manager" => Some(LockRole::Owner),
         b"edit_records" => Some(LockRole::Manager),
         b"enable_sub_account" => Some(LockRole::Owner),
+        b"lease_account" => Some(LockRole::Owner),
         // account-sale-cell-type
         b"start_account_sale" => Some(LockRole::Owner),
         b"edit_account_sale" => Some(LockRole::Owner),
         b"cancel_account_sale" => Some(LockRole::Owner),
         b"buy_account" => Some(LockRole::Owner),
+        // offer-cell-type
+        b"counter_offer" => Some(LockRole::Owner),
+        b"cancel_offer" => Some(LockRole::Owner),
         _ => None,
     }
 }
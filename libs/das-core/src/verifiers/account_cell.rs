@@ -1,5 +1,5 @@
 use crate::{
-    assert, constants::DasLockType, constants::*, data_parser, error::Error, util, warn,
+    assert, constants::DasLockType, constants::*, data_parser, error::Error, util, verifiers::record_value, warn,
     witness_parser::WitnessesParser,
 };
 use alloc::borrow::ToOwned;
@@ -201,6 +201,34 @@ pub fn verify_account_data_consistent(
             output_expired_at
         );
     }
+    if !except.contains(&"lease_end") {
+        let input_lease_end = data_parser::account_cell::get_lease_end(&input_data);
+        let output_lease_end = data_parser::account_cell::get_lease_end(&output_data);
+
+        assert!(
+            input_lease_end == output_lease_end,
+            Error::AccountCellDataNotConsistent,
+            "The data.lease_end field of inputs[{}] and outputs[{}] should be the same. (inputs: {:?}, outputs: {:?})",
+            input_account_index,
+            output_account_index,
+            input_lease_end,
+            output_lease_end
+        );
+    }
+    if !except.contains(&"records_updated_at") {
+        let input_records_updated_at = data_parser::account_cell::get_records_updated_at(&input_data);
+        let output_records_updated_at = data_parser::account_cell::get_records_updated_at(&output_data);
+
+        assert!(
+            input_records_updated_at == output_records_updated_at,
+            Error::AccountCellDataNotConsistent,
+            "The data.records_updated_at field of inputs[{}] and outputs[{}] should be the same. (inputs: {:?}, outputs: {:?})",
+            input_account_index,
+            output_account_index,
+            input_records_updated_at,
+            output_records_updated_at
+        );
+    }
 
     Ok(())
 }
@@ -275,10 +303,33 @@ pub fn verify_account_witness_consistent<'a>(
         (registered_at, "registered_at")
     );
 
+    if !except.contains(&"records") {
+        let input_records = input_witness_reader.records();
+        let output_records = output_witness_reader.records();
+
+        assert!(
+            input_records.len() == output_records.len(),
+            Error::AccountCellProtectFieldIsModified,
+            "The witness.records field of inputs[{}] and outputs[{}] should have the same length.",
+            input_index,
+            output_index
+        );
+
+        for (i, (input_record, output_record)) in input_records.iter().zip(output_records.iter()).enumerate() {
+            assert!(
+                util::is_reader_eq(input_record, output_record),
+                Error::AccountCellProtectFieldIsModified,
+                "The witness.records[{}] field of inputs[{}] and outputs[{}] should be the same.",
+                i,
+                input_index,
+                output_index
+            );
+        }
+    }
+
     assert_field_consistent_if_not_except!(
         input_witness_reader,
         output_witness_reader,
-        (records, "records"),
         (last_transfer_account_at, "last_transfer_account_at"),
         (last_edit_manager_at, "last_edit_manager_at"),
         (last_edit_records_at, "last_edit_records_at"),
@@ -526,30 +577,11 @@ pub fn verify_account_chars(parser: &WitnessesParser, chars_reader: AccountChars
         }
     }
 
-    let tmp = vec![0u8];
     let char_sets = parser.configs.char_set();
-    let mut required_char_sets = vec![tmp.as_slice(); CHAR_SET_LENGTH];
     for account_char in chars_reader.iter() {
         let char_set_index = u32::from(account_char.char_set_name()) as usize;
-        if required_char_sets[char_set_index].len() <= 1 {
-            let char_set = char_sets[char_set_index].unwrap();
-            required_char_sets[char_set_index] = char_set.data.as_slice();
-        }
-
-        let account_char_bytes = account_char.bytes().raw_data();
-        let mut found = false;
-        let mut from = 0;
-        for (i, item) in required_char_sets[char_set_index].iter().enumerate() {
-            if item == &0 {
-                let char_bytes = required_char_sets[char_set_index].get(from..i).unwrap();
-                if account_char_bytes == char_bytes {
-                    found = true;
-                    break;
-                }
-
-                from = i + 1;
-            }
-        }
+        let char_set = char_sets[char_set_index].unwrap();
+        let found = char_set.contains(account_char.bytes().raw_data());
 
         assert!(
             found,
@@ -564,6 +596,140 @@ pub fn verify_account_chars(parser: &WitnessesParser, chars_reader: AccountChars
     Ok(())
 }
 
+fn get_confusable_class(char_bytes: &[u8]) -> Option<&'static [(&'static str, CharSetType)]> {
+    ACCOUNT_CONFUSABLES
+        .iter()
+        .find(|class| class.iter().any(|(item, _)| item.as_bytes() == char_bytes))
+        .copied()
+}
+
+/// Verify if there is no registered or pending account visually identical to the account.
+///
+/// The visually identical accounts are derived by replacing the characters of the account with the others in their
+/// confusable classes. Only the accounts which can be registered are derived, so a character is skipped if the ConfigCell
+/// of its char set is provided and does not contain it, or if it makes the account use more than one non-global char set.
+/// Then an AccountCell in cell_deps or inputs is required to prove each of them is not registered, which means its
+/// account ID is between the ID of the AccountCell and its next, and none of them should be in `pending_account_ids`.
+///
+/// CAREFUL! The number of the derived accounts grows exponentially with the confusable characters, so an account with too
+/// many of them may exceed the cycles limit of a transaction.
+pub fn verify_account_not_confusable(
+    parser: &WitnessesParser,
+    chars_reader: AccountCharsReader,
+    pending_account_ids: &[Vec<u8>],
+) -> Result<(), Error> {
+    debug!("Verify if the account is not confusable with any registered account.");
+
+    let account = chars_reader.as_readable();
+    let char_sets = parser.configs.char_set();
+
+    // Every variant is the bytes of the account with the non-global char set it uses.
+    let mut variants: Vec<(Vec<u8>, Option<usize>)> = vec![(Vec::new(), None)];
+    let mut has_confusable = false;
+    for account_char in chars_reader.iter() {
+        let char_bytes = account_char.bytes().raw_data();
+        let mut candidates = vec![(char_bytes, u32::from(account_char.char_set_name()) as usize)];
+        if let Some(class) = get_confusable_class(char_bytes) {
+            for (item, char_set_type) in class.iter() {
+                if item.as_bytes() != char_bytes {
+                    candidates.push((item.as_bytes(), *char_set_type as usize));
+                }
+            }
+        }
+
+        let mut next_variants = Vec::with_capacity(variants.len() * candidates.len());
+        for (candidate_index, (candidate, char_set_index)) in candidates.iter().enumerate() {
+            // The char sets whose ConfigCell is not provided are treated as global, so no variant is skipped by mistake.
+            let is_non_global = match char_sets.get(*char_set_index) {
+                Some(Ok(char_set)) => {
+                    if candidate_index != 0 && !char_set.contains(candidate) {
+                        continue;
+                    }
+                    !char_set.global
+                }
+                _ => false,
+            };
+            if candidate_index != 0 {
+                has_confusable = true;
+            }
+
+            for (variant, non_global) in variants.iter() {
+                let next_non_global = match non_global {
+                    Some(prev) if is_non_global && prev != char_set_index => continue,
+                    Some(prev) => Some(*prev),
+                    None if is_non_global => Some(*char_set_index),
+                    None => None,
+                };
+                next_variants.push(([variant.as_slice(), *candidate].concat(), next_non_global));
+            }
+        }
+        variants = next_variants;
+    }
+
+    if !has_confusable {
+        debug!("The account has no confusable characters, skip.");
+        return Ok(());
+    }
+
+    let config_main = parser.configs.main()?;
+    let mut proof_ranges = Vec::new();
+    for source in [Source::CellDep, Source::Input].iter() {
+        let cells = util::find_cells_by_type_id(ScriptType::Type, config_main.type_id_table().account_cell(), *source)?;
+        for index in cells {
+            let data = util::load_cell_data(index, *source)?;
+            let id = data_parser::account_cell::get_id(&data).to_vec();
+            let next = data_parser::account_cell::get_next(&data).to_vec();
+            proof_ranges.push((*source, index, id, next));
+        }
+    }
+
+    for (variant, _) in variants.iter() {
+        if variant == &account {
+            continue;
+        }
+
+        let variant_id = util::get_account_id_from_account(&[variant.as_slice(), ACCOUNT_SUFFIX.as_bytes()].concat());
+        assert!(
+            pending_account_ids.iter().all(|id| id.as_slice() != &variant_id[..]),
+            Error::PreRegisterAccountIsConfusable,
+            "The account {} is visually identical to the account {} which is being registered.",
+            String::from_utf8(account.to_vec()).unwrap(),
+            String::from_utf8(variant.to_vec()).unwrap()
+        );
+
+        let mut proved = false;
+        for (source, index, id, next) in proof_ranges.iter() {
+            assert!(
+                id.as_slice() != &variant_id[..],
+                Error::PreRegisterAccountIsConfusable,
+                "The account {} is visually identical to the registered account {}.",
+                String::from_utf8(account.to_vec()).unwrap(),
+                String::from_utf8(variant.to_vec()).unwrap()
+            );
+
+            if id.as_slice() < &variant_id[..] && &variant_id[..] < next.as_slice() {
+                debug!(
+                    "The account {} is not registered, proved by {:?}[{}].",
+                    String::from_utf8(variant.to_vec()).unwrap(),
+                    source,
+                    index
+                );
+                proved = true;
+            }
+        }
+
+        assert!(
+            proved,
+            Error::PreRegisterAccountIsConfusable,
+            "An AccountCell in cell_deps or inputs is required to prove the account {} is not registered.(account_id: 0x{})",
+            String::from_utf8(variant.to_vec()).unwrap(),
+            util::hex_string(&variant_id)
+        );
+    }
+
+    Ok(())
+}
+
 pub fn verify_account_chars_max_length(
     parser: &WitnessesParser,
     chars_reader: AccountCharsReader,
@@ -583,6 +749,92 @@ pub fn verify_account_chars_max_length(
     Ok(())
 }
 
+/// Find out the indexes of records in outputs which are changed or newly added, a record is unchanged only if the same
+/// record exists in inputs.
+pub fn get_changed_records(input_records: RecordsReader, output_records: RecordsReader) -> Vec<usize> {
+    output_records
+        .iter()
+        .enumerate()
+        .filter(|(_, output_record)| {
+            !input_records
+                .iter()
+                .any(|input_record| util::is_reader_eq(input_record, *output_record))
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+pub fn verify_records_ttl(parser: &WitnessesParser, records: RecordsReader, indexes: &[usize]) -> Result<(), Error> {
+    debug!("Verify if the ttl of changed records is not less than the minimum.");
+
+    let config_account = parser.configs.account()?;
+    let record_min_ttl = u32::from(config_account.record_min_ttl());
+
+    for &i in indexes {
+        let ttl = u32::from(records.get(i).unwrap().record_ttl());
+        assert!(
+            ttl >= record_min_ttl,
+            Error::AccountCellRecordTTLInvalid,
+            "The records[{}].ttl should not be less than {}.(current: {})",
+            i,
+            record_min_ttl,
+            ttl
+        );
+    }
+
+    Ok(())
+}
+
+/// Verify if the records_updated_at in the tail of AccountCell.data matches the records in the witness.
+///
+/// The records which are changed or newly added are updated at the current timestamp, the others keep the time when
+/// they were updated in inputs, which is 0 if the input AccountCell has no records_updated_at yet.
+pub fn verify_records_updated_at(
+    input_account_index: usize,
+    output_account_index: usize,
+    input_records: RecordsReader,
+    output_records: RecordsReader,
+    current_timestamp: u64,
+) -> Result<(), Error> {
+    debug!("Verify if the records_updated_at is updated for the changed records only.");
+
+    let input_data = util::load_cell_data(input_account_index, Source::Input)?;
+    let output_data = util::load_cell_data(output_account_index, Source::Output)?;
+    let input_records_updated_at = data_parser::account_cell::get_records_updated_at(&input_data);
+    let output_records_updated_at = data_parser::account_cell::get_records_updated_at(&output_data);
+
+    assert!(
+        output_records_updated_at.len() == output_records.len(),
+        Error::AccountCellRecordUpdatedAtError,
+        "outputs[{}] The data.records_updated_at should have one item for each record.(expected: {}, current: {})",
+        output_account_index,
+        output_records.len(),
+        output_records_updated_at.len()
+    );
+
+    for (i, output_record) in output_records.iter().enumerate() {
+        let expected = match input_records
+            .iter()
+            .position(|input_record| util::is_reader_eq(input_record, output_record))
+        {
+            Some(j) => input_records_updated_at.get(j).copied().unwrap_or(0),
+            None => current_timestamp,
+        };
+
+        assert!(
+            output_records_updated_at[i] == expected,
+            Error::AccountCellRecordUpdatedAtError,
+            "outputs[{}] The data.records_updated_at[{}] should be {}.(current: {})",
+            output_account_index,
+            i,
+            expected,
+            output_records_updated_at[i]
+        );
+    }
+
+    Ok(())
+}
+
 pub fn verify_records_keys(parser: &WitnessesParser, records: RecordsReader) -> Result<(), Error> {
     let config_account = parser.configs.account()?;
     let record_key_namespace = parser.configs.record_key_namespace()?;
@@ -603,7 +855,7 @@ pub fn verify_records_keys(parser: &WitnessesParser, records: RecordsReader) ->
             let key_vec = &record_key_namespace[key_start_at..index];
             key_start_at = index + 1;
 
-            key_list.push(key_vec);
+            key_list.push(record_value::split_key_and_validator(key_vec));
         }
     }
 
@@ -633,8 +885,12 @@ pub fn verify_records_keys(parser: &WitnessesParser, records: RecordsReader) ->
         record_type.push(46);
         record_type.append(&mut record_key);
 
-        for key in &key_list {
+        for (key, validator_opt) in &key_list {
             if vec_compare(record_type.as_slice(), *key) {
+                if let Some(validator) = validator_opt {
+                    record_value::verify_record_value(key, validator, record.record_value().raw_data())?;
+                }
+
                 is_valid = true;
                 break;
             }
@@ -0,0 +1,114 @@
+use crate::{assert, constants::*, data_parser, debug, error::Error, util, warn};
+use alloc::boxed::Box;
+use ckb_std::ckb_constants::Source;
+use das_types::mixer::AccountSaleCellDataReaderMixer;
+
+/// Verify if the AccountCells are placed as a bundle at the beginning of inputs and outputs.
+///
+/// The AccountCells should appear in inputs[0..n] and outputs[0..n] in the same order, so the n is also the index of
+/// the AccountSaleCell.
+pub fn verify_account_cells_of_bundle(
+    input_account_cells: &[usize],
+    output_account_cells: &[usize],
+) -> Result<(), Error> {
+    let bundle_size = input_account_cells.len();
+
+    assert!(
+        bundle_size >= 1 && bundle_size == output_account_cells.len(),
+        Error::InvalidTransactionStructure,
+        "There should be at least 1 AccountCell and the same number of AccountCells in both inputs and outputs.(inputs: {}, outputs: {})",
+        bundle_size,
+        output_account_cells.len()
+    );
+    assert!(
+        bundle_size <= ACCOUNT_SALE_MAX_BUNDLE_SIZE,
+        Error::AccountSaleCellBundleError,
+        "There should be at most {} AccountCells sold in one bundle.(current: {})",
+        ACCOUNT_SALE_MAX_BUNDLE_SIZE,
+        bundle_size
+    );
+
+    for (i, (input_index, output_index)) in input_account_cells.iter().zip(output_account_cells.iter()).enumerate() {
+        assert!(
+            *input_index == i && *output_index == i,
+            Error::InvalidTransactionStructure,
+            "The AccountCells should only appear in inputs[0..{}] and outputs[0..{}].",
+            bundle_size,
+            bundle_size
+        );
+    }
+
+    Ok(())
+}
+
+/// Verify if the AccountSaleCell is selling exactly the accounts of the AccountCells in inputs.
+///
+/// The first AccountCell should match AccountSaleCell.witness.account and AccountSaleCell.witness.account_id, the rest
+/// of them should match the bundle account IDs in AccountSaleCell.data in the same order.
+pub fn verify_sale_cell_account_and_id<'a>(
+    input_account_cells: &[usize],
+    sale_cell_index: usize,
+    sale_cell_source: Source,
+    witness_reader: &Box<dyn AccountSaleCellDataReaderMixer + 'a>,
+) -> Result<(), Error> {
+    debug!("Verify if the AccountSaleCell is selling the accounts of the AccountCells in inputs.");
+
+    let input_account_cell_data = util::load_cell_data(input_account_cells[0], Source::Input)?;
+    let account_cell_account = data_parser::account_cell::get_account(&input_account_cell_data);
+    let account_cell_account_id = data_parser::account_cell::get_id(&input_account_cell_data);
+
+    // read account_id from AccountSaleCell's witness
+    let account_sale_cell_account_id = witness_reader.account_id().raw_data();
+    // ensure the AccountSaleCell's args equal to accountCell's id
+    assert!(
+        account_cell_account_id == account_sale_cell_account_id,
+        Error::AccountSaleCellAccountIdInvalid,
+        "The AccountSaleCell.witness.account_id should be equal to the AccountCell.data.account_id ."
+    );
+
+    // read account from AccountSaleCell's witness
+    let account_sale_cell_account = witness_reader.account().raw_data();
+    // ensure the AccountSaleCell's args equal to accountCell's id
+    assert!(
+        account_cell_account == account_sale_cell_account,
+        Error::AccountSaleCellAccountIdInvalid,
+        "The AccountSaleCell.witness.account should be equal to the AccountCell.data.account ."
+    );
+
+    let sale_cell_data = util::load_cell_data(sale_cell_index, sale_cell_source)?;
+    let bundle_account_ids = match data_parser::account_sale_cell::get_bundle_account_ids(&sale_cell_data) {
+        Some(ids) => ids,
+        None => {
+            warn!(
+                "{:?}[{}] The AccountSaleCell.data should be a hash followed by account IDs.",
+                sale_cell_source, sale_cell_index
+            );
+            return Err(Error::AccountSaleCellBundleError);
+        }
+    };
+
+    assert!(
+        bundle_account_ids.len() + 1 == input_account_cells.len(),
+        Error::AccountSaleCellBundleError,
+        "{:?}[{}] The AccountSaleCell is selling {} accounts, but there are {} AccountCells in inputs.",
+        sale_cell_source,
+        sale_cell_index,
+        bundle_account_ids.len() + 1,
+        input_account_cells.len()
+    );
+
+    for (account_cell_index, expected_account_id) in input_account_cells.iter().skip(1).zip(bundle_account_ids) {
+        let data = util::load_cell_data(*account_cell_index, Source::Input)?;
+        let account_id = data_parser::account_cell::get_id(&data);
+
+        assert!(
+            account_id == expected_account_id,
+            Error::AccountSaleCellBundleError,
+            "inputs[{}] The AccountCell.data.account_id should be 0x{} as the AccountSaleCell.data required.",
+            account_cell_index,
+            util::hex_string(expected_account_id)
+        );
+    }
+
+    Ok(())
+}
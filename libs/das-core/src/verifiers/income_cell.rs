@@ -1,4 +1,13 @@
-use crate::{assert, constants::ScriptType, debug, error::Error, util, warn, witness_parser::WitnessesParser};
+use crate::{
+    assert,
+    constants::{IncomePayoutType, ScriptType},
+    data_parser::income_cell,
+    debug,
+    error::Error,
+    util, warn,
+    witness_parser::WitnessesParser,
+};
+use alloc::vec::Vec;
 use ckb_std::{ckb_constants::Source, high_level};
 use das_map::{map::Map, util as map_util};
 use das_types::{packed::*, prelude::*};
@@ -20,7 +29,39 @@ pub fn verify_newly_created(
     Ok(())
 }
 
-fn verify_records_limit(
+/// Verify the payouts in the outputs_data of the IncomeCell and return the payout of each record.
+pub fn verify_payouts(index: usize, source: Source, records_len: usize) -> Result<Vec<u8>, Error> {
+    let data = util::load_cell_data(index, source)?;
+
+    assert!(
+        income_cell::is_data_length_valid(&data, records_len),
+        Error::IncomeCellPayoutError,
+        "{:?}[{}] The payouts of the IncomeCell should be omitted or one for each record.(records: {}, data_length: {})",
+        source,
+        index,
+        records_len,
+        data.len()
+    );
+
+    let mut payouts = Vec::new();
+    for i in 0..records_len {
+        let payout = income_cell::get_payout(&data, i);
+        assert!(
+            income_cell::is_payout_valid(payout),
+            Error::IncomeCellPayoutError,
+            "{:?}[{}] The payout of the record at {} is undefined.(payout: {})",
+            source,
+            index,
+            i,
+            payout
+        );
+        payouts.push(payout);
+    }
+
+    Ok(payouts)
+}
+
+pub fn verify_records_limit(
     config_reader: ConfigCellIncomeReader,
     income_cell_witness_reader: IncomeCellDataReader,
 ) -> Result<(), Error> {
@@ -37,7 +78,7 @@ fn verify_records_limit(
     Ok(())
 }
 
-fn verify_cell_capacity_with_records_capacity(
+pub fn verify_cell_capacity_with_records_capacity(
     config_reader: ConfigCellIncomeReader,
     index: usize,
     source: Source,
@@ -99,17 +140,26 @@ pub fn verify_income_cells(parser: &WitnessesParser, profit_map: Map<Vec<u8>, u6
         output_income_cells
     );
 
-    // If an existing IncomeCell is used, collect all its records for later usage.
+    // If an existing IncomeCell is used, collect all its records and payouts for later usage.
     let mut exist_records_opt = None;
+    let mut exist_payouts = Vec::new();
     if input_income_cells.len() == 1 {
         let input_income_witness = util::parse_income_cell_witness(parser, input_income_cells[0], Source::Input)?;
         let input_income_witness_reader = input_income_witness.as_reader();
+        let payouts = verify_payouts(
+            input_income_cells[0],
+            Source::Input,
+            input_income_witness_reader.records().len(),
+        )?;
 
         let mut tmp = Map::new();
-        for item in input_income_witness_reader.records().iter() {
+        for (item, payout) in input_income_witness_reader.records().iter().zip(payouts.into_iter()) {
             let key = item.belong_to().as_slice().to_vec();
             let value = u64::from(item.capacity());
 
+            if payout != IncomePayoutType::Auto as u8 {
+                exist_payouts.push((key.clone(), payout));
+            }
             map_util::add(&mut tmp, key, value);
         }
         exist_records_opt = Some(tmp);
@@ -135,6 +185,32 @@ pub fn verify_income_cells(parser: &WitnessesParser, profit_map: Map<Vec<u8>, u6
         output_income_witness_reader,
     )?;
 
+    debug!("  Verify if the payouts of the records in the IncomeCell in inputs are reserved in outputs and the new records are paid out by default.");
+
+    let output_payouts = verify_payouts(
+        output_income_cells[0],
+        Source::Output,
+        output_income_witness_reader.records().len(),
+    )?;
+    for (item, payout) in output_income_witness_reader.records().iter().zip(output_payouts.iter()) {
+        // The records are added by whoever builds the transaction, so only the payee can choose a payout other than the
+        // default one, see the edit_income_payout action.
+        let expected_payout = exist_payouts
+            .iter()
+            .find(|(key, _)| key.as_slice() == item.belong_to().as_slice())
+            .map(|(_, exist_payout)| *exist_payout)
+            .unwrap_or(IncomePayoutType::Auto as u8);
+        assert!(
+            *payout == expected_payout,
+            Error::IncomeCellPayoutError,
+            "outputs[{}] The payout of some record is not the same as in inputs or the default.(belong_to: {}, expected: {}, current: {})",
+            output_income_cells[0],
+            Script::from_slice(item.belong_to().as_slice()).unwrap(),
+            expected_payout,
+            payout
+        );
+    }
+
     // Combine records with the same belong_to.
     let mut output_records = Map::new();
     for item in output_income_witness_reader.records().iter() {
@@ -1,5 +1,9 @@
 pub mod account_cell;
+pub mod account_sale_cell;
 pub mod common;
 pub mod income_cell;
 pub mod misc;
+pub mod pre_account_cell;
+pub mod record_value;
+pub mod reverse_record_cell;
 pub mod sub_account_cell;
@@ -0,0 +1,390 @@
+use crate::{assert, constants::*, data_parser::account_cell, debug, error::Error, util};
+use alloc::{borrow::ToOwned, vec::Vec};
+use ckb_std::{
+    ckb_constants::Source,
+    high_level::{load_cell_capacity, load_cell_lock},
+};
+use das_map::{map::Map, util as map_util};
+use das_types::{constants::*, packed::*, prelude::*};
+
+pub fn is_new_account_cell_lock_correct(
+    item_index: usize,
+    input_cell_index: usize,
+    input_cell_witness_reader: PreAccountCellDataReader,
+    output_cell_index: usize,
+) -> Result<(), Error> {
+    debug!(
+        "  Item[{}] Check if the lock script of new AccountCells is das-lock.",
+        item_index
+    );
+
+    let das_lock = das_lock();
+    let owner_lock_args = input_cell_witness_reader.owner_lock_args().raw_data().to_owned();
+    let output_cell_lock = load_cell_lock(output_cell_index, Source::Output)?;
+
+    let expected_lock = das_lock.as_builder().args(Bytes::from(owner_lock_args).into()).build();
+
+    assert!(
+        util::is_entity_eq(&expected_lock, &output_cell_lock),
+        Error::ProposalConfirmAccountLockArgsIsInvalid,
+        "  Item[{}] The outputs[{}].lock should come from the owner_lock_args of inputs[{}]. (expected: {}, current: {})",
+        item_index,
+        output_cell_index,
+        input_cell_index,
+        expected_lock,
+        output_cell_lock
+    );
+
+    Ok(())
+}
+
+pub fn is_bytes_eq(
+    item_index: usize,
+    field: &str,
+    current_bytes: &[u8],
+    expected_bytes: &[u8],
+    error_code: Error,
+) -> Result<(), Error> {
+    assert!(
+        current_bytes == expected_bytes,
+        error_code,
+        "  Item[{}] The AccountCell.{} should be consist in inputs and outputs.(expected: {}, current: {})",
+        item_index,
+        field,
+        util::hex_string(expected_bytes),
+        util::hex_string(current_bytes)
+    );
+
+    Ok(())
+}
+
+pub fn is_old_account_cell_data_consistent(
+    item_index: usize,
+    output_cell_data: &[u8],
+    input_cell_data: &[u8],
+) -> Result<(), Error> {
+    is_bytes_eq(
+        item_index,
+        "id",
+        account_cell::get_id(output_cell_data),
+        account_cell::get_id(input_cell_data),
+        Error::ProposalFieldCanNotBeModified,
+    )?;
+    is_bytes_eq(
+        item_index,
+        "account",
+        account_cell::get_account(output_cell_data),
+        account_cell::get_account(input_cell_data),
+        Error::ProposalFieldCanNotBeModified,
+    )?;
+    is_bytes_eq(
+        item_index,
+        "expired_at",
+        &account_cell::get_expired_at(output_cell_data).to_le_bytes(),
+        &account_cell::get_expired_at(input_cell_data).to_le_bytes(),
+        Error::ProposalFieldCanNotBeModified,
+    )?;
+
+    Ok(())
+}
+
+pub fn is_id_correct(item_index: usize, output_cell_data: &[u8], input_cell_data: &[u8]) -> Result<(), Error> {
+    is_bytes_eq(
+        item_index,
+        "id",
+        account_cell::get_id(output_cell_data),
+        account_cell::get_id(input_cell_data),
+        Error::ProposalConfirmNewAccountCellDataError,
+    )
+}
+
+pub fn is_next_correct(item_index: usize, output_cell_data: &[u8], expected_next: &[u8]) -> Result<(), Error> {
+    is_bytes_eq(
+        item_index,
+        "next",
+        account_cell::get_next(output_cell_data),
+        expected_next,
+        Error::ProposalConfirmNewAccountCellDataError,
+    )
+}
+
+pub fn is_expired_at_correct(
+    item_index: usize,
+    profit: u64,
+    current_timestamp: u64,
+    output_cell_data: &[u8],
+    pre_account_cell_witness: PreAccountCellDataReader,
+) -> Result<(), Error> {
+    let price = u64::from(pre_account_cell_witness.price().new());
+    let quote = u64::from(pre_account_cell_witness.quote());
+    let discount = u32::from(pre_account_cell_witness.invited_discount());
+    let duration = util::calc_duration_from_paid(profit, price, quote, discount);
+    let expired_at = account_cell::get_expired_at(output_cell_data);
+    let calculated_expired_at = current_timestamp + duration;
+
+    debug!(
+        "  Item[{}] Params of expired_at calculation: --profit={} --price={} --quote={} --discount={} --current={}",
+        item_index, profit, price, quote, discount, current_timestamp
+    );
+    debug!(
+        "  Item[{}] Critical value of expired_at calculation process: duration={}, calculated_expired_at={}",
+        item_index, duration, calculated_expired_at
+    );
+
+    assert!(
+        calculated_expired_at == expired_at,
+        Error::ProposalConfirmNewAccountCellDataError,
+        "  Item[{}] The AccountCell.expired_at should be {}, but {} found.",
+        item_index,
+        calculated_expired_at,
+        expired_at
+    );
+
+    Ok(())
+}
+
+pub fn is_account_correct(item_index: usize, output_cell_data: &[u8]) -> Result<(), Error> {
+    let expected_account_id = account_cell::get_id(output_cell_data);
+    let account = account_cell::get_account(output_cell_data);
+
+    let hash = util::blake2b_256(account);
+    let account_id = hash.get(..ACCOUNT_ID_LENGTH).unwrap();
+
+    is_bytes_eq(
+        item_index,
+        "account",
+        account_id,
+        expected_account_id,
+        Error::ProposalConfirmNewAccountCellDataError,
+    )
+}
+
+pub fn is_cell_capacity_correct(item_index: usize, cell_index: usize, expected_capacity: u64) -> Result<(), Error> {
+    let cell_capacity = load_cell_capacity(cell_index, Source::Output)?;
+
+    assert!(
+        expected_capacity == cell_capacity,
+        Error::ProposalConfirmNewAccountCellCapacityError,
+        "  Item[{}] The AccountCell.capacity should be {}, but {} found.",
+        item_index,
+        expected_capacity,
+        cell_capacity
+    );
+
+    Ok(())
+}
+
+pub fn verify_witness_id(
+    item_index: usize,
+    output_cell_data: &[u8],
+    output_cell_witness_reader: AccountCellDataReader,
+) -> Result<(), Error> {
+    let account_id = output_cell_witness_reader.id().raw_data();
+    let expected_account_id = account_cell::get_id(output_cell_data);
+
+    is_bytes_eq(
+        item_index,
+        "witness.id",
+        account_id,
+        expected_account_id,
+        Error::ProposalConfirmNewAccountWitnessError,
+    )
+}
+
+pub fn verify_witness_account(
+    item_index: usize,
+    output_cell_data: &[u8],
+    output_cell_witness_reader: AccountCellDataReader,
+) -> Result<(), Error> {
+    let mut account = output_cell_witness_reader.account().as_readable();
+    account.append(&mut ACCOUNT_SUFFIX.as_bytes().to_vec());
+    let expected_account = account_cell::get_account(output_cell_data);
+
+    is_bytes_eq(
+        item_index,
+        "witness.account",
+        account.as_slice(),
+        expected_account,
+        Error::ProposalConfirmNewAccountWitnessError,
+    )
+}
+
+pub fn verify_witness_registered_at(
+    item_index: usize,
+    timestamp: u64,
+    output_cell_witness_reader: AccountCellDataReader,
+) -> Result<(), Error> {
+    let registered_at = u64::from(output_cell_witness_reader.registered_at());
+
+    assert!(
+        registered_at == timestamp,
+        Error::ProposalConfirmNewAccountWitnessError,
+        "  Item[{}] The AccountCell.registered_at should be the same as the timestamp in TimeCell.(expected: {}, current: {})",
+        item_index,
+        timestamp,
+        registered_at
+    );
+
+    Ok(())
+}
+
+pub fn verify_witness_throttle_fields(
+    item_index: usize,
+    output_cell_witness_reader: AccountCellDataReader,
+) -> Result<(), Error> {
+    let last_transfer_account_at = u64::from(output_cell_witness_reader.last_transfer_account_at());
+    let last_edit_manager_at = u64::from(output_cell_witness_reader.last_edit_manager_at());
+    let last_edit_records_at = u64::from(output_cell_witness_reader.last_edit_records_at());
+
+    assert!(
+        last_transfer_account_at == 0 && last_edit_manager_at == 0 && last_edit_records_at == 0,
+        Error::ProposalConfirmNewAccountWitnessError,
+        "  Item[{}] The AccountCell.last_transfer_account_at/last_edit_manager_at/last_edit_records_at should be 0 .",
+        item_index
+    );
+
+    Ok(())
+}
+
+pub fn verify_witness_status(
+    item_index: usize,
+    output_cell_witness_reader: AccountCellDataReader,
+) -> Result<(), Error> {
+    let status = u8::from(output_cell_witness_reader.status());
+
+    assert!(
+        status == AccountStatus::Normal as u8,
+        Error::ProposalConfirmNewAccountWitnessError,
+        "  Item[{}] The AccountCell.status should be normal. (expected: 0, current: {})",
+        item_index,
+        status
+    );
+
+    Ok(())
+}
+
+pub fn verify_witness_sub_account_fields(
+    item_index: usize,
+    output_cell_witness_reader: AccountCellDataReader,
+) -> Result<(), Error> {
+    let enable_sub_account = u8::from(output_cell_witness_reader.enable_sub_account());
+    let renew_sub_account_price = u64::from(output_cell_witness_reader.renew_sub_account_price());
+
+    assert!(
+        enable_sub_account == SubAccountEnableStatus::Off as u8,
+        Error::ProposalConfirmNewAccountWitnessError,
+        "  Item[{}] The AccountCell.enable_sub_account should be off. (expected: 0, current: {})",
+        item_index,
+        enable_sub_account
+    );
+
+    assert!(
+        renew_sub_account_price == 0,
+        Error::ProposalConfirmNewAccountWitnessError,
+        "  Item[{}] The AccountCell.renew_sub_account_price should be 0. (expected: 0, current: {})",
+        item_index,
+        renew_sub_account_price
+    );
+
+    Ok(())
+}
+
+/// Allocate the profit carried by a PreAccountCell to the inviter, the channel, the proposer and DAS.
+///
+/// The profit of confirming is not recorded, because the transaction creator can take it freely and this script do not
+/// know which lock script the transaction creator will use. When there is no proposer, the profit of proposing is
+/// treated in the same way.
+pub fn allocate_profit(
+    item_index: usize,
+    profit_map: &mut Map<Vec<u8>, u64>,
+    config_profit_rate: ConfigCellProfitRateReader,
+    pre_account_cell_witness: PreAccountCellDataReader,
+    proposer_lock_opt: Option<ScriptReader>,
+    profit: u64,
+) -> Result<(), Error> {
+    let das_wallet_lock = das_wallet_lock();
+    let default_lock = Script::default();
+    let default_lock_reader = default_lock.as_reader();
+
+    let inviter_profit_rate = u32::from(config_profit_rate.inviter()) as u64;
+    let channel_profit_rate = u32::from(config_profit_rate.channel()) as u64;
+    let proposal_create_profit_rate = u32::from(config_profit_rate.proposal_create()) as u64;
+    let proposal_confirm_profit_rate = u32::from(config_profit_rate.proposal_confirm()) as u64;
+
+    let mut inviter_profit = 0;
+    if pre_account_cell_witness.inviter_lock().is_some() {
+        let inviter_lock_reader = pre_account_cell_witness.inviter_lock().to_opt().unwrap();
+        // Skip default value for supporting transactions treat default value as None.
+        if !util::is_reader_eq(default_lock_reader, inviter_lock_reader) {
+            inviter_profit = profit * inviter_profit_rate / RATE_BASE;
+            debug!(
+                "  Item[{}] lock.args[{}]: {}(inviter_profit) = {}(profit) * {}(inviter_profit_rate) / {}(RATE_BASE)",
+                item_index,
+                inviter_lock_reader.args(),
+                inviter_profit,
+                profit,
+                inviter_profit_rate,
+                RATE_BASE
+            );
+            map_util::add(profit_map, inviter_lock_reader.as_slice().to_vec(), inviter_profit);
+        }
+    };
+
+    let mut channel_profit = 0;
+    if pre_account_cell_witness.channel_lock().is_some() {
+        let channel_lock_reader = pre_account_cell_witness.channel_lock().to_opt().unwrap();
+        // Skip default value for supporting transactions treat default value as None.
+        if !util::is_reader_eq(default_lock_reader, channel_lock_reader) {
+            channel_profit = profit * channel_profit_rate / RATE_BASE;
+            debug!(
+                "  Item[{}] lock.args[{}]: {}(channel_profit) = {}(profit) * {}(channel_profit_rate) / {}(RATE_BASE)",
+                item_index,
+                channel_lock_reader.args(),
+                channel_profit,
+                profit,
+                channel_profit_rate,
+                RATE_BASE
+            );
+            map_util::add(profit_map, channel_lock_reader.as_slice().to_vec(), channel_profit);
+        }
+    };
+
+    let proposal_create_profit = profit * proposal_create_profit_rate / RATE_BASE;
+    if let Some(proposer_lock_reader) = proposer_lock_opt {
+        debug!(
+            "  Item[{}] lock.args[{}]: {}(proposal_create_profit) = {}(profit) * {}(proposal_create_profit_rate) / {}(RATE_BASE)",
+            item_index,
+            proposer_lock_reader.args(),
+            proposal_create_profit,
+            profit,
+            proposal_create_profit_rate,
+            RATE_BASE
+        );
+        map_util::add(
+            profit_map,
+            proposer_lock_reader.as_slice().to_vec(),
+            proposal_create_profit,
+        );
+    } else {
+        debug!(
+            "  Item[{}] {}(proposal_create_profit) = {}(profit) * {}(proposal_create_profit_rate) / {}(RATE_BASE) (! not included in IncomeCell)",
+            item_index, proposal_create_profit, profit, proposal_create_profit_rate, RATE_BASE
+        );
+    }
+
+    let proposal_confirm_profit = profit * proposal_confirm_profit_rate / RATE_BASE;
+    debug!(
+        "  Item[{}] {}(proposal_confirm_profit) = {}(profit) * {}(proposal_confirm_profit_rate) / {}(RATE_BASE) (! not included in IncomeCell)",
+        item_index, proposal_confirm_profit, profit, proposal_confirm_profit_rate, RATE_BASE
+    );
+
+    let das_profit = profit - inviter_profit - channel_profit - proposal_create_profit - proposal_confirm_profit;
+    map_util::add(profit_map, das_wallet_lock.as_reader().as_slice().to_vec(), das_profit);
+
+    debug!(
+        "  Item[{}] lock.args[{}]: {}(das_profit) = {}(profit) - {}(inviter_profit) - {}(channel_profit) - {}(proposal_create_profit) - {}(proposal_confirm_profit)",
+        item_index, das_wallet_lock.as_reader().args(), das_profit, profit, inviter_profit, channel_profit, proposal_create_profit, proposal_confirm_profit
+    );
+
+    Ok(())
+}
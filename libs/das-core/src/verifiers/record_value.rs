@@ -0,0 +1,132 @@
+use crate::{debug, error::Error, warn};
+use alloc::string::String;
+use bech32;
+use bs58;
+use eip712::util::keccak256;
+
+// The validators of record values are selected by the suffix of keys in ConfigCellRecordKeyNamespace, like
+// `address.eth:evm_address`, the keys without suffix accept any value.
+pub const RECORD_KEY_VALIDATOR_SEPARATOR: u8 = b':';
+
+pub const RECORD_VALUE_EVM_ADDRESS: &[u8] = b"evm_address";
+pub const RECORD_VALUE_CKB_ADDRESS: &[u8] = b"ckb_address";
+pub const RECORD_VALUE_URL: &[u8] = b"url";
+pub const RECORD_VALUE_IPFS_CID: &[u8] = b"ipfs_cid";
+
+/// Split an item of ConfigCellRecordKeyNamespace into the key and its optional validator.
+pub fn split_key_and_validator(item: &[u8]) -> (&[u8], Option<&[u8]>) {
+    match item.iter().position(|c| c == &RECORD_KEY_VALIDATOR_SEPARATOR) {
+        Some(i) => (&item[..i], Some(&item[(i + 1)..])),
+        None => (item, None),
+    }
+}
+
+pub fn verify_record_value(key: &[u8], validator: &[u8], value: &[u8]) -> Result<(), Error> {
+    debug!(
+        "Verify if the value of record {:?} is a valid {:?}.",
+        String::from_utf8(key.to_vec()),
+        String::from_utf8(validator.to_vec())
+    );
+
+    let value_str = match core::str::from_utf8(value) {
+        Ok(value_str) => value_str,
+        Err(_) => {
+            warn!(
+                "The value of record {:?} should be a valid utf-8 string.",
+                String::from_utf8(key.to_vec())
+            );
+            return Err(Error::AccountCellRecordValueInvalid);
+        }
+    };
+
+    let is_valid = match validator {
+        RECORD_VALUE_EVM_ADDRESS => is_evm_address(value_str),
+        RECORD_VALUE_CKB_ADDRESS => is_ckb_address(value_str),
+        RECORD_VALUE_URL => is_url(value_str),
+        RECORD_VALUE_IPFS_CID => is_ipfs_cid(value_str),
+        _ => {
+            warn!(
+                "The validator {:?} of record {:?} is undefined.",
+                String::from_utf8(validator.to_vec()),
+                String::from_utf8(key.to_vec())
+            );
+            return Err(Error::AccountCellRecordValueInvalid);
+        }
+    };
+
+    if !is_valid {
+        warn!(
+            "The value of record {:?} is not a valid {:?}.(value: {})",
+            String::from_utf8(key.to_vec()),
+            String::from_utf8(validator.to_vec()),
+            value_str
+        );
+        return Err(Error::AccountCellRecordValueInvalid);
+    }
+
+    Ok(())
+}
+
+/// An EVM address is 0x-prefixed 20 bytes hex, if it contains both lowercase and uppercase letters it must pass the
+/// EIP-55 checksum.
+fn is_evm_address(value: &str) -> bool {
+    let hex = match value.strip_prefix("0x") {
+        Some(hex) => hex,
+        None => return false,
+    };
+    if hex.len() != 40 || !hex.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return false;
+    }
+    if !hex.bytes().any(|c| c.is_ascii_lowercase()) || !hex.bytes().any(|c| c.is_ascii_uppercase()) {
+        return true;
+    }
+
+    let hash = keccak256(hex.to_ascii_lowercase().as_bytes());
+    hex.bytes().enumerate().all(|(i, c)| {
+        let nibble = if i % 2 == 0 {
+            hash[i / 2] >> 4
+        } else {
+            hash[i / 2] & 0x0f
+        };
+        !c.is_ascii_alphabetic() || c.is_ascii_uppercase() == (nibble >= 8)
+    })
+}
+
+/// A CKB address is bech32 or bech32m encoded with the human-readable part of mainnet or testnet.
+fn is_ckb_address(value: &str) -> bool {
+    match bech32::decode(value) {
+        Ok((hrp, _, _)) => hrp == "ckb" || hrp == "ckt",
+        Err(_) => false,
+    }
+}
+
+/// A URL should be http or https with a host, and contain only visible ASCII characters.
+fn is_url(value: &str) -> bool {
+    let rest = match value.strip_prefix("https://").or_else(|| value.strip_prefix("http://")) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let host = rest.split(|c| c == '/' || c == '?' || c == '#').next().unwrap_or("");
+
+    !host.is_empty() && rest.bytes().all(|c| c.is_ascii_graphic())
+}
+
+/// An IPFS CID is either a CIDv0 which is a base58btc encoded sha2-256 multihash, or a CIDv1 in the default base32
+/// multibase.
+fn is_ipfs_cid(value: &str) -> bool {
+    if value.starts_with("Qm") {
+        match bs58::decode(value).into_vec() {
+            Ok(bytes) => bytes.len() == 34 && bytes[0] == 0x12 && bytes[1] == 0x20,
+            Err(_) => false,
+        }
+    } else if let Some(data) = value.strip_prefix('b') {
+        // The first decoded byte is the CID version 0x01, so the data always starts with `a` followed by one of `e..h`.
+        let bytes = data.as_bytes();
+        bytes.len() > 2
+            && bytes[0] == b'a'
+            && (b'e'..=b'h').contains(&bytes[1])
+            && bytes.iter().all(|c| matches!(c, b'a'..=b'z' | b'2'..=b'7'))
+    } else {
+        false
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{
+    assert, constants::*, data_parser, debug, error::Error, sub_account_witness_parser::SubAccountWitnessesParser,
+    util, verifiers, warn, witness_parser::WitnessesParser,
+};
+use alloc::{string::String, vec::Vec};
+use ckb_std::{ckb_constants::Source, high_level};
+use das_types::{constants::AccountStatus, packed::*, prelude::*};
+
+/// Verify if the declarer of the ReverseRecordCell is the owner of the account in its data.
+///
+/// The owner lock is loaded from an AccountCell in cell_deps, or from a sub-account witness whose SMT proof is against
+/// the root of the SubAccountCell of its parent account in cell_deps. The account must not be expired and must be in
+/// normal status. The address.* records are not accepted as proofs, because they are editable by the manager.
+///
+/// The contract can not invalidate the flag when the account is transferred later, so resolvers may trust the binding
+/// without re-verifying it only while the ReverseRecordCell is newer than the last transfer of the account.
+pub fn verify_reverse_record_ownership(
+    parser: &WitnessesParser,
+    reverse_record_index: usize,
+    source: Source,
+    timestamp: u64,
+) -> Result<(), Error> {
+    let data = high_level::load_cell_data(reverse_record_index, source)?;
+    let account = data_parser::reverse_record_cell::get_account(&data);
+
+    debug!(
+        "Verify if the declarer of the ReverseRecordCell is the owner of the account {:?}.",
+        String::from_utf8(account.to_vec())
+    );
+
+    // The sub-accounts have at least two dots, like `sub.parent.bit`.
+    let owner_lock_args = if account.iter().filter(|c| **c == b'.').count() > 1 {
+        load_sub_account_owner(parser, account, timestamp)?
+    } else {
+        load_account_owner(parser, account, timestamp)?
+    };
+
+    let lock = high_level::load_cell_lock(reverse_record_index, source)?;
+    let declarer_lock_args = lock.as_reader().args().raw_data();
+    let declarer_type = data_parser::das_lock_args::get_owner_type(declarer_lock_args);
+    let declarer_args = data_parser::das_lock_args::get_owner_lock_args(declarer_lock_args);
+
+    assert!(
+        data_parser::das_lock_args::get_owner_type(&owner_lock_args) == declarer_type
+            && data_parser::das_lock_args::get_owner_lock_args(&owner_lock_args) == declarer_args,
+        Error::ReverseRecordCellOwnershipError,
+        "The declarer 0x{} is not the owner of {:?}.",
+        util::hex_string(declarer_args),
+        String::from_utf8(account.to_vec())
+    );
+
+    Ok(())
+}
+
+fn load_account_owner(parser: &WitnessesParser, account: &[u8], timestamp: u64) -> Result<Vec<u8>, Error> {
+    let config_main = parser.configs.main()?;
+    let config_account = parser.configs.account()?;
+    let account_cells = util::find_cells_by_type_id(
+        ScriptType::Type,
+        config_main.type_id_table().account_cell(),
+        Source::CellDep,
+    )?;
+
+    for i in account_cells {
+        let data = util::load_cell_data(i, Source::CellDep)?;
+        if data_parser::account_cell::get_account(&data) != account {
+            continue;
+        }
+
+        let expired_at = data_parser::account_cell::get_expired_at(&data);
+        let expiration_grace_period = u32::from(config_account.expiration_grace_period()) as u64;
+        if timestamp > expired_at {
+            if timestamp - expired_at > expiration_grace_period {
+                warn!(
+                    "CellDeps[{}] The AccountCell has been expired. Will be recycled soon.",
+                    i
+                );
+                return Err(Error::AccountCellHasExpired);
+            } else {
+                warn!("CellDeps[{}] The AccountCell has been in expiration grace period. Need to be renew as soon as possible.", i);
+                return Err(Error::AccountCellInExpirationGracePeriod);
+            }
+        }
+
+        let witness = util::parse_account_cell_witness(parser, i, Source::CellDep)?;
+        verifiers::account_cell::verify_account_cell_status(
+            &witness.as_reader(),
+            AccountStatus::Normal,
+            i,
+            Source::CellDep,
+        )?;
+
+        let lock = high_level::load_cell_lock(i, Source::CellDep)?;
+        return Ok(lock.as_reader().args().raw_data().to_vec());
+    }
+
+    warn!(
+        "The AccountCell of {:?} should be in cell_deps.",
+        String::from_utf8(account.to_vec())
+    );
+    Err(Error::ReverseRecordCellOwnershipError)
+}
+
+fn load_sub_account_owner(parser: &WitnessesParser, account: &[u8], timestamp: u64) -> Result<Vec<u8>, Error> {
+    let config_main = parser.configs.main()?;
+    let config_account = parser.configs.account()?;
+    let sub_account_cells = util::find_cells_by_type_id(
+        ScriptType::Type,
+        config_main.type_id_table().sub_account_cell(),
+        Source::CellDep,
+    )?;
+
+    let dot = account.iter().position(|c| *c == b'.').unwrap();
+    let parent_account_id = util::get_account_id_from_account(&account[(dot + 1)..]);
+    let sub_account_cell_opt = sub_account_cells.into_iter().find(|i| {
+        high_level::load_cell_type(*i, Source::CellDep)
+            .ok()
+            .flatten()
+            .map(|type_| type_.as_reader().args().raw_data() == parent_account_id)
+            .unwrap_or(false)
+    });
+
+    assert!(
+        sub_account_cell_opt.is_some(),
+        Error::ReverseRecordCellOwnershipError,
+        "The SubAccountCell of the parent account of {:?} should be in cell_deps.",
+        String::from_utf8(account.to_vec())
+    );
+
+    let data = util::load_cell_data(sub_account_cell_opt.unwrap(), Source::CellDep)?;
+    let root = data_parser::sub_account_cell::get_smt_root(&data).ok_or(Error::ReverseRecordCellOwnershipError)?;
+
+    let sub_account_parser = SubAccountWitnessesParser::new()?;
+    for witness_ret in sub_account_parser.iter() {
+        let witness = witness_ret?;
+        let sub_account_reader = witness.sub_account.as_reader();
+        if util::get_sub_account_name_from_reader(sub_account_reader).as_bytes() != account {
+            continue;
+        }
+
+        // The SMT key of a sub-account is its account ID padded with zeros to 32 bytes.
+        let mut key = [0u8; 32];
+        key[..ACCOUNT_ID_LENGTH].copy_from_slice(witness.sub_account.id().as_slice());
+        let val = util::blake2b_256(witness.sub_account.as_slice());
+        let mut root_bytes = [0u8; 32];
+        root_bytes.copy_from_slice(root);
+        verifiers::sub_account_cell::verify_smt_proof(key, val, root_bytes, &witness.proof)?;
+        verifiers::sub_account_cell::verify_expiration(config_account, witness.index, sub_account_reader, timestamp)?;
+        verifiers::sub_account_cell::verify_status(witness.index, sub_account_reader, AccountStatus::Normal)?;
+
+        return Ok(sub_account_reader.lock().args().raw_data().to_vec());
+    }
+
+    warn!(
+        "The sub-account witness of {:?} should be in the transaction.",
+        String::from_utf8(account.to_vec())
+    );
+    Err(Error::ReverseRecordCellOwnershipError)
+}
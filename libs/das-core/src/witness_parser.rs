@@ -212,6 +212,37 @@ impl WitnessesParser {
                     Bytes::from(bytes_of_role),
                 ]
             }
+            // The counter offer needs the same proof as the accept_offer when the OfferCell being countered is bidding on a merkle
+            // tree of accounts.
+            b"accept_offer" | b"counter_offer" => {
+                let bytes = action_data.as_reader().params().raw_data();
+                if bytes.is_empty() {
+                    Vec::new()
+                } else if bytes.len() == 1 {
+                    // Only the role is provided, the OfferCell should be on one account or a pattern of accounts.
+                    vec![action_data.params()]
+                } else {
+                    // The merkle proof of the account ID is placed before the role.
+                    let bytes_of_proof = bytes.get(..(bytes.len() - 1)).ok_or(Error::ParamsDecodingError)?;
+                    let bytes_of_role = bytes.get((bytes.len() - 1)..).ok_or(Error::ParamsDecodingError)?;
+
+                    vec![Bytes::from(bytes_of_proof), Bytes::from(bytes_of_role)]
+                }
+            }
+            b"lease_account" => {
+                // The price of the lease is placed before the role, it is paid to the owner by the lessee.
+                let bytes = action_data.as_reader().params().raw_data();
+                let bytes_of_price = bytes.get(..8).ok_or(Error::ParamsDecodingError)?;
+                let bytes_of_role = bytes.get(8..).ok_or(Error::ParamsDecodingError)?;
+
+                assert!(
+                    bytes_of_role.len() == 1,
+                    Error::ParamsDecodingError,
+                    "The params of this action should contains a param of role at the end."
+                );
+
+                vec![Bytes::from(bytes_of_price), Bytes::from(bytes_of_role)]
+            }
             _ => {
                 if action_data.params().is_empty() {
                     Vec::new()
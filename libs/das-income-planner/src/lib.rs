@@ -0,0 +1,8 @@
+#![no_std]
+
+extern crate alloc;
+extern crate no_std_compat as std;
+
+mod planner;
+
+pub use crate::planner::*;
@@ -0,0 +1,593 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use std::prelude::v1::*;
+
+pub const RATE_BASE: u64 = 10_000;
+
+/// The payout of the records without any payout descriptor.
+pub const PAYOUT_AUTO: u8 = 0;
+
+/// An income record, the belong_to field is the molecule encoded bytes of a lock script.
+///
+/// The payout field is the payout descriptor of the record which decides what kind of cell the record should be
+/// transferred to, the planner only carries it along with the record.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IncomeRecord {
+    pub belong_to: Vec<u8>,
+    pub capacity: u64,
+    pub payout: u8,
+}
+
+impl IncomeRecord {
+    pub fn new(belong_to: Vec<u8>, capacity: u64) -> Self {
+        IncomeRecord {
+            belong_to,
+            capacity,
+            payout: PAYOUT_AUTO,
+        }
+    }
+
+    pub fn with_payout(mut self, payout: u8) -> Self {
+        self.payout = payout;
+        self
+    }
+
+    pub fn with_capacity(&self, capacity: u64) -> Self {
+        IncomeRecord {
+            capacity,
+            ..self.clone()
+        }
+    }
+}
+
+/// The fields of ConfigCellIncome and ConfigCellProfitRate which take effect in consolidating.
+#[derive(Clone, Debug, Default)]
+pub struct IncomeConfig {
+    pub basic_capacity: u64,
+    pub max_records: usize,
+    pub min_transfer_capacity: u64,
+    pub consolidate_profit_rate: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConsolidatePlan {
+    /// The records of each IncomeCell in outputs, in the same order as the IncomeCells should be.
+    pub income_cells: Vec<Vec<IncomeRecord>>,
+    /// The capacity each lock should receive in outputs, with the consolidating fee deducted.
+    pub transfers: Vec<IncomeRecord>,
+    /// The parts of records which are kept in IncomeCells for padding their capacity to the basic capacity.
+    pub paddings: Vec<IncomeRecord>,
+    /// The total consolidating fee which the keeper can take.
+    pub fee: u64,
+}
+
+impl ConsolidatePlan {
+    pub fn need_pad(&self) -> bool {
+        !self.paddings.is_empty()
+    }
+
+    pub fn is_padding_source(&self, belong_to: &[u8]) -> bool {
+        self.paddings.iter().any(|item| item.belong_to == belong_to)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PlanError {
+    /// The config is invalid, for example the max_records is 0.
+    InvalidConfig,
+    /// The capacity of the records is not enough to create IncomeCells with the basic capacity.
+    CapacityNotEnough,
+    /// The records of the same lock have different payout descriptors.
+    PayoutConflict,
+    /// Some records should be kept, but there is no IncomeCell in outputs.
+    IncomeCellMissing,
+    /// The records in IncomeCells are not consolidated as expected.
+    ConsolidateError,
+    /// Some capacity of the records to transfer is neither transferred nor kept for padding.
+    ConsolidateWaste,
+    /// The capacity transferred to a lock is not as expected.
+    TransferError,
+}
+
+/// Merge the records of IncomeCells into unique lock to capacity pairs, the order of locks is the order they first appear.
+///
+/// A payout descriptor overrides the default payout of the same lock, but different descriptors of the same lock are
+/// conflicting.
+pub fn merge_records(cells: &[Vec<IncomeRecord>]) -> Result<Vec<IncomeRecord>, PlanError> {
+    let mut merged: Vec<IncomeRecord> = Vec::new();
+    for record in cells.iter().flatten() {
+        match merged.iter_mut().find(|item| item.belong_to == record.belong_to) {
+            Some(item) => {
+                if item.payout == PAYOUT_AUTO {
+                    item.payout = record.payout;
+                } else if record.payout != PAYOUT_AUTO && record.payout != item.payout {
+                    return Err(PlanError::PayoutConflict);
+                }
+                item.capacity += record.capacity;
+            }
+            None => merged.push(record.clone()),
+        }
+    }
+
+    Ok(merged)
+}
+
+/// The merged records classified by whether they should be transferred or kept in IncomeCells.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Classification {
+    /// The indexes of the records to transfer, in the order of the merged records.
+    pub to_transfer: Vec<usize>,
+    /// The indexes of the records to keep in IncomeCells.
+    pub to_keep: BTreeSet<usize>,
+    /// If the records to keep are not enough to fill IncomeCells to the basic capacity.
+    pub need_pad: bool,
+}
+
+/// Classify the merged records, a record is transferred if the capacity left after the consolidating fee is at least
+/// `min_transfer_capacity`, otherwise it is kept in IncomeCells.
+pub fn classify_records(config: &IncomeConfig, records: &[IncomeRecord]) -> Result<Classification, PlanError> {
+    if config.max_records == 0 || config.consolidate_profit_rate >= RATE_BASE {
+        return Err(PlanError::InvalidConfig);
+    }
+
+    let mut to_transfer = Vec::new();
+    let mut to_keep = BTreeSet::new();
+    for (i, record) in records.iter().enumerate() {
+        if after_fee(config, record.capacity) >= config.min_transfer_capacity {
+            to_transfer.push(i);
+        } else {
+            to_keep.insert(i);
+        }
+    }
+
+    // If the total capacity remains 0, that means no IncomeCell is needed in outputs.
+    let keep_capacity: u64 = to_keep.iter().map(|i| records[*i].capacity).sum();
+    let need_pad = keep_capacity != 0 && keep_capacity < config.basic_capacity * cell_count(config, to_keep.len());
+
+    Ok(Classification {
+        to_transfer,
+        to_keep,
+        need_pad,
+    })
+}
+
+fn cell_count(config: &IncomeConfig, records_count: usize) -> u64 {
+    let mut count = (records_count / config.max_records) as u64;
+    if records_count % config.max_records != 0 {
+        count += 1;
+    }
+    count
+}
+
+fn after_fee(config: &IncomeConfig, capacity: u64) -> u64 {
+    capacity / RATE_BASE * (RATE_BASE - config.consolidate_profit_rate)
+}
+
+/// The capacity a record spends for transferring the given capacity, the consolidating fee included.
+fn before_fee(config: &IncomeConfig, is_free: bool, transferred: u64) -> u64 {
+    if is_free {
+        transferred
+    } else {
+        transferred / (RATE_BASE - config.consolidate_profit_rate) * RATE_BASE
+    }
+}
+
+fn expected_transfer(config: &IncomeConfig, is_free: bool, capacity: u64) -> u64 {
+    if is_free {
+        capacity
+    } else {
+        after_fee(config, capacity)
+    }
+}
+
+/// Plan the outputs of consolidating the given IncomeCells, the plan always passes `verify_consolidation`.
+///
+/// - If the kept records are not enough to fill IncomeCells to `basic_capacity`, the records to transfer are used for
+///   padding in their order, each of them pads at least what is still missing.
+/// - The records of `free_locks` are free from the consolidating fee.
+/// - The records in IncomeCells keep the order of the merged records, and every IncomeCell holds `max_records` records
+///   except the last one.
+pub fn plan_consolidation(
+    config: &IncomeConfig,
+    free_locks: &[Vec<u8>],
+    cells: &[Vec<IncomeRecord>],
+) -> Result<ConsolidatePlan, PlanError> {
+    let records = merge_records(cells)?;
+    let classification = classify_records(config, &records)?;
+    let is_free = |i: usize| free_locks.contains(&records[i].belong_to);
+
+    let mut paddings: BTreeMap<usize, u64> = BTreeMap::new();
+    if classification.need_pad {
+        let keep_capacity: u64 = classification.to_keep.iter().map(|i| records[*i].capacity).sum();
+        let mut missing = config.basic_capacity * cell_count(config, classification.to_keep.len()) - keep_capacity;
+        for i in classification.to_transfer.iter() {
+            if missing == 0 {
+                break;
+            }
+
+            let capacity = records[*i].capacity;
+            // The capacity used for padding is derived from the capacity transferred when verifying, so the fee should
+            // be paid in whole units of RATE_BASE to make the transferred capacity less than a full transfer.
+            let part = if is_free(*i) {
+                missing
+            } else {
+                (missing + RATE_BASE - 1) / RATE_BASE * RATE_BASE
+            };
+            let padding = if part >= capacity {
+                capacity
+            } else {
+                let transferred = expected_transfer(config, is_free(*i), capacity - part);
+                if transferred == 0 {
+                    capacity
+                } else {
+                    capacity - before_fee(config, is_free(*i), transferred)
+                }
+            };
+
+            paddings.insert(*i, padding);
+            missing -= core::cmp::min(missing, padding);
+        }
+
+        if missing > 0 {
+            return Err(PlanError::CapacityNotEnough);
+        }
+    }
+
+    let mut kept_records = Vec::new();
+    for (i, record) in records.iter().enumerate() {
+        if classification.to_keep.contains(&i) {
+            kept_records.push(record.clone());
+        } else if let Some(capacity) = paddings.get(&i) {
+            kept_records.push(record.with_capacity(*capacity));
+        }
+    }
+
+    let mut income_cells = Vec::new();
+    for chunk in kept_records.chunks(config.max_records) {
+        let total: u64 = chunk.iter().map(|item| item.capacity).sum();
+        if total < config.basic_capacity {
+            return Err(PlanError::CapacityNotEnough);
+        }
+        income_cells.push(chunk.to_vec());
+    }
+
+    let mut transfers = Vec::new();
+    let mut fee = 0;
+    for i in classification.to_transfer.iter() {
+        let record = &records[*i];
+        let remain = record.capacity - paddings.get(i).copied().unwrap_or(0);
+        if remain == 0 {
+            continue;
+        }
+
+        // The remain of a padding source is always in whole units of RATE_BASE, so no extra fee is taken from it.
+        let capacity = expected_transfer(config, is_free(*i), remain);
+        fee += remain - capacity;
+        transfers.push(record.with_capacity(capacity));
+    }
+
+    Ok(ConsolidatePlan {
+        income_cells,
+        transfers,
+        paddings: paddings
+            .into_iter()
+            .map(|(i, capacity)| records[i].with_capacity(capacity))
+            .collect(),
+        fee,
+    })
+}
+
+/// Verify the outputs of consolidating IncomeCells, the outputs may be built by any keeper.
+///
+/// - Every record to keep should be kept in IncomeCells with the same capacity.
+/// - Every record to transfer should be transferred with its capacity after the consolidating fee, except that when
+///   padding is needed, it may be transferred partially or not at all. In that case, the fee is recalculated from the
+///   transferred part and the rest of the record should be kept in IncomeCells for padding.
+/// - Every IncomeCell should hold at most `max_records` records and at least `basic_capacity`.
+///
+/// The `transfers` are the capacity each lock received in outputs, the locks which received nothing are omitted.
+///
+/// The padding is not compared with the output of `plan_consolidation`, so a keeper may pad with other records or more
+/// capacity than the plan does. This is accepted because the padding stays in the record of its owner and the fee is
+/// only charged on what is transferred, so nobody loses capacity, the transfer is only deferred to a later
+/// consolidation. Requiring the exact plan would bind every keeper to the same planner version and tie-break order.
+pub fn verify_consolidation(
+    config: &IncomeConfig,
+    free_locks: &[Vec<u8>],
+    records: &[IncomeRecord],
+    income_cells: &[Vec<IncomeRecord>],
+    transfers: &[IncomeRecord],
+) -> Result<(), PlanError> {
+    let classification = classify_records(config, records)?;
+
+    if !classification.to_keep.is_empty() && income_cells.is_empty() {
+        return Err(PlanError::IncomeCellMissing);
+    }
+
+    let mut output_records: BTreeMap<&[u8], &IncomeRecord> = BTreeMap::new();
+    for cell in income_cells.iter() {
+        let total: u64 = cell.iter().map(|item| item.capacity).sum();
+        if cell.len() > config.max_records || total < config.basic_capacity {
+            return Err(PlanError::ConsolidateError);
+        }
+
+        for record in cell.iter() {
+            if output_records.insert(&record.belong_to, record).is_some() {
+                return Err(PlanError::ConsolidateError);
+            }
+        }
+    }
+
+    let mut paddings = Vec::new();
+    for i in classification.to_transfer.iter() {
+        let record = &records[*i];
+        let is_free = free_locks.contains(&record.belong_to);
+        let expected = expected_transfer(config, is_free, record.capacity);
+
+        match transfers.iter().find(|item| item.belong_to == record.belong_to) {
+            None if classification.need_pad => paddings.push(record.clone()),
+            None => return Err(PlanError::TransferError),
+            Some(transferred) if transferred.capacity < expected && classification.need_pad => {
+                let spent = before_fee(config, is_free, transferred.capacity);
+                let padding = record.capacity.checked_sub(spent).ok_or(PlanError::TransferError)?;
+                paddings.push(record.with_capacity(padding));
+            }
+            Some(transferred) if transferred.capacity != expected => return Err(PlanError::TransferError),
+            Some(_) => {}
+        }
+    }
+
+    for i in classification.to_keep.iter() {
+        let record = &records[*i];
+        match output_records.remove(record.belong_to.as_slice()) {
+            Some(item) if item == record => {}
+            _ => return Err(PlanError::ConsolidateError),
+        }
+    }
+
+    for padding in paddings.iter() {
+        match output_records.remove(padding.belong_to.as_slice()) {
+            Some(item) if item == padding => {}
+            Some(_) => return Err(PlanError::ConsolidateError),
+            None => return Err(PlanError::ConsolidateWaste),
+        }
+    }
+
+    // Any record left is neither kept nor used for padding.
+    if !output_records.is_empty() {
+        return Err(PlanError::ConsolidateError);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    const CKB: u64 = 100_000_000;
+
+    fn config() -> IncomeConfig {
+        IncomeConfig {
+            basic_capacity: 200 * CKB,
+            max_records: 50,
+            min_transfer_capacity: 100 * CKB,
+            consolidate_profit_rate: 100,
+        }
+    }
+
+    fn record(lock: u8, capacity: u64) -> IncomeRecord {
+        IncomeRecord::new(vec![lock], capacity)
+    }
+
+    #[test]
+    fn test_merge_records() {
+        let merged = merge_records(&[
+            vec![record(0, 200 * CKB), record(1, CKB)],
+            vec![record(0, 200 * CKB), record(2, CKB), record(1, CKB)],
+        ])
+        .unwrap();
+
+        assert_eq!(merged, vec![record(0, 400 * CKB), record(1, 2 * CKB), record(2, CKB)]);
+    }
+
+    #[test]
+    fn test_merge_records_with_payout() {
+        let merged = merge_records(&[
+            vec![record(0, 200 * CKB), record(1, CKB)],
+            vec![record(0, 200 * CKB), record(1, CKB).with_payout(3)],
+        ])
+        .unwrap();
+
+        // The payout descriptor overrides the default payout.
+        assert_eq!(merged, vec![record(0, 400 * CKB), record(1, 2 * CKB).with_payout(3)]);
+
+        let ret = merge_records(&[vec![record(1, CKB).with_payout(1)], vec![record(1, CKB).with_payout(3)]]);
+
+        assert_eq!(ret, Err(PlanError::PayoutConflict));
+    }
+
+    #[test]
+    fn test_plan_without_padding() {
+        let creator = vec![0u8];
+        let plan = plan_consolidation(
+            &config(),
+            &[creator],
+            &[
+                vec![record(0, 200 * CKB), record(1, 100 * CKB)],
+                vec![record(0, 200 * CKB), record(1, 2 * CKB)],
+            ],
+        )
+        .unwrap();
+
+        assert!(plan.income_cells.is_empty());
+        assert!(!plan.need_pad());
+        assert_eq!(plan.transfers, vec![record(0, 400 * CKB), record(1, 10_098_000_000)]);
+        assert_eq!(plan.fee, 102_000_000);
+    }
+
+    #[test]
+    fn test_plan_with_padding() {
+        let creator = vec![0u8];
+        let plan = plan_consolidation(
+            &config(),
+            &[creator],
+            &[
+                vec![
+                    record(0, 200 * CKB),
+                    record(1, 100 * CKB),
+                    record(2, CKB),
+                    record(3, 99 * CKB),
+                ],
+                vec![record(0, 200 * CKB), record(1, 100 * CKB), record(2, CKB)],
+            ],
+        )
+        .unwrap();
+
+        // The creator is the first record to transfer, so it pads the missing 99 CKB.
+        assert_eq!(
+            plan.income_cells,
+            vec![vec![record(0, 99 * CKB), record(2, 2 * CKB), record(3, 99 * CKB)]]
+        );
+        assert_eq!(plan.paddings, vec![record(0, 99 * CKB)]);
+        assert!(plan.is_padding_source(&[0u8]));
+        assert_eq!(plan.transfers, vec![record(0, 301 * CKB), record(1, 198 * CKB)]);
+    }
+
+    #[test]
+    fn test_plan_padding_with_whole_record() {
+        let plan = plan_consolidation(
+            &config(),
+            &[],
+            &[
+                vec![record(1, 102 * CKB), record(2, 50 * CKB)],
+                vec![record(3, 150 * CKB)],
+            ],
+        )
+        .unwrap();
+
+        // The record 1 is used for padding with all its capacity, so it is not transferred at all.
+        assert_eq!(
+            plan.income_cells,
+            vec![vec![record(1, 102 * CKB), record(2, 50 * CKB), record(3, 48 * CKB)]]
+        );
+        assert_eq!(plan.transfers, vec![record(3, 10_098_000_000)]);
+    }
+
+    #[test]
+    fn test_plan_padding_in_whole_units() {
+        let cells = [vec![record(1, 300 * CKB), record(2, 50 * CKB - 1)]];
+        let plan = plan_consolidation(&config(), &[], &cells).unwrap();
+
+        // The missing 150 CKB + 1 shannon is rounded up to whole units of RATE_BASE for padding.
+        assert_eq!(plan.paddings, vec![record(1, 15_000_010_000)]);
+        assert_eq!(plan.transfers, vec![record(1, 14_849_990_100)]);
+        assert_eq!(plan.fee, 150_000_000 - 100);
+
+        let records = merge_records(&cells).unwrap();
+        assert_eq!(
+            verify_consolidation(&config(), &[], &records, &plan.income_cells, &plan.transfers),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_verify_plan() {
+        let creator = vec![0u8];
+        let cells = [
+            vec![
+                record(0, 200 * CKB),
+                record(1, 100 * CKB),
+                record(2, CKB),
+                record(3, 99 * CKB),
+            ],
+            vec![record(0, 200 * CKB), record(1, 100 * CKB), record(2, CKB)],
+        ];
+        let plan = plan_consolidation(&config(), core::slice::from_ref(&creator), &cells).unwrap();
+        let records = merge_records(&cells).unwrap();
+
+        assert_eq!(
+            verify_consolidation(&config(), &[creator], &records, &plan.income_cells, &plan.transfers),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_verify_padding_chosen_by_keeper() {
+        let creator = vec![0u8];
+        let cells = [
+            vec![
+                record(0, 200 * CKB),
+                record(1, 100 * CKB),
+                record(2, CKB),
+                record(3, 99 * CKB),
+            ],
+            vec![record(0, 200 * CKB), record(1, 100 * CKB), record(2, CKB)],
+        ];
+        let records = merge_records(&cells).unwrap();
+
+        // The keeper may pad with any record to transfer, here the record 1 pads 100 CKB instead of the creator.
+        let ret = verify_consolidation(
+            &config(),
+            core::slice::from_ref(&creator),
+            &records,
+            &[vec![record(1, 100 * CKB), record(2, 2 * CKB), record(3, 99 * CKB)]],
+            &[record(0, 400 * CKB), record(1, 99 * CKB)],
+        );
+        assert_eq!(ret, Ok(()));
+
+        // The rest of the record 1 is neither transferred nor kept for padding.
+        let ret = verify_consolidation(
+            &config(),
+            core::slice::from_ref(&creator),
+            &records,
+            &[vec![record(0, 100 * CKB), record(2, 2 * CKB), record(3, 99 * CKB)]],
+            &[record(0, 300 * CKB), record(1, 99 * CKB)],
+        );
+        assert_eq!(ret, Err(PlanError::ConsolidateWaste));
+
+        // The record to keep is missing in IncomeCells.
+        let ret = verify_consolidation(
+            &config(),
+            &[creator],
+            &records,
+            &[vec![record(1, 100 * CKB), record(3, 101 * CKB)]],
+            &[record(0, 400 * CKB), record(1, 99 * CKB)],
+        );
+        assert_eq!(ret, Err(PlanError::ConsolidateError));
+    }
+
+    #[test]
+    fn test_verify_transfer_without_padding() {
+        let records = [record(1, 200 * CKB)];
+
+        let ret = verify_consolidation(&config(), &[], &records, &[], &[record(1, 198 * CKB)]);
+        assert_eq!(ret, Ok(()));
+
+        // Nothing needs padding, so the record should be transferred completely.
+        let ret = verify_consolidation(&config(), &[], &records, &[], &[record(1, 197 * CKB)]);
+        assert_eq!(ret, Err(PlanError::TransferError));
+
+        let ret = verify_consolidation(&config(), &[], &records, &[], &[]);
+        assert_eq!(ret, Err(PlanError::TransferError));
+    }
+
+    #[test]
+    fn test_plan_capacity_not_enough() {
+        let ret = plan_consolidation(&config(), &[], &[vec![record(1, CKB)], vec![record(2, CKB)]]);
+
+        assert_eq!(ret, Err(PlanError::CapacityNotEnough));
+    }
+
+    #[test]
+    fn test_plan_invalid_config() {
+        let mut config = config();
+        config.max_records = 0;
+        let ret = plan_consolidation(&config, &[], &[vec![record(1, CKB)]]);
+
+        assert_eq!(ret, Err(PlanError::InvalidConfig));
+    }
+}
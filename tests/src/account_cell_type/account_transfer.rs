@@ -6,6 +6,8 @@ use crate::util::{
 use das_types_std::constants::AccountStatus;
 use serde_json::json;
 
+const ETH_RECEIVER: &str = "0x050000000000000000000000000000000000002222";
+
 fn before_each() -> (TemplateGenerator, u64) {
     let (mut template, timestamp) = init("transfer_account", Some("0x00"));
 
@@ -44,6 +46,48 @@ fn test_account_transfer() {
     test_tx(template.as_json())
 }
 
+fn before_each_typed_data_signed() -> (TemplateGenerator, u64) {
+    let (mut template, timestamp) = init("transfer_account", Some("0x00"));
+
+    // inputs
+    push_input_account_cell(&mut template, json!({}));
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": ETH_RECEIVER,
+                "manager_lock_args": ETH_RECEIVER
+            },
+            "witness": {
+                "last_transfer_account_at": timestamp,
+            }
+        }),
+    );
+
+    (template, timestamp)
+}
+
+#[test]
+fn test_account_transfer_typed_data_signed() {
+    let (mut template, _) = before_each_typed_data_signed();
+
+    template.set_das_message("TRANSFER THE ACCOUNT xxxxx.bit TO 0x0000000000000000000000000000000000002222");
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_account_transfer_typed_data_message_mismatched() {
+    let (mut template, _) = before_each_typed_data_signed();
+
+    // Simulate signing a DAS_MESSAGE which is different from the transaction.
+    template.set_das_message("TRANSFER THE ACCOUNT xxxxx.bit TO 0x0000000000000000000000000000000000003333");
+
+    challenge_tx(template.as_json(), Error::EIP712SignatureError)
+}
+
 #[test]
 fn challenge_account_transfer_account_multiple_cells() {
     let (mut template, timestamp) = init("transfer_account", Some("0x00"));
@@ -38,6 +38,45 @@ fn test_account_edit_manager() {
     test_tx(template.as_json());
 }
 
+fn push_output_account_cell_with_eth_manager(template: &mut TemplateGenerator, timestamp: u64) {
+    push_output_account_cell(
+        template,
+        json!({
+            "lock": {
+                "manager_lock_args": "0x050000000000000000000000000000000000002222"
+            },
+            "witness": {
+                "last_edit_manager_at": timestamp,
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_account_edit_manager_typed_data_signed() {
+    let (mut template, timestamp) = before_each();
+
+    // outputs
+    push_output_account_cell_with_eth_manager(&mut template, timestamp);
+
+    template.set_das_message("EDIT MANAGER OF ACCOUNT xxxxx.bit TO 0x0000000000000000000000000000000000002222");
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_edit_manager_typed_data_message_mismatched() {
+    let (mut template, timestamp) = before_each();
+
+    // outputs
+    push_output_account_cell_with_eth_manager(&mut template, timestamp);
+
+    // Simulate signing the DAS_MESSAGE without the new manager.
+    template.set_das_message("EDIT MANAGER OF ACCOUNT xxxxx.bit");
+
+    challenge_tx(template.as_json(), Error::EIP712SignatureError)
+}
+
 #[test]
 fn test_account_edit_manager_and_upgrade_lock_type() {
     let (mut template, timestamp) = before_each();
@@ -1,7 +1,10 @@
 use super::common::init;
 use crate::util::{accounts::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*};
 use das_types_std::constants::*;
-use serde_json::json;
+use serde_json::{json, Value};
+
+const CID_1: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+const CID_2: &str = "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku";
 
 fn before_each() -> (TemplateGenerator, u64) {
     let (mut template, timestamp) = init("edit_records", Some("0x01"));
@@ -64,6 +67,10 @@ fn test_account_edit_records() {
     push_output_account_cell(
         &mut template,
         json!({
+            "data": {
+                // The records are not changed, so they keep the updated_at in inputs.
+                "records_updated_at": [0, 0]
+            },
             "witness": {
                 "last_edit_records_at": timestamp,
                 "records": [
@@ -87,6 +94,59 @@ fn test_account_edit_records() {
     test_tx(template.as_json());
 }
 
+fn push_output_account_cell_with_eth_records(template: &mut TemplateGenerator, timestamp: u64) {
+    push_output_account_cell(
+        template,
+        json!({
+            "data": {
+                "records_updated_at": [0, timestamp]
+            },
+            "witness": {
+                "last_edit_records_at": timestamp,
+                "records": [
+                    {
+                        "type": "address",
+                        "key": "eth",
+                        "label": "Personal",
+                        "value": "0x0000000000000000000000000000000000000000",
+                    },
+                    {
+                        "type": "address",
+                        "key": "eth",
+                        "label": "Company",
+                        "value": "0x0000000000000000000000000000000000003333",
+                    }
+                ]
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_account_edit_records_typed_data_signed() {
+    let (mut template, timestamp) = before_each();
+
+    push_output_account_cell_with_eth_records(&mut template, timestamp);
+
+    template.set_das_message(
+        "EDIT RECORDS OF ACCOUNT xxxxx.bit: CHANGE address.eth, REMOVE address.btc, REMOVE dweb.ipfs, REMOVE profile.email, REMOVE custom_key.xxxx",
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_edit_records_typed_data_message_mismatched() {
+    let (mut template, timestamp) = before_each();
+
+    push_output_account_cell_with_eth_records(&mut template, timestamp);
+
+    // Simulate signing the DAS_MESSAGE without the records changed.
+    template.set_das_message("EDIT RECORDS OF ACCOUNT xxxxx.bit");
+
+    challenge_tx(template.as_json(), Error::EIP712SignatureError)
+}
+
 #[test]
 fn challenge_account_edit_records_multiple_cells() {
     let (mut template, timestamp) = before_each();
@@ -197,3 +257,320 @@ fn challenge_account_edit_records_invalid_key() {
 
     challenge_tx(template.as_json(), Error::AccountCellRecordKeyInvalid)
 }
+
+#[test]
+fn test_account_edit_records_with_validated_values() {
+    let (mut template, timestamp) = before_each();
+
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "data": {
+                "records_updated_at": [timestamp, timestamp, timestamp]
+            },
+            "witness": {
+                "last_edit_records_at": timestamp,
+                "records": [
+                    {
+                        "type": "address",
+                        "key": "ckb",
+                        "label": "Personal",
+                        "value": "ckb1qzda0cr08m85hc8jlnfp3zer7xulejywt49kt2rr0vthywaa50xwsqgqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq5m759c",
+                    },
+                    {
+                        "type": "profile",
+                        "key": "website",
+                        "label": "Personal",
+                        "value": "https://did.id/explorer?account=xxxxx.bit",
+                    },
+                    {
+                        "type": "dweb",
+                        "key": "ipfs",
+                        "label": "Mars",
+                        "value": "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+                    }
+                ]
+            }
+        }),
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_edit_records_invalid_ckb_address() {
+    let (mut template, timestamp) = before_each();
+
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "last_edit_records_at": timestamp,
+                "records": [
+                    {
+                        "type": "address",
+                        "key": "ckb",
+                        "label": "Personal",
+                        // Simulate the checksum of the address is broken.
+                        "value": "ckb1qzda0cr08m85hc8jlnfp3zer7xulejywt49kt2rr0vthywaa50xwsqgqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq5m759d",
+                    }
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), Error::AccountCellRecordValueInvalid)
+}
+
+#[test]
+fn challenge_account_edit_records_invalid_url() {
+    let (mut template, timestamp) = before_each();
+
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "last_edit_records_at": timestamp,
+                "records": [
+                    {
+                        "type": "profile",
+                        "key": "website",
+                        "label": "Personal",
+                        // Simulate the url contains whitespace.
+                        "value": "https://did.id/ explorer",
+                    }
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), Error::AccountCellRecordValueInvalid)
+}
+
+#[test]
+fn challenge_account_edit_records_invalid_ipfs_cid() {
+    let (mut template, timestamp) = before_each();
+
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "last_edit_records_at": timestamp,
+                "records": [
+                    {
+                        "type": "dweb",
+                        "key": "ipfs",
+                        "label": "Mars",
+                        // Simulate the value is not a CID.
+                        "value": "0x00000000000000000000",
+                    }
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), Error::AccountCellRecordValueInvalid)
+}
+
+fn push_output_account_cell_with_evm_address(template: &mut TemplateGenerator, timestamp: u64, address: &str) {
+    push_output_account_cell(
+        template,
+        json!({
+            "data": {
+                "records_updated_at": [timestamp]
+            },
+            "witness": {
+                "last_edit_records_at": timestamp,
+                "records": [
+                    {
+                        "type": "address",
+                        "key": "eth",
+                        "label": "Personal",
+                        "value": address,
+                    }
+                ]
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_account_edit_records_evm_address_with_checksum() {
+    let (mut template, timestamp) = before_each();
+
+    push_output_account_cell_with_evm_address(&mut template, timestamp, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_account_edit_records_evm_address_in_lowercase() {
+    let (mut template, timestamp) = before_each();
+
+    // The addresses without uppercase letters have no checksum.
+    push_output_account_cell_with_evm_address(&mut template, timestamp, "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed");
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_edit_records_evm_address_with_broken_checksum() {
+    let (mut template, timestamp) = before_each();
+
+    // Simulate the case of the last letter is changed, so the EIP-55 checksum is broken.
+    push_output_account_cell_with_evm_address(&mut template, timestamp, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD");
+
+    challenge_tx(template.as_json(), Error::AccountCellRecordValueInvalid)
+}
+
+#[test]
+fn challenge_account_edit_records_evm_address_too_short() {
+    let (mut template, timestamp) = before_each();
+
+    // Simulate the address is not 20 bytes.
+    push_output_account_cell_with_evm_address(&mut template, timestamp, "0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea");
+
+    challenge_tx(template.as_json(), Error::AccountCellRecordValueInvalid)
+}
+
+#[test]
+fn challenge_account_edit_records_ttl_out_of_range() {
+    let (mut template, timestamp) = before_each();
+
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "last_edit_records_at": timestamp,
+                "records": [
+                    {
+                        "type": "address",
+                        "key": "eth",
+                        "label": "Personal",
+                        "value": "0x0000000000000000000000000000000000000000",
+                        // Simulate the ttl of a changed record is too small.
+                        "ttl": 1
+                    }
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), Error::AccountCellRecordTTLInvalid)
+}
+
+#[test]
+fn test_account_edit_records_keep_updated_at() {
+    let (mut template, timestamp) = init("edit_records", Some("0x01"));
+    template.push_config_cell(DataType::ConfigCellRecordKeyNamespace, Source::CellDep);
+
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "data": {
+                "records_updated_at": [timestamp - 100, timestamp - 200]
+            },
+            "witness": {
+                "records": [
+                    gen_eth_record("0x0000000000000000000000000000000000000000"),
+                    gen_ipfs_record(CID_1)
+                ]
+            }
+        }),
+    );
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "data": {
+                // The unchanged record keeps its updated_at even if its index is changed.
+                "records_updated_at": [timestamp, timestamp - 100]
+            },
+            "witness": {
+                "last_edit_records_at": timestamp,
+                "records": [
+                    gen_ipfs_record(CID_2),
+                    gen_eth_record("0x0000000000000000000000000000000000000000")
+                ]
+            }
+        }),
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_edit_records_updated_at_of_unchanged_record() {
+    let (mut template, timestamp) = init("edit_records", Some("0x01"));
+    template.push_config_cell(DataType::ConfigCellRecordKeyNamespace, Source::CellDep);
+
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "data": {
+                "records_updated_at": [timestamp - 100, timestamp - 200]
+            },
+            "witness": {
+                "records": [
+                    gen_eth_record("0x0000000000000000000000000000000000000000"),
+                    gen_ipfs_record(CID_1)
+                ]
+            }
+        }),
+    );
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "data": {
+                // Simulate updating the updated_at of the unchanged record.
+                "records_updated_at": [timestamp, timestamp]
+            },
+            "witness": {
+                "last_edit_records_at": timestamp,
+                "records": [
+                    gen_eth_record("0x0000000000000000000000000000000000000000"),
+                    gen_ipfs_record(CID_2)
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), Error::AccountCellRecordUpdatedAtError)
+}
+
+#[test]
+fn challenge_account_edit_records_updated_at_missing() {
+    let (mut template, timestamp) = before_each();
+
+    // Simulate changing the records without the records_updated_at.
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "last_edit_records_at": timestamp,
+                "records": [
+                    gen_eth_record("0x0000000000000000000000000000000000001111")
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), Error::AccountCellRecordUpdatedAtError)
+}
+
+fn gen_ipfs_record(cid: &str) -> Value {
+    json!({
+        "type": "dweb",
+        "key": "ipfs",
+        "label": "Mars",
+        "value": cid,
+    })
+}
+
+fn gen_eth_record(address: &str) -> Value {
+    json!({
+        "type": "address",
+        "key": "eth",
+        "label": "Personal",
+        "value": address,
+    })
+}
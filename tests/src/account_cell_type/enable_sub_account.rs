@@ -52,6 +52,38 @@ fn test_enable_sub_account() {
     test_tx(template.as_json())
 }
 
+#[test]
+fn test_enable_sub_account_typed_data_signed() {
+    let mut template = before_each();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER,
+                "manager_lock_args": MANAGER
+            },
+            "witness": {
+                "enable_sub_account": 1,
+            }
+        }),
+    );
+    push_output_sub_account_cell(
+        &mut template,
+        json!({
+            "type": {
+                "args": ACCOUNT
+            },
+        }),
+    );
+    push_output_balance_cell(&mut template, 479_000_000_000, SENDER);
+
+    template.set_das_message("ENABLE SUB-ACCOUNT FOR ACCOUNT xxxxx.bit");
+
+    test_tx(template.as_json())
+}
+
 #[test]
 fn challenge_enable_sub_account_beta_limit() {
     let mut template = init_for_sub_account("enable_sub_account", Some("0x00"));
@@ -0,0 +1,122 @@
+use super::common::init;
+use crate::util::{
+    accounts::*, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
+};
+use das_types_std::constants::*;
+use serde_json::json;
+
+fn before_each(lease_end: u64) -> TemplateGenerator {
+    let (mut template, _) = init("end_lease", None);
+
+    // inputs
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": RECEIVER
+            },
+            "data": {
+                "lease_end": lease_end
+            },
+            "witness": {
+                "status": ACCOUNT_STATUS_LEASED
+            }
+        }),
+    );
+
+    template
+}
+
+#[test]
+fn test_account_end_lease() {
+    let (_, timestamp) = init("end_lease", None);
+    let mut template = before_each(timestamp - 1);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": OWNER
+            },
+            "witness": {
+                "last_edit_manager_at": timestamp,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_end_lease_not_ended() {
+    let (_, timestamp) = init("end_lease", None);
+    // Simulate the lease is still not ended.
+    let mut template = before_each(timestamp + 1);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": OWNER
+            },
+            "witness": {
+                "last_edit_manager_at": timestamp,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), Error::AccountCellLeaseError);
+}
+
+#[test]
+fn challenge_account_end_lease_manager_not_restored() {
+    let (_, timestamp) = init("end_lease", None);
+    let mut template = before_each(timestamp - 1);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            // Simulate the manager is not restored to the owner.
+            "lock": {
+                "manager_lock_args": "0x050000000000000000000000000000000000003333"
+            },
+            "witness": {
+                "last_edit_manager_at": timestamp,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), Error::AccountCellLeaseError);
+}
+
+#[test]
+fn challenge_account_end_lease_lease_end_not_removed() {
+    let (_, timestamp) = init("end_lease", None);
+    let mut template = before_each(timestamp - 1);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": OWNER
+            },
+            "data": {
+                // Simulate the lease_end is kept in the AccountCell.data after the lease ends.
+                "lease_end": timestamp - 1
+            },
+            "witness": {
+                "last_edit_manager_at": timestamp,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), Error::AccountCellLeaseError);
+}
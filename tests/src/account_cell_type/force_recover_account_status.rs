@@ -1,9 +1,9 @@
 use super::common::init;
 use crate::util::{
-    self, accounts::*, constants::*, template_common_cell::*, template_generator::*, template_parser::*,
+    self, accounts::*, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
 };
 use das_types_std::constants::AccountStatus;
-use serde_json::json;
+use serde_json::{json, Value};
 
 fn push_input_account_cell(template: &mut TemplateGenerator, timestamp: u64, status: AccountStatus) {
     template.push_input(
@@ -70,10 +70,7 @@ fn before_each() -> (TemplateGenerator, u64) {
     (template, timestamp)
 }
 
-#[test]
-fn test_account_force_recover_account_status() {
-    let (mut template, timestamp) = before_each();
-
+fn push_output_recovered_account_cell(template: &mut TemplateGenerator, timestamp: u64) {
     template.push_output(
         json!({
             "capacity": util::gen_account_cell_capacity(8),
@@ -100,8 +97,216 @@ fn test_account_force_recover_account_status() {
         }),
         Some(3),
     );
+}
 
+#[test]
+fn test_account_force_recover_account_status() {
+    let (mut template, timestamp) = before_each();
+
+    push_output_recovered_account_cell(&mut template, timestamp);
     push_output_balance_cell(&mut template, 20_099_990_000, OWNER);
 
     test_tx(template.as_json());
 }
+
+fn push_input_counter_offer(template: &mut TemplateGenerator, account: &str) {
+    template.push_input(
+        json!({
+            "capacity": "20_100_000_000",
+            "lock": {
+                "owner_lock_args": OWNER,
+                "manager_lock_args": OWNER
+            },
+            "type": {
+                "code_hash": "{{offer-cell-type}}"
+            },
+            "data": {
+                "collection": {
+                    "type": OFFER_COLLECTION_TYPE_COUNTER,
+                    "args": util::bytes_to_hex(util::mock_out_point(0).as_slice())
+                }
+            },
+            "witness": {
+                "account": account,
+                "price": "250_000_000_000",
+                "message": "Make it a bit higher.",
+                "inviter_lock": Value::Null,
+                "channel_lock": Value::Null
+            }
+        }),
+        None,
+    );
+    template.push_das_lock_witness("0000000000000000000000000000000000000000000000000000000000000000");
+}
+
+fn before_each_counter_offer(account: &str) -> (TemplateGenerator, u64) {
+    let (mut template, timestamp) = init("force_recover_account_status", None);
+
+    template.push_contract_cell("offer-cell-type", false);
+    template.push_contract_cell("balance-cell-type", false);
+
+    push_input_account_cell(&mut template, timestamp, AccountStatus::Selling);
+    // The AccountCell is locked by a counter offer instead of an AccountSaleCell.
+    push_input_counter_offer(&mut template, account);
+
+    (template, timestamp)
+}
+
+#[test]
+fn test_account_force_recover_account_status_locked_by_counter_offer() {
+    let (mut template, timestamp) = before_each_counter_offer("das00001.bit");
+
+    push_output_recovered_account_cell(&mut template, timestamp);
+    push_output_balance_cell(&mut template, 20_099_990_000, OWNER);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_force_recover_account_status_counter_offer_mismatched() {
+    // Simulate recycling a counter offer which is made for another account.
+    let (mut template, timestamp) = before_each_counter_offer("das00014.bit");
+
+    push_output_recovered_account_cell(&mut template, timestamp);
+    push_output_balance_cell(&mut template, 20_099_990_000, OWNER);
+
+    challenge_tx(template.as_json(), Error::InvalidTransactionStructure);
+}
+
+const BUNDLE_ACCOUNT: &str = "das00014.bit";
+
+fn push_bundle_account_cell(
+    template: &mut TemplateGenerator,
+    is_input: bool,
+    account: &str,
+    expired_at: u64,
+    registered_at: u64,
+    status: AccountStatus,
+) {
+    let cell = json!({
+        "capacity": util::gen_account_cell_capacity(8),
+        "lock": {
+            "owner_lock_args": OWNER,
+            "manager_lock_args": MANAGER
+        },
+        "type": {
+            "code_hash": "{{account-cell-type}}"
+        },
+        "data": {
+            "account": account,
+            "next": "das00020.bit",
+            "expired_at": expired_at,
+        },
+        "witness": {
+            "account": account,
+            "registered_at": registered_at,
+            "last_transfer_account_at": 0,
+            "last_edit_manager_at": 0,
+            "last_edit_records_at": 0,
+            "status": (status as u8)
+        }
+    });
+
+    if is_input {
+        template.push_input(cell, Some(2));
+        template.push_das_lock_witness("0000000000000000000000000000000000000000000000000000000000000000");
+    } else {
+        template.push_output(cell, Some(3));
+    }
+}
+
+fn before_each_bundle(is_bundle_expired: bool, bundle_status: AccountStatus) -> TemplateGenerator {
+    let (mut template, timestamp) = init("force_recover_account_status", None);
+
+    template.push_contract_cell("account-sale-cell-type", false);
+    template.push_contract_cell("balance-cell-type", false);
+
+    let registered_at = timestamp - YEAR_SEC;
+    let bundle_expired_at = if is_bundle_expired {
+        timestamp - DAY_SEC
+    } else {
+        timestamp + YEAR_SEC
+    };
+    push_bundle_account_cell(
+        &mut template,
+        true,
+        "das00001.bit",
+        timestamp - DAY_SEC,
+        registered_at,
+        AccountStatus::Selling,
+    );
+    push_bundle_account_cell(
+        &mut template,
+        true,
+        BUNDLE_ACCOUNT,
+        bundle_expired_at,
+        registered_at,
+        bundle_status,
+    );
+    template.push_input(
+        json!({
+            "capacity": "20_100_000_000",
+            "lock": {
+                "owner_lock_args": OWNER,
+                "manager_lock_args": MANAGER
+            },
+            "type": {
+                "code_hash": "{{account-sale-cell-type}}"
+            },
+            "data": {
+                "bundle": [BUNDLE_ACCOUNT]
+            },
+            "witness": {
+                "account": "das00001.bit",
+                "price": "20_000_000_000",
+                "description": "This is some account description.",
+                "started_at": timestamp - MONTH_SEC,
+                "buyer_inviter_profit_rate": SALE_BUYER_INVITER_PROFIT_RATE
+            }
+        }),
+        Some(2),
+    );
+
+    push_bundle_account_cell(
+        &mut template,
+        false,
+        "das00001.bit",
+        timestamp - DAY_SEC,
+        registered_at,
+        AccountStatus::Normal,
+    );
+    push_bundle_account_cell(
+        &mut template,
+        false,
+        BUNDLE_ACCOUNT,
+        bundle_expired_at,
+        registered_at,
+        AccountStatus::Normal,
+    );
+    push_output_balance_cell(&mut template, 20_099_990_000, OWNER);
+
+    template
+}
+
+#[test]
+fn test_account_force_recover_account_status_bundle() {
+    let template = before_each_bundle(true, AccountStatus::Selling);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_force_recover_account_status_bundle_partially_expired() {
+    // Simulate recovering a bundle while one of its accounts is still not expired.
+    let template = before_each_bundle(false, AccountStatus::Selling);
+
+    challenge_tx(template.as_json(), Error::AccountCellIsNotExpired);
+}
+
+#[test]
+fn challenge_account_force_recover_account_status_bundle_status_mismatched() {
+    // Simulate recovering a bundle while one of its accounts is in a different status.
+    let template = before_each_bundle(true, AccountStatus::Auction);
+
+    challenge_tx(template.as_json(), Error::InvalidTransactionStructure);
+}
@@ -0,0 +1,228 @@
+use super::common::init;
+use crate::util::{
+    accounts::*, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
+};
+use das_types_std::constants::*;
+use serde_json::json;
+
+const LEASE_DURATION: u64 = 86400 * 30;
+const LEASE_PRICE: u64 = 10_000_000_000;
+// The params are the price of the lease in little-endian, followed by the role.
+const LEASE_PARAMS: &str = "0x00e40b540200000000";
+
+fn before_each() -> (TemplateGenerator, u64) {
+    let (mut template, timestamp) = init("lease_account", Some(LEASE_PARAMS));
+    template.push_contract_cell("balance-cell-type", false);
+
+    // inputs
+    push_input_account_cell(&mut template, json!({}));
+    push_input_balance_cell(&mut template, LEASE_PRICE, RECEIVER);
+
+    (template, timestamp)
+}
+
+#[test]
+fn test_account_lease_account() {
+    let (mut template, timestamp) = before_each();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": RECEIVER
+            },
+            "data": {
+                "lease_end": timestamp + LEASE_DURATION
+            },
+            "witness": {
+                "last_edit_manager_at": timestamp,
+                "status": ACCOUNT_STATUS_LEASED
+            }
+        }),
+    );
+    push_output_balance_cell(&mut template, LEASE_PRICE, OWNER);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_lease_account_end_in_past() {
+    let (mut template, timestamp) = before_each();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": RECEIVER
+            },
+            "data": {
+                // Simulate the lease is ended before it starts.
+                "lease_end": timestamp
+            },
+            "witness": {
+                "last_edit_manager_at": timestamp,
+                "status": ACCOUNT_STATUS_LEASED
+            }
+        }),
+    );
+    push_output_balance_cell(&mut template, LEASE_PRICE, OWNER);
+
+    challenge_tx(template.as_json(), Error::AccountCellLeaseError);
+}
+
+#[test]
+fn challenge_account_lease_account_end_after_expired() {
+    let (mut template, timestamp) = init("lease_account", Some(LEASE_PARAMS));
+    template.push_contract_cell("balance-cell-type", false);
+
+    // inputs
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "data": {
+                "expired_at": timestamp + LEASE_DURATION - 1
+            }
+        }),
+    );
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": RECEIVER
+            },
+            "data": {
+                "expired_at": timestamp + LEASE_DURATION - 1,
+                // Simulate the lease is ended after the AccountCell expired.
+                "lease_end": timestamp + LEASE_DURATION
+            },
+            "witness": {
+                "last_edit_manager_at": timestamp,
+                "status": ACCOUNT_STATUS_LEASED
+            }
+        }),
+    );
+    push_output_balance_cell(&mut template, LEASE_PRICE, OWNER);
+
+    challenge_tx(template.as_json(), Error::AccountCellLeaseError);
+}
+
+#[test]
+fn challenge_account_lease_account_without_lease_end() {
+    let (mut template, timestamp) = before_each();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": RECEIVER
+            },
+            // Simulate the lease_end is not appended to the AccountCell.data.
+            "witness": {
+                "last_edit_manager_at": timestamp,
+                "status": ACCOUNT_STATUS_LEASED
+            }
+        }),
+    );
+    push_output_balance_cell(&mut template, LEASE_PRICE, OWNER);
+
+    challenge_tx(template.as_json(), Error::AccountCellLeaseError);
+}
+
+#[test]
+fn challenge_account_lease_account_status_not_changed() {
+    let (mut template, timestamp) = before_each();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": RECEIVER
+            },
+            "data": {
+                "lease_end": timestamp + LEASE_DURATION
+            },
+            "witness": {
+                "last_edit_manager_at": timestamp,
+                // Simulate the status is not changed to LEASED.
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+    push_output_balance_cell(&mut template, LEASE_PRICE, OWNER);
+
+    challenge_tx(template.as_json(), Error::AccountCellStatusLocked);
+}
+
+#[test]
+fn challenge_account_lease_account_not_paid() {
+    let (mut template, timestamp) = before_each();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": RECEIVER
+            },
+            "data": {
+                "lease_end": timestamp + LEASE_DURATION
+            },
+            "witness": {
+                "last_edit_manager_at": timestamp,
+                "status": ACCOUNT_STATUS_LEASED
+            }
+        }),
+    );
+    // Simulate the lessee paying less than the price of the lease.
+    push_output_balance_cell(&mut template, LEASE_PRICE - 1, OWNER);
+    push_output_balance_cell(&mut template, 1, RECEIVER);
+
+    challenge_tx(template.as_json(), Error::AccountCellLeaseError);
+}
+
+#[test]
+fn challenge_account_edit_manager_when_leased() {
+    let (mut template, timestamp) = init("edit_manager", Some("0x00"));
+
+    // inputs
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "manager_lock_args": RECEIVER
+            },
+            "data": {
+                "lease_end": timestamp + LEASE_DURATION
+            },
+            "witness": {
+                "status": ACCOUNT_STATUS_LEASED
+            }
+        }),
+    );
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            // Simulate the owner taking the manager back during the lease.
+            "lock": {
+                "manager_lock_args": MANAGER
+            },
+            "data": {
+                "lease_end": timestamp + LEASE_DURATION
+            },
+            "witness": {
+                "last_edit_manager_at": timestamp,
+                "status": ACCOUNT_STATUS_LEASED
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), Error::AccountCellStatusLocked);
+}
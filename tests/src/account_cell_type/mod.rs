@@ -2,7 +2,9 @@ mod account_transfer;
 mod common;
 mod edit_manager;
 mod edit_records;
+mod end_lease;
 mod force_recover_account_status;
+mod lease_account;
 // mod init_account_chain;
 mod enable_sub_account;
 mod renew_account;
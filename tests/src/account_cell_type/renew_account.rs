@@ -362,3 +362,44 @@ fn challenge_account_renew_income_cell_capacity() {
 
     challenge_tx(template.as_json(), Error::IncomeCellCapacityError)
 }
+
+#[test]
+fn challenge_account_renew_income_cell_payout() {
+    let (mut template, timestamp) = before_each();
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": OWNER,
+            },
+            "data": {
+                "expired_at": timestamp + 31_536_000,
+            }
+        }),
+    );
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "data": {
+                // Simulate choosing a payout for the new record which is not signed by the payee.
+                "payouts": [INCOME_PAYOUT_DAO_DEPOSIT]
+            },
+            "witness": {
+                "records": [
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": DAS_WALLET_LOCK_ARGS
+                        },
+                        "capacity": "500_000_000_000"
+                    }
+                ]
+            }
+        }),
+    );
+    push_output_balance_cell(&mut template, 500_000_000_000, OWNER);
+
+    challenge_tx(template.as_json(), Error::IncomeCellPayoutError);
+}
@@ -922,3 +922,126 @@ fn challenge_account_sale_buy_not_clear_records() {
 
     challenge_tx(template.as_json(), Error::AccountCellRecordNotEmpty)
 }
+
+fn before_each_bundle(paid: u64) -> TemplateGenerator {
+    let params = gen_params(INVITER, CHANNEL);
+    let mut template = init_with_profit_rate("buy_account", Some(&params));
+
+    // inputs
+    for account in [ACCOUNT, BUNDLE_ACCOUNT] {
+        push_input_account_cell(
+            &mut template,
+            json!({
+                "lock": {
+                    "owner_lock_args": SELLER,
+                    "manager_lock_args": SELLER
+                },
+                "data": {
+                    "account": account,
+                },
+                "witness": {
+                    "status": (AccountStatus::Selling as u8)
+                }
+            }),
+        );
+    }
+    push_input_account_sale_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": SELLER,
+                "manager_lock_args": SELLER
+            },
+            "data": {
+                "bundle": [BUNDLE_ACCOUNT]
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": PRICE.to_string(),
+            }
+        }),
+    );
+    push_input_balance_cell(&mut template, paid, BUYER);
+
+    template
+}
+
+#[test]
+fn test_account_sale_buy_bundle() {
+    let mut template = before_each_bundle(PRICE);
+
+    // outputs
+    for account in [ACCOUNT, BUNDLE_ACCOUNT] {
+        push_output_account_cell(
+            &mut template,
+            json!({
+                "lock": {
+                    "owner_lock_args": BUYER,
+                    "manager_lock_args": BUYER
+                },
+                "data": {
+                    "account": account,
+                },
+                "witness": {
+                    "status": (AccountStatus::Normal as u8)
+                }
+            }),
+        );
+    }
+    push_simple_output_income_cell(&mut template);
+    push_output_balance_cell(
+        &mut template,
+        PRICE - 6_000_000_000 + ACCOUNT_SALE_BASIC_CAPACITY + ACCOUNT_SALE_PREPARED_FEE_CAPACITY
+            - SECONDARY_MARKET_COMMON_FEE,
+        SELLER,
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_sale_buy_bundle_partially() {
+    let mut template = before_each_bundle(PRICE);
+
+    // outputs
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": BUYER,
+                "manager_lock_args": BUYER
+            },
+            "data": {
+                "account": ACCOUNT,
+            },
+            "witness": {
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+    push_output_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                // Simulate keeping one of the accounts in the bundle to the seller.
+                "owner_lock_args": SELLER,
+                "manager_lock_args": SELLER
+            },
+            "data": {
+                "account": BUNDLE_ACCOUNT,
+            },
+            "witness": {
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+    push_simple_output_income_cell(&mut template);
+    push_output_balance_cell(
+        &mut template,
+        PRICE - 6_000_000_000 + ACCOUNT_SALE_BASIC_CAPACITY + ACCOUNT_SALE_PREPARED_FEE_CAPACITY
+            - SECONDARY_MARKET_COMMON_FEE,
+        SELLER,
+    );
+
+    challenge_tx(template.as_json(), Error::AccountSaleCellNewOwnerError)
+}
@@ -3,6 +3,8 @@ use das_types_std::{constants::*, packed::*};
 use serde_json::{json, Value};
 
 pub const PRICE: u64 = 200_000_000_000;
+// The account which is sold together with the ACCOUNT in bundle tests.
+pub const BUNDLE_ACCOUNT: &str = "bundle.bit";
 
 pub fn init(action: &str, params_opt: Option<&str>) -> TemplateGenerator {
     let mut template = TemplateGenerator::new(action, params_opt.map(|raw| Bytes::from(util::hex_to_bytes(raw))));
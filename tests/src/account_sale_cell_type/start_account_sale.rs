@@ -659,3 +659,136 @@ fn challenge_account_sale_start_with_old_version() {
 
     challenge_tx(template.as_json(), Error::InvalidTransactionStructure)
 }
+
+fn before_each_bundle(bundle_owner: &str) -> (TemplateGenerator, u64) {
+    let mut template = init("start_account_sale", Some("0x00"));
+
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": SELLER,
+                "manager_lock_args": SELLER
+            },
+            "data": {
+                "account": ACCOUNT
+            },
+            "witness": {
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": bundle_owner,
+                "manager_lock_args": bundle_owner
+            },
+            "data": {
+                "account": BUNDLE_ACCOUNT
+            },
+            "witness": {
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    let total_input = 600_000_000_000;
+    push_input_balance_cell(&mut template, total_input, SELLER);
+
+    (template, total_input)
+}
+
+fn push_bundle_outputs(template: &mut TemplateGenerator, total_input: u64, bundle_owner: &str, bundle: Vec<&str>) {
+    push_output_account_cell(
+        template,
+        json!({
+            "lock": {
+                "owner_lock_args": SELLER,
+                "manager_lock_args": SELLER
+            },
+            "data": {
+                "account": ACCOUNT
+            },
+            "witness": {
+                "status": (AccountStatus::Selling as u8)
+            }
+        }),
+    );
+    push_output_account_cell(
+        template,
+        json!({
+            "lock": {
+                "owner_lock_args": bundle_owner,
+                "manager_lock_args": bundle_owner
+            },
+            "data": {
+                "account": BUNDLE_ACCOUNT
+            },
+            "witness": {
+                "status": (AccountStatus::Selling as u8)
+            }
+        }),
+    );
+    push_output_account_sale_cell(
+        template,
+        json!({
+            "lock": {
+                "owner_lock_args": SELLER,
+                "manager_lock_args": SELLER
+            },
+            "data": {
+                "bundle": bundle
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": PRICE
+            }
+        }),
+    );
+    push_output_balance_cell(
+        template,
+        total_input - ACCOUNT_SALE_BASIC_CAPACITY - ACCOUNT_SALE_PREPARED_FEE_CAPACITY - SECONDARY_MARKET_COMMON_FEE,
+        SELLER,
+    );
+}
+
+#[test]
+fn test_account_sale_start_bundle() {
+    let (mut template, total_input) = before_each_bundle(SELLER);
+
+    push_bundle_outputs(&mut template, total_input, SELLER, vec![BUNDLE_ACCOUNT]);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_account_sale_start_bundle_owner() {
+    // Simulate selling an account which belongs to others in the bundle.
+    let (mut template, total_input) = before_each_bundle(BUYER);
+
+    push_bundle_outputs(&mut template, total_input, BUYER, vec![BUNDLE_ACCOUNT]);
+
+    challenge_tx(template.as_json(), Error::AccountSaleCellBundleError)
+}
+
+#[test]
+fn challenge_account_sale_start_bundle_missing_account_id() {
+    let (mut template, total_input) = before_each_bundle(SELLER);
+
+    // Simulate the AccountSaleCell do not record all the accounts in the bundle.
+    push_bundle_outputs(&mut template, total_input, SELLER, vec![]);
+
+    challenge_tx(template.as_json(), Error::AccountSaleCellBundleError)
+}
+
+#[test]
+fn challenge_account_sale_start_bundle_wrong_account_id() {
+    let (mut template, total_input) = before_each_bundle(SELLER);
+
+    // Simulate the AccountSaleCell recording an account which is not in the bundle.
+    push_bundle_outputs(&mut template, total_input, SELLER, vec!["yyyyy.bit"]);
+
+    challenge_tx(template.as_json(), Error::AccountSaleCellBundleError)
+}
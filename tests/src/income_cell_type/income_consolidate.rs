@@ -1,8 +1,9 @@
 use crate::util::{
     accounts::*, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
 };
+use das_income_planner::{ConsolidatePlan, IncomeConfig, IncomeRecord};
 use das_types_std::constants::{DataType, Source};
-use serde_json::json;
+use serde_json::{json, Value};
 
 use super::common::init;
 
@@ -63,6 +64,138 @@ fn push_common_inputs(template: &mut TemplateGenerator) {
     );
 }
 
+fn to_json_records(records: &[IncomeRecord]) -> Value {
+    Value::Array(
+        records
+            .iter()
+            .map(|record| {
+                json!({
+                    "belong_to": {
+                        "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                        "args": format!("0x{}", hex::encode(&record.belong_to))
+                    },
+                    "capacity": record.capacity
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Push the IncomeCells in inputs and build the outputs with the planner which the keepers use.
+///
+/// The planner treats `belong_to` as opaque bytes, so the lock args are used here instead of the whole lock script.
+fn push_planned_consolidation(
+    template: &mut TemplateGenerator,
+    creator: Option<&str>,
+    cells: &[Vec<(&str, u64)>],
+) -> ConsolidatePlan {
+    let cells = cells
+        .iter()
+        .map(|records| {
+            records
+                .iter()
+                .map(|(args, capacity)| {
+                    IncomeRecord::new(hex::decode(args.trim_start_matches("0x")).unwrap(), *capacity)
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    for records in cells.iter() {
+        let cell = json!({ "witness": { "records": to_json_records(records) } });
+        match creator {
+            Some(_) => push_input_income_cell(template, cell),
+            None => push_input_income_cell_no_creator(template, cell),
+        }
+    }
+
+    let config = IncomeConfig {
+        basic_capacity: INCOME_BASIC_CAPACITY,
+        max_records: 50,
+        min_transfer_capacity: 10_000_000_000,
+        consolidate_profit_rate: CONSOLIDATING_FEE,
+    };
+    // Always include DAS in the members which is free from consolidating fee.
+    let free_locks = creator
+        .into_iter()
+        .chain([DAS_WALLET_LOCK_ARGS])
+        .map(|args| hex::decode(args.trim_start_matches("0x")).unwrap())
+        .collect::<Vec<_>>();
+    let plan = das_income_planner::plan_consolidation(&config, &free_locks, &cells).unwrap();
+
+    for records in plan.income_cells.iter() {
+        let cell = json!({ "witness": { "records": to_json_records(records) } });
+        match creator {
+            Some(_) => push_output_income_cell(template, cell),
+            None => push_output_income_cell_no_creator(template, cell),
+        }
+    }
+    for record in plan.transfers.iter() {
+        push_output_normal_cell(
+            template,
+            record.capacity,
+            &format!("0x{}", hex::encode(&record.belong_to)),
+        );
+    }
+
+    plan
+}
+
+fn push_input_income_cells_with_payout(template: &mut TemplateGenerator) {
+    push_input_income_cell(
+        template,
+        json!({
+            "data": {
+                // The record of 0x0000000000000000000000000000000000000010 should be paid out as a NervosDAO deposit.
+                "payouts": [0, INCOME_PAYOUT_DAO_DEPOSIT]
+            },
+            "witness": {
+                "records": [
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": COMMON_INCOME_CREATOR
+                        },
+                        "capacity": 20_000_000_000u64
+                    },
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": "0x0000000000000000000000000000000000000010"
+                        },
+                        "capacity": 10_000_000_000u64, // 100 CKB
+                    },
+                ]
+            }
+        }),
+    );
+    push_input_income_cell(
+        template,
+        json!({
+            "witness": {
+                "records": [
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": COMMON_INCOME_CREATOR
+                        },
+                        "capacity": 20_000_000_000u64
+                    },
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": "0x0000000000000000000000000000000000000010"
+                        },
+                        "capacity": 200_000_000, // 2 CKB
+                    },
+                ]
+            }
+        }),
+    );
+    // 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF is the keeper who pushed the consolidate_income transaction.
+    push_input_normal_cell(template, 6_100_000_000, "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF");
+}
+
 #[test]
 fn test_income_consolidate_need_pad_1() {
     let mut template = before();
@@ -217,71 +350,33 @@ fn test_income_consolidate_need_pad_1() {
 fn test_income_consolidate_no_pad() {
     let mut template = before();
 
-    // inputs
-    push_input_income_cell(
-        &mut template,
-        json!({
-            "witness": {
-                "records": [
-                    {
-                        "belong_to": {
-                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
-                            "args": COMMON_INCOME_CREATOR
-                        },
-                        "capacity": 20_000_000_000u64
-                    },
-                    {
-                        "belong_to": {
-                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
-                            "args": "0x0000000000000000000000000000000000000010"
-                        },
-                        "capacity": 10_000_000_000u64, // 100 CKB
-                    },
-                ]
-            }
-        }),
-    );
-    push_input_income_cell(
-        &mut template,
-        json!({
-            "witness": {
-                "records": [
-                    {
-                        "belong_to": {
-                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
-                            "args": COMMON_INCOME_CREATOR
-                        },
-                        "capacity": 20_000_000_000u64
-                    },
-                    {
-                        "belong_to": {
-                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
-                            "args": "0x0000000000000000000000000000000000000010"
-                        },
-                        "capacity": 200_000_000, // 2 CKB
-                    },
-                ]
-            }
-        }),
-    );
     // 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF is the keeper who pushed the consolidate_income transaction.
     push_input_normal_cell(
         &mut template,
         6_100_000_000,
         "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
     );
-
-    // outputs
-    push_output_normal_cell(&mut template, 40_000_000_000, COMMON_INCOME_CREATOR);
-    push_output_normal_cell(
+    let plan = push_planned_consolidation(
         &mut template,
-        10_098_000_000,
-        "0x0000000000000000000000000000000000000010",
+        Some(COMMON_INCOME_CREATOR),
+        &[
+            vec![
+                (COMMON_INCOME_CREATOR, 20_000_000_000),
+                ("0x0000000000000000000000000000000000000010", 10_000_000_000), // 100 CKB
+            ],
+            vec![
+                (COMMON_INCOME_CREATOR, 20_000_000_000),
+                ("0x0000000000000000000000000000000000000010", 200_000_000), // 2 CKB
+            ],
+        ],
     );
+
+    assert!(!plan.need_pad());
+    assert_eq!(plan.transfers.len(), 2);
     // 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF can take some from user as their profit.
     push_output_normal_cell(
         &mut template,
-        6_162_000_000,
+        6_100_000_000 + plan.fee,
         "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
     );
 
@@ -292,58 +387,23 @@ fn test_income_consolidate_no_pad() {
 fn test_income_consolidate_free_fee() {
     let mut template = before();
 
-    // inputs
-    push_input_income_cell(
+    let plan = push_planned_consolidation(
         &mut template,
-        json!({
-            "witness": {
-                "records": [
-                    {
-                        "belong_to": {
-                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
-                            "args": COMMON_INCOME_CREATOR
-                        },
-                        "capacity": 20_000_000_000u64
-                    },
-                    {
-                        "belong_to": {
-                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
-                            "args": DAS_WALLET_LOCK_ARGS
-                        },
-                        "capacity": 10_000_000_000u64, // 100 CKB
-                    },
-                ]
-            }
-        }),
-    );
-    push_input_income_cell(
-        &mut template,
-        json!({
-            "witness": {
-                "records": [
-                    {
-                        "belong_to": {
-                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
-                            "args": COMMON_INCOME_CREATOR
-                        },
-                        "capacity": 20_000_000_000u64
-                    },
-                    {
-                        "belong_to": {
-                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
-                            "args": DAS_WALLET_LOCK_ARGS
-                        },
-                        "capacity": 10_000_000_000u64, // 100 CKB
-                    },
-                ]
-            }
-        }),
+        Some(COMMON_INCOME_CREATOR),
+        &[
+            vec![
+                (COMMON_INCOME_CREATOR, 20_000_000_000),
+                (DAS_WALLET_LOCK_ARGS, 10_000_000_000), // 100 CKB
+            ],
+            vec![
+                (COMMON_INCOME_CREATOR, 20_000_000_000),
+                (DAS_WALLET_LOCK_ARGS, 10_000_000_000), // 100 CKB
+            ],
+        ],
     );
 
-    // outputs
-    push_output_normal_cell(&mut template, 40_000_000_000, COMMON_INCOME_CREATOR);
     // DAS should be free from consolidating fee.
-    push_output_normal_cell(&mut template, 20_000_000_000, DAS_WALLET_LOCK_ARGS);
+    assert_eq!(plan.fee, 0);
 
     test_tx(template.as_json())
 }
@@ -354,83 +414,61 @@ fn test_income_consolidate_big_capacity() {
 
     let capacity_of_10 = 1_000_000_000_000_000_000u64; // 10 billion CKB
 
-    // inputs
-    push_input_income_cell_no_creator(
+    let plan = push_planned_consolidation(
         &mut template,
-        json!({
-            "witness": {
-                "records": [
-                    {
-                        "belong_to": {
-                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
-                            "args": "0x0000000000000000000000000000000000000010"
-                        },
-                        "capacity": capacity_of_10,
-                    },
-                    {
-                        "belong_to": {
-                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
-                            "args": "0x0000000000000000000000000000000000000020"
-                        },
-                        "capacity": 500_000_000,
-                    },
-                ]
-            }
-        }),
+        None,
+        &[
+            vec![
+                ("0x0000000000000000000000000000000000000010", capacity_of_10),
+                ("0x0000000000000000000000000000000000000020", 500_000_000),
+            ],
+            vec![("0x0000000000000000000000000000000000000010", capacity_of_10)],
+        ],
     );
-    push_input_income_cell_no_creator(
-        &mut template,
-        json!({
-            "witness": {
-                "records": [
-                    {
-                        "belong_to": {
-                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
-                            "args": "0x0000000000000000000000000000000000000010"
-                        },
-                        "capacity": capacity_of_10,
-                    },
-                ]
-            }
-        }),
+
+    // The record of 0x0000000000000000000000000000000000000010 is used to pad the IncomeCell capacity.
+    assert!(plan.need_pad());
+    assert_eq!(
+        plan.transfers[0].capacity,
+        (capacity_of_10 + capacity_of_10 - 19_500_000_000u64) / RATE_BASE * (RATE_BASE - CONSOLIDATING_FEE)
     );
+    push_output_normal_cell(&mut template, plan.fee, "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF");
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_income_consolidate_payout_dao_deposit() {
+    let mut template = before();
+    template.push_contract_cell("dao", true);
+
+    // inputs
+    push_input_income_cells_with_payout(&mut template);
 
     // outputs
-    push_output_income_cell_no_creator(
-        &mut template,
+    push_output_normal_cell(&mut template, 40_000_000_000, COMMON_INCOME_CREATOR);
+    template.push_output(
         json!({
-            "witness": {
-                "records": [
-                    {
-                        "belong_to": {
-                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
-                            "args": "0x0000000000000000000000000000000000000010"
-                        },
-                        "capacity": 19_500_000_000u64, // 195 CKB
-                    },
-                    {
-                        "belong_to": {
-                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
-                            "args": "0x0000000000000000000000000000000000000020"
-                        },
-                        "capacity": 500_000_000,
-                    },
-                ]
-            }
+            "capacity": 10_098_000_000u64,
+            "lock": {
+                "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                "args": "0x0000000000000000000000000000000000000010"
+            },
+            "type": {
+                "code_hash": "{{dao}}"
+            },
+            "data": "0x0000000000000000"
         }),
+        None,
     );
+    // 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF can take some from user as their profit.
     push_output_normal_cell(
         &mut template,
-        (capacity_of_10 + capacity_of_10 - 19_500_000_000u64) / RATE_BASE * (RATE_BASE - CONSOLIDATING_FEE),
-        "0x0000000000000000000000000000000000000010",
-    );
-    push_output_normal_cell(
-        &mut template,
-        20_000_000_000_000_000,
+        6_162_000_000,
         "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
     );
 
-    test_tx(template.as_json());
+    test_tx(template.as_json())
 }
 
 #[test]
@@ -988,3 +1026,28 @@ fn challenge_income_consolidate_eip712_cells_without_type_script() {
     //     ],
     // );
 }
+
+#[test]
+fn challenge_income_consolidate_payout_mismatch() {
+    let mut template = before();
+
+    // inputs
+    push_input_income_cells_with_payout(&mut template);
+
+    // outputs
+    push_output_normal_cell(&mut template, 40_000_000_000, COMMON_INCOME_CREATOR);
+    // Simulate transferring the record which should be paid out as a NervosDAO deposit to a normal cell.
+    push_output_normal_cell(
+        &mut template,
+        10_098_000_000,
+        "0x0000000000000000000000000000000000000010",
+    );
+    // 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF can take some from user as their profit.
+    push_output_normal_cell(
+        &mut template,
+        6_162_000_000,
+        "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+    );
+
+    challenge_tx(template.as_json(), Error::IncomeCellPayoutError);
+}
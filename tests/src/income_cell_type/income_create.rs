@@ -39,6 +39,71 @@ fn test_income_create() {
     test_tx(template.as_json())
 }
 
+#[test]
+fn test_income_create_with_payout() {
+    let mut template = before();
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "data": {
+                // The creator has signed the transaction, so they can choose the payout of their record.
+                "payouts": [INCOME_PAYOUT_DAO_DEPOSIT]
+            },
+            "witness": {
+                "records": [
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": COMMON_INCOME_CREATOR
+                        },
+                        "capacity": "20_000_000_000"
+                    },
+                ]
+            }
+        }),
+    );
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_income_create_payout_without_signature() {
+    let mut template = init("create_income");
+
+    // inputs
+    // Simulate creating the IncomeCell for others without their signature.
+    push_input_normal_cell(
+        &mut template,
+        20_000_000_000,
+        "0x0000000000000000000000000000000000000000",
+    );
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "data": {
+                "payouts": [INCOME_PAYOUT_DAO_DEPOSIT]
+            },
+            "witness": {
+                "records": [
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": COMMON_INCOME_CREATOR
+                        },
+                        "capacity": "20_000_000_000"
+                    },
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), Error::IncomeCellPayoutError)
+}
+
 #[test]
 fn challenge_income_create_stored_capacity_error() {
     let mut template = before();
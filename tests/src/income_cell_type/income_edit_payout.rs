@@ -0,0 +1,133 @@
+use crate::util::{
+    accounts::*, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
+};
+use serde_json::{json, Value};
+
+use super::common::init;
+
+const PAYEE: &str = "0x0000000000000000000000000000000000000010";
+const OTHER: &str = "0x0000000000000000000000000000000000000020";
+
+fn gen_record(args: &str, capacity: u64) -> Value {
+    json!({
+        "belong_to": {
+            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+            "args": args
+        },
+        "capacity": capacity
+    })
+}
+
+fn gen_records() -> Value {
+    json!([
+        gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+        gen_record(PAYEE, 10_000_000_000),
+        gen_record(OTHER, 5_000_000_000),
+    ])
+}
+
+fn before() -> TemplateGenerator {
+    let mut template = init("edit_income_payout");
+
+    // inputs
+    push_input_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": gen_records()
+            }
+        }),
+    );
+    // The payee proves their signature with a cell of their own.
+    push_input_normal_cell(&mut template, 10_000_000_000, PAYEE);
+
+    template
+}
+
+#[test]
+fn test_income_edit_payout() {
+    let mut template = before();
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "data": {
+                "payouts": [0, INCOME_PAYOUT_DAO_DEPOSIT, 0]
+            },
+            "witness": {
+                "records": gen_records()
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 10_000_000_000, PAYEE);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_income_edit_payout_of_others() {
+    let mut template = before();
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "data": {
+                // Simulate modifying the payout of others without their signature.
+                "payouts": [0, INCOME_PAYOUT_DAO_DEPOSIT, INCOME_PAYOUT_PLAIN]
+            },
+            "witness": {
+                "records": gen_records()
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 10_000_000_000, PAYEE);
+
+    challenge_tx(template.as_json(), Error::IncomeCellPayoutError)
+}
+
+#[test]
+fn challenge_income_edit_payout_records_modified() {
+    let mut template = before();
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "data": {
+                "payouts": [0, INCOME_PAYOUT_DAO_DEPOSIT, 0]
+            },
+            "witness": {
+                // Simulate modifying the records at the same time.
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                    gen_record(PAYEE, 5_000_000_000),
+                    gen_record(OTHER, 10_000_000_000),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 10_000_000_000, PAYEE);
+
+    challenge_tx(template.as_json(), Error::IncomeCellPayoutError)
+}
+
+#[test]
+fn challenge_income_edit_payout_not_modified() {
+    let mut template = before();
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            // Simulate modifying nothing.
+            "witness": {
+                "records": gen_records()
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 10_000_000_000, PAYEE);
+
+    challenge_tx(template.as_json(), Error::IncomeCellPayoutError)
+}
@@ -0,0 +1,284 @@
+use crate::util::{accounts::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*};
+use serde_json::{json, Value};
+
+use super::common::init;
+
+const INVITER_A: &str = "0x0000000000000000000000000000000000000010";
+const INVITER_B: &str = "0x0000000000000000000000000000000000000020";
+
+fn gen_record(args: &str, capacity: u64) -> Value {
+    json!({
+        "belong_to": {
+            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+            "args": args
+        },
+        "capacity": capacity
+    })
+}
+
+fn before() -> TemplateGenerator {
+    let mut template = init("merge_income");
+
+    // inputs
+    push_input_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                    gen_record(INVITER_A, 1_000_000_000),
+                ]
+            }
+        }),
+    );
+    push_input_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                    gen_record(INVITER_B, 500_000_000),
+                ]
+            }
+        }),
+    );
+    push_input_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                    gen_record(INVITER_A, 300_000_000),
+                ]
+            }
+        }),
+    );
+    // 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF is the keeper who pushed the merge_income transaction.
+    push_input_normal_cell(
+        &mut template,
+        6_100_000_000,
+        "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+    );
+
+    template
+}
+
+#[test]
+fn test_income_merge() {
+    let mut template = before();
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 60_000_000_000),
+                    gen_record(INVITER_A, 1_300_000_000),
+                    gen_record(INVITER_B, 500_000_000),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(
+        &mut template,
+        6_099_990_000,
+        "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+    );
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_income_merge_not_fewer_cells() {
+    let mut template = before();
+
+    // outputs
+    // Simulate the IncomeCells are not merged into fewer cells.
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                    gen_record(INVITER_A, 1_300_000_000),
+                ]
+            }
+        }),
+    );
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                    gen_record(INVITER_B, 500_000_000),
+                ]
+            }
+        }),
+    );
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(
+        &mut template,
+        6_099_990_000,
+        "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+    );
+
+    challenge_tx(template.as_json(), Error::IncomeCellMergeError)
+}
+
+#[test]
+fn challenge_income_merge_capacity_changed() {
+    let mut template = before();
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 60_000_000_000),
+                    // Simulate moving some capacity from one lock to another.
+                    gen_record(INVITER_A, 1_200_000_000),
+                    gen_record(INVITER_B, 600_000_000),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(
+        &mut template,
+        6_099_990_000,
+        "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+    );
+
+    challenge_tx(template.as_json(), Error::IncomeCellMergeError)
+}
+
+#[test]
+fn challenge_income_merge_records_not_compacted() {
+    let mut template = before();
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 60_000_000_000),
+                    gen_record(INVITER_A, 1_000_000_000),
+                    gen_record(INVITER_B, 500_000_000),
+                    // Simulate the records of the same lock are not compacted.
+                    gen_record(INVITER_A, 300_000_000),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(
+        &mut template,
+        6_099_990_000,
+        "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+    );
+
+    challenge_tx(template.as_json(), Error::IncomeCellMergeError)
+}
+
+#[test]
+fn challenge_income_merge_drop_creator() {
+    let mut template = init("merge_income");
+
+    // inputs
+    push_input_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                    gen_record(INVITER_A, 1_000_000_000),
+                ]
+            }
+        }),
+    );
+    push_input_income_cell(
+        &mut template,
+        json!({
+            // Simulate merging the IncomeCells of different creators.
+            "witness": {
+                "creator": {
+                    "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                    "args": INVITER_B
+                },
+                "records": [
+                    gen_record(INVITER_B, 20_000_000_000),
+                    gen_record(INVITER_A, 300_000_000),
+                ]
+            }
+        }),
+    );
+    push_input_normal_cell(
+        &mut template,
+        6_100_000_000,
+        "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+    );
+
+    // outputs
+    // The creator of the second IncomeCell is dropped, so it will no longer be free from the consolidating fee.
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                    gen_record(INVITER_A, 1_300_000_000),
+                    gen_record(INVITER_B, 20_000_000_000),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(
+        &mut template,
+        6_099_990_000,
+        "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+    );
+
+    challenge_tx(template.as_json(), Error::IncomeCellMergeError)
+}
+
+#[test]
+fn challenge_income_merge_creator_changed() {
+    let mut template = before();
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                // Simulate the creator of the merged IncomeCell is not the creator of the IncomeCells in inputs.
+                "creator": {
+                    "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                    "args": INVITER_A
+                },
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 60_000_000_000),
+                    gen_record(INVITER_A, 1_300_000_000),
+                    gen_record(INVITER_B, 500_000_000),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(
+        &mut template,
+        6_099_990_000,
+        "0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+    );
+
+    challenge_tx(template.as_json(), Error::IncomeCellMergeError)
+}
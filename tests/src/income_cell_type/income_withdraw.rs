@@ -0,0 +1,218 @@
+use crate::util::{
+    accounts::*, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
+};
+use serde_json::{json, Value};
+
+use super::common::init;
+
+const WITHDRAWER: &str = "0x0000000000000000000000000000000000000010";
+const OTHER: &str = "0x0000000000000000000000000000000000000020";
+
+fn gen_record(args: &str, capacity: u64) -> Value {
+    json!({
+        "belong_to": {
+            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+            "args": args
+        },
+        "capacity": capacity
+    })
+}
+
+fn before() -> TemplateGenerator {
+    let mut template = init("withdraw_income");
+
+    // inputs
+    push_input_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                    gen_record(WITHDRAWER, 10_000_000_000),
+                    gen_record(OTHER, 5_000_000_000),
+                ]
+            }
+        }),
+    );
+    // The withdrawer proves their signature with a cell of their own.
+    push_input_normal_cell(&mut template, 10_000_000_000, WITHDRAWER);
+
+    template
+}
+
+#[test]
+fn test_income_withdraw() {
+    let mut template = before();
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                    gen_record(OTHER, 5_000_000_000),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 20_000_000_000, WITHDRAWER);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_income_withdraw_without_signature() {
+    let mut template = init("withdraw_income");
+
+    // inputs
+    push_input_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                    gen_record(WITHDRAWER, 10_000_000_000),
+                ]
+            }
+        }),
+    );
+    // Simulate there is no cell of the withdrawer in inputs.
+    push_input_normal_cell(&mut template, 10_000_000_000, OTHER);
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 10_000_000_000, WITHDRAWER);
+
+    challenge_tx(template.as_json(), Error::IncomeCellWithdrawError)
+}
+
+#[test]
+fn challenge_income_withdraw_records_of_others() {
+    let mut template = before();
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                // Simulate withdrawing the records of others at the same time.
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 25_000_000_000, WITHDRAWER);
+
+    challenge_tx(template.as_json(), Error::IncomeCellWithdrawError)
+}
+
+#[test]
+fn challenge_income_withdraw_records_modified() {
+    let mut template = before();
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                    // Simulate the record of others is modified.
+                    gen_record(OTHER, 4_000_000_000),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 21_000_000_000, WITHDRAWER);
+
+    challenge_tx(template.as_json(), Error::IncomeCellWithdrawError)
+}
+
+#[test]
+fn challenge_income_withdraw_capacity_less_than_basic() {
+    let mut template = init("withdraw_income");
+
+    // inputs
+    push_input_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "records": [
+                    gen_record(WITHDRAWER, 20_000_000_000),
+                    gen_record(OTHER, 5_000_000_000),
+                ]
+            }
+        }),
+    );
+    push_input_normal_cell(&mut template, 10_000_000_000, WITHDRAWER);
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "witness": {
+                // Simulate the IncomeCell is left with less than the basic capacity.
+                "records": [
+                    gen_record(OTHER, 5_000_000_000),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 30_000_000_000, WITHDRAWER);
+
+    challenge_tx(template.as_json(), Error::IncomeCellCapacityError)
+}
+
+#[test]
+fn challenge_income_withdraw_payouts_modified() {
+    let mut template = init("withdraw_income");
+
+    // inputs
+    push_input_income_cell(
+        &mut template,
+        json!({
+            "data": {
+                "payouts": [0, 0, INCOME_PAYOUT_DAO_DEPOSIT]
+            },
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                    gen_record(WITHDRAWER, 10_000_000_000),
+                    gen_record(OTHER, 5_000_000_000),
+                ]
+            }
+        }),
+    );
+    push_input_normal_cell(&mut template, 10_000_000_000, WITHDRAWER);
+
+    // outputs
+    push_output_income_cell(
+        &mut template,
+        json!({
+            "data": {
+                // Simulate the payout of others is modified.
+                "payouts": [0, 0]
+            },
+            "witness": {
+                "records": [
+                    gen_record(COMMON_INCOME_CREATOR, 20_000_000_000),
+                    gen_record(OTHER, 5_000_000_000),
+                ]
+            }
+        }),
+    );
+    push_output_normal_cell(&mut template, 20_000_000_000, WITHDRAWER);
+
+    challenge_tx(template.as_json(), Error::IncomeCellPayoutError)
+}
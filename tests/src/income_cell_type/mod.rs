@@ -3,3 +3,6 @@ mod common;
 
 mod income_consolidate;
 mod income_create;
+mod income_edit_payout;
+mod income_merge;
+mod income_withdraw;
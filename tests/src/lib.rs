@@ -1,5 +1,7 @@
 #![allow(dead_code)]
 
+extern crate alloc;
+
 use std::env;
 use std::fs;
 use std::path::PathBuf;
@@ -0,0 +1,259 @@
+use super::common::*;
+use crate::util::{
+    accounts::*, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
+};
+use das_types_std::constants::*;
+use serde_json::json;
+
+const COUNTER_PRICE: u64 = 250_000_000_000;
+const COUNTER_OFFER_CAPACITY: u64 = OFFER_BASIC_CAPACITY + OFFER_PREPARED_FEE_CAPACITY;
+const OFFER_CELL_CAPACITY: u64 = PRICE + OFFER_PREPARED_FEE_CAPACITY;
+const BUYER_TOP_UP: u64 = 60_000_000_000;
+
+fn push_simple_output_income_cell(template: &mut TemplateGenerator) {
+    push_output_income_cell(
+        template,
+        json!({
+            "witness": {
+                "records": [
+                    // It is a conversion in this transaction that the first record always belong to the creator of the IncomeCell.
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": COMMON_INCOME_CREATOR
+                        },
+                        "capacity": "20_000_000_000"
+                    },
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-das-lock}}",
+                            "args": gen_das_lock_args(INVITER, None)
+                        },
+                        "capacity": "2_500_000_000"
+                    },
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-das-lock}}",
+                            "args": gen_das_lock_args(CHANNEL, None)
+                        },
+                        "capacity": "2_500_000_000"
+                    },
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": DAS_WALLET_LOCK_ARGS
+                        },
+                        "capacity": "2_500_000_000"
+                    }
+                ]
+            }
+        }),
+    );
+}
+
+fn push_common_outputs(template: &mut TemplateGenerator) {
+    push_output_account_cell(
+        template,
+        json!({
+            "lock": {
+                "owner_lock_args": BUYER,
+                "manager_lock_args": BUYER
+            },
+            "data": {
+                "account": ACCOUNT,
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    push_simple_output_income_cell(template);
+    // The seller get their profit and the capacity of the counter offer back.
+    push_output_balance_cell(template, 242_500_000_000 + COUNTER_OFFER_CAPACITY, SELLER);
+    push_output_balance_cell(
+        template,
+        OFFER_CELL_CAPACITY + BUYER_TOP_UP - COUNTER_PRICE - SECONDARY_MARKET_COMMON_FEE,
+        BUYER,
+    );
+}
+
+fn push_input_simple_account_cell(template: &mut TemplateGenerator) {
+    push_input_account_cell_with_status(template, AccountStatus::Selling);
+}
+
+fn push_input_account_cell_with_status(template: &mut TemplateGenerator, status: AccountStatus) {
+    push_input_account_cell(
+        template,
+        json!({
+            "lock": {
+                "owner_lock_args": SELLER,
+                "manager_lock_args": SELLER
+            },
+            "data": {
+                "account": ACCOUNT,
+            },
+            "witness": {
+                "account": ACCOUNT,
+                // The AccountCell has been locked by the counter offer.
+                "status": (status as u8)
+            }
+        }),
+    );
+}
+
+fn push_input_simple_offer_cell(template: &mut TemplateGenerator) {
+    push_input_offer_cell(
+        template,
+        json!({
+            "capacity": OFFER_CELL_CAPACITY,
+            "witness": {
+                "account": ACCOUNT,
+                "price": PRICE,
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+}
+
+fn push_input_simple_counter_offer(template: &mut TemplateGenerator, args: String) {
+    push_input_offer_cell(
+        template,
+        json!({
+            "capacity": COUNTER_OFFER_CAPACITY,
+            "lock": {
+                "owner_lock_args": SELLER,
+                "manager_lock_args": SELLER
+            },
+            "data": {
+                "collection": {
+                    "type": OFFER_COLLECTION_TYPE_COUNTER,
+                    "args": args
+                }
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": COUNTER_PRICE,
+                "message": "Make it a bit higher."
+            }
+        }),
+    );
+}
+
+fn before_each() -> TemplateGenerator {
+    let mut template = init_with_timestamp("accept_counter_offer");
+
+    // inputs
+    push_input_simple_account_cell(&mut template);
+    // The counter offer is made for the OfferCell at inputs[2].
+    push_input_simple_counter_offer(&mut template, gen_input_out_point_hex(2));
+    push_input_simple_offer_cell(&mut template);
+    push_input_balance_cell(&mut template, BUYER_TOP_UP, BUYER);
+
+    // Transaction builder's BalanceCell
+    push_input_balance_cell(
+        &mut template,
+        100_000_000_000,
+        "0x050000000000000000000000000000000000003333",
+    );
+
+    template
+}
+
+#[test]
+fn test_offer_accept_counter_offer() {
+    let mut template = before_each();
+
+    // outputs
+    push_common_outputs(&mut template);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_offer_accept_counter_offer_out_point_mismatch() {
+    let mut template = init_with_timestamp("accept_counter_offer");
+
+    // inputs
+    push_input_simple_account_cell(&mut template);
+    // Simulate the counter offer is made for another OfferCell.
+    push_input_simple_counter_offer(&mut template, gen_input_out_point_hex(3));
+    push_input_simple_offer_cell(&mut template);
+    push_input_balance_cell(&mut template, BUYER_TOP_UP, BUYER);
+
+    // outputs
+    push_common_outputs(&mut template);
+
+    challenge_tx(template.as_json(), Error::OfferCellCounterOfferError);
+}
+
+#[test]
+fn challenge_offer_accept_counter_offer_not_made_by_seller() {
+    let mut template = init_with_timestamp("accept_counter_offer");
+
+    // inputs
+    push_input_simple_account_cell(&mut template);
+    // Simulate the counter offer is made by someone other than the owner of the AccountCell.
+    push_input_offer_cell(
+        &mut template,
+        json!({
+            "capacity": COUNTER_OFFER_CAPACITY,
+            "lock": {
+                "owner_lock_args": "0x050000000000000000000000000000000000005555",
+                "manager_lock_args": "0x050000000000000000000000000000000000005555"
+            },
+            "data": {
+                "collection": {
+                    "type": OFFER_COLLECTION_TYPE_COUNTER,
+                    "args": gen_input_out_point_hex(2)
+                }
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": COUNTER_PRICE,
+                "message": "Make it a bit higher."
+            }
+        }),
+    );
+    push_input_simple_offer_cell(&mut template);
+    push_input_balance_cell(&mut template, BUYER_TOP_UP, BUYER);
+
+    // outputs
+    push_common_outputs(&mut template);
+
+    challenge_tx(template.as_json(), Error::OfferCellCounterOfferError);
+}
+
+#[test]
+fn challenge_offer_accept_counter_offer_pay_not_enough() {
+    let mut template = init_with_timestamp("accept_counter_offer");
+
+    // inputs
+    push_input_simple_account_cell(&mut template);
+    push_input_simple_counter_offer(&mut template, gen_input_out_point_hex(2));
+    push_input_simple_offer_cell(&mut template);
+    // Simulate the buyer do not top up the OfferCell.
+
+    // outputs
+    push_common_outputs(&mut template);
+
+    challenge_tx(template.as_json(), Error::OfferCellCapacityError);
+}
+
+#[test]
+fn challenge_offer_accept_counter_offer_account_cell_not_locked() {
+    let mut template = init_with_timestamp("accept_counter_offer");
+
+    // inputs
+    // Simulate the AccountCell is not locked by the counter offer.
+    push_input_account_cell_with_status(&mut template, AccountStatus::Normal);
+    push_input_simple_counter_offer(&mut template, gen_input_out_point_hex(2));
+    push_input_simple_offer_cell(&mut template);
+    push_input_balance_cell(&mut template, BUYER_TOP_UP, BUYER);
+
+    // outputs
+    push_common_outputs(&mut template);
+
+    challenge_tx(template.as_json(), Error::AccountCellStatusLocked);
+}
@@ -650,3 +650,175 @@ fn challenge_offer_accept_offer_others_profit_wrong() {
 
     challenge_tx(template.as_json(), Error::IncomeCellProfitMismatch);
 }
+
+fn before_each_with_collection(params: Vec<u8>, collection_type: u8, collection_args: String) -> TemplateGenerator {
+    let mut template = init_with_timestamp_and_params("accept_offer", params);
+
+    // inputs
+    push_input_offer_cell(
+        &mut template,
+        json!({
+            "capacity": "200_100_000_000",
+            "data": {
+                "collection": {
+                    "type": collection_type,
+                    "args": collection_args
+                }
+            },
+            "witness": {
+                "account": "",
+                "price": "200_000_000_000",
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": SELLER,
+                "manager_lock_args": "0x050000000000000000000000000000000000005555"
+            },
+            "data": {
+                "account": ACCOUNT,
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+
+    // Transaction builder's BalanceCell
+    push_input_balance_cell(
+        &mut template,
+        100_000_000_000,
+        "0x050000000000000000000000000000000000003333",
+    );
+
+    template
+}
+
+#[test]
+fn test_offer_accept_offer_on_pattern() {
+    let mut template = before_each_with_collection(
+        vec![0],
+        OFFER_COLLECTION_TYPE_PATTERN,
+        format!("0x{:02x}{:02x}", CharSetType::En as u8, ACCOUNT.len() - 4),
+    );
+
+    // outputs
+    push_common_outputs(&mut template);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_offer_accept_offer_on_pattern_length_mismatch() {
+    // Simulate accepting the offer with an account which is longer than the OfferCell required.
+    let mut template = before_each_with_collection(
+        vec![0],
+        OFFER_COLLECTION_TYPE_PATTERN,
+        format!("0x{:02x}{:02x}", OFFER_COLLECTION_ANY_CHAR_SET, ACCOUNT.len() - 5),
+    );
+
+    // outputs
+    push_common_outputs(&mut template);
+
+    challenge_tx(template.as_json(), Error::OfferCellAccountMismatch);
+}
+
+#[test]
+fn challenge_offer_accept_offer_on_pattern_char_set_mismatch() {
+    // Simulate accepting the offer with an account which contains characters not in the required char set.
+    let mut template = before_each_with_collection(
+        vec![0],
+        OFFER_COLLECTION_TYPE_PATTERN,
+        format!("0x{:02x}{:02x}", CharSetType::Digit as u8, OFFER_COLLECTION_ANY_LENGTH),
+    );
+
+    // outputs
+    push_common_outputs(&mut template);
+
+    challenge_tx(template.as_json(), Error::OfferCellAccountMismatch);
+}
+
+#[test]
+fn test_offer_accept_offer_on_merkle_root() {
+    let (root, proof) = gen_merkle_root_and_proof(ACCOUNT, "yyyyy.bit");
+    let mut template = before_each_with_collection(
+        [proof, vec![0]].concat(),
+        OFFER_COLLECTION_TYPE_MERKLE_ROOT,
+        format!("0x{}", util::bytes_to_hex(&root)),
+    );
+
+    // outputs
+    push_common_outputs(&mut template);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_offer_accept_offer_on_merkle_root_invalid_proof() {
+    let (root, _) = gen_merkle_root_and_proof(ACCOUNT, "yyyyy.bit");
+    // Simulate providing a proof which does not belong to the tree.
+    let (_, proof) = gen_merkle_root_and_proof(ACCOUNT, "zzzzz.bit");
+    let mut template = before_each_with_collection(
+        [proof, vec![0]].concat(),
+        OFFER_COLLECTION_TYPE_MERKLE_ROOT,
+        format!("0x{}", util::bytes_to_hex(&root)),
+    );
+
+    // outputs
+    push_common_outputs(&mut template);
+
+    challenge_tx(template.as_json(), Error::OfferCellAccountMismatch);
+}
+
+#[test]
+fn challenge_offer_accept_offer_expired() {
+    let mut template = init_with_timestamp("accept_offer");
+
+    // inputs
+    push_input_offer_cell(
+        &mut template,
+        json!({
+            "capacity": "200_100_000_000",
+            "data": {
+                // Simulate accepting an OfferCell which has been expired.
+                "expired_at": TIMESTAMP - 1
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": "200_000_000_000",
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+    push_input_account_cell(
+        &mut template,
+        json!({
+            "lock": {
+                "owner_lock_args": SELLER,
+                "manager_lock_args": "0x050000000000000000000000000000000000005555"
+            },
+            "data": {
+                "account": ACCOUNT,
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+    push_input_balance_cell(
+        &mut template,
+        100_000_000_000,
+        "0x050000000000000000000000000000000000003333",
+    );
+
+    // outputs
+    push_common_outputs(&mut template);
+
+    challenge_tx(template.as_json(), Error::OfferCellExpirationError);
+}
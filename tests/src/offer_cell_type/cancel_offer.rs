@@ -1,7 +1,12 @@
 use super::common::*;
-use crate::util::{accounts::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*};
+use crate::util::{
+    accounts::*, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
+};
+use das_types_std::constants::*;
 use serde_json::json;
 
+const COUNTER_OFFER_CAPACITY: u64 = OFFER_BASIC_CAPACITY + OFFER_PREPARED_FEE_CAPACITY;
+
 fn before_each() -> TemplateGenerator {
     let mut template = init("cancel_offer");
 
@@ -100,3 +105,123 @@ fn challenge_offer_cancel_offer_change_owner() {
 
     challenge_tx(template.as_json(), Error::ChangeError);
 }
+
+fn push_input_account_cell_locked_by_counter_offer(template: &mut TemplateGenerator) {
+    push_input_account_cell(
+        template,
+        json!({
+            "lock": {
+                "owner_lock_args": SELLER,
+                "manager_lock_args": SELLER
+            },
+            "data": {
+                "account": ACCOUNT,
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "status": (AccountStatus::Selling as u8)
+            }
+        }),
+    );
+}
+
+fn push_input_simple_counter_offer(template: &mut TemplateGenerator) {
+    push_input_offer_cell(
+        template,
+        json!({
+            "capacity": COUNTER_OFFER_CAPACITY,
+            "lock": {
+                "owner_lock_args": SELLER,
+                "manager_lock_args": SELLER
+            },
+            "data": {
+                "collection": {
+                    "type": OFFER_COLLECTION_TYPE_COUNTER,
+                    "args": gen_input_out_point_hex(2)
+                }
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": "250_000_000_000",
+                "message": "Make it a bit higher."
+            }
+        }),
+    );
+}
+
+fn push_output_account_cell_with_status(template: &mut TemplateGenerator, status: AccountStatus) {
+    push_output_account_cell(
+        template,
+        json!({
+            "lock": {
+                "owner_lock_args": SELLER,
+                "manager_lock_args": SELLER
+            },
+            "data": {
+                "account": ACCOUNT,
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "status": (status as u8)
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_offer_cancel_counter_offer() {
+    let mut template = init_with_timestamp("cancel_offer");
+
+    // inputs
+    push_input_account_cell_locked_by_counter_offer(&mut template);
+    push_input_simple_counter_offer(&mut template);
+
+    // outputs
+    // The AccountCell is unlocked when the counter offer is canceled.
+    push_output_account_cell_with_status(&mut template, AccountStatus::Normal);
+    push_output_balance_cell(
+        &mut template,
+        COUNTER_OFFER_CAPACITY - SECONDARY_MARKET_COMMON_FEE,
+        SELLER,
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_offer_cancel_counter_offer_without_account_cell() {
+    let mut template = init_with_timestamp("cancel_offer");
+
+    // inputs
+    // Simulate canceling the counter offer without unlocking the AccountCell.
+    push_input_simple_counter_offer(&mut template);
+
+    // outputs
+    push_output_balance_cell(
+        &mut template,
+        COUNTER_OFFER_CAPACITY - SECONDARY_MARKET_COMMON_FEE,
+        SELLER,
+    );
+
+    challenge_tx(template.as_json(), Error::OfferCellCounterOfferError);
+}
+
+#[test]
+fn challenge_offer_cancel_counter_offer_account_cell_still_locked() {
+    let mut template = init_with_timestamp("cancel_offer");
+
+    // inputs
+    push_input_account_cell_locked_by_counter_offer(&mut template);
+    push_input_simple_counter_offer(&mut template);
+
+    // outputs
+    // Simulate the AccountCell is still in the Selling status.
+    push_output_account_cell_with_status(&mut template, AccountStatus::Selling);
+    push_output_balance_cell(
+        &mut template,
+        COUNTER_OFFER_CAPACITY - SECONDARY_MARKET_COMMON_FEE,
+        SELLER,
+    );
+
+    challenge_tx(template.as_json(), Error::AccountCellStatusLocked);
+}
@@ -1,11 +1,16 @@
 use crate::util::{self, accounts::*, constants::*, template_generator::*};
+use ckb_testtool::{ckb_hash::blake2b_256, ckb_types::prelude::Entity};
 use das_types_std::{constants::*, packed::*};
 use serde_json::{json, Value};
 
 pub const PRICE: u64 = 200_000_000_000;
 
 pub fn init(action: &str) -> TemplateGenerator {
-    let mut template = TemplateGenerator::new(action, Some(Bytes::from(vec![0])));
+    init_with_params(action, vec![0])
+}
+
+pub fn init_with_params(action: &str, params: Vec<u8>) -> TemplateGenerator {
+    let mut template = TemplateGenerator::new(action, Some(Bytes::from(params)));
 
     template.push_contract_cell("always_success", true);
     template.push_contract_cell("fake-das-lock", true);
@@ -20,7 +25,11 @@ pub fn init(action: &str) -> TemplateGenerator {
 }
 
 pub fn init_with_timestamp(action: &str) -> TemplateGenerator {
-    let mut template = init(action);
+    init_with_timestamp_and_params(action, vec![0])
+}
+
+pub fn init_with_timestamp_and_params(action: &str, params: Vec<u8>) -> TemplateGenerator {
+    let mut template = init_with_params(action, params);
 
     template.push_contract_cell("account-cell-type", false);
     template.push_contract_cell("income-cell-type", false);
@@ -64,6 +73,35 @@ pub fn push_input_offer_cell(template: &mut TemplateGenerator, cell_partial: Val
     template.push_das_lock_witness("0000000000000000000000000000000000000000000000000000000000000000");
 }
 
+pub fn push_dep_offer_cell(template: &mut TemplateGenerator, cell_partial: Value) -> usize {
+    let mut cell = json!({
+        "capacity": 0,
+        "lock": {
+            "owner_lock_args": BUYER,
+            "manager_lock_args": BUYER,
+        },
+        "type": {
+            "code_hash": "{{offer-cell-type}}"
+        },
+        "witness": {
+            "account": ACCOUNT,
+            "price": "200_000_000_000",
+            "message": "Take my money.🍀",
+            "inviter_lock": {
+                "code_hash": "{{fake-das-lock}}",
+                "args": gen_das_lock_args(INVITER, None)
+            },
+            "channel_lock": {
+                "code_hash": "{{fake-das-lock}}",
+                "args": gen_das_lock_args(CHANNEL, None)
+            }
+        }
+    });
+    util::merge_json(&mut cell, cell_partial);
+
+    template.push_dep(cell, None)
+}
+
 pub fn push_output_offer_cell(template: &mut TemplateGenerator, cell_partial: Value) {
     let mut cell = json!({
         "capacity": 0,
@@ -92,3 +130,27 @@ pub fn push_output_offer_cell(template: &mut TemplateGenerator, cell_partial: Va
 
     template.push_output(cell, None);
 }
+
+/// Generate a merkle tree of two accounts, return the root and the proof of the first account.
+pub fn gen_merkle_root_and_proof(first: &str, second: &str) -> (Vec<u8>, Vec<u8>) {
+    let first_leaf = blake2b_256(util::account_to_id(first));
+    let second_leaf = blake2b_256(util::account_to_id(second));
+
+    let root = if first_leaf <= second_leaf {
+        blake2b_256([first_leaf, second_leaf].concat())
+    } else {
+        blake2b_256([second_leaf, first_leaf].concat())
+    };
+
+    (root.to_vec(), second_leaf.to_vec())
+}
+
+/// Generate the out point of inputs[index] in hex, it is used as the args of counter offers.
+pub fn gen_input_out_point_hex(index: usize) -> String {
+    util::bytes_to_hex(util::mock_out_point(index + 1_000_000).as_slice())
+}
+
+/// Generate the out point of cell_deps[index] in hex, it is used as the args of counter offers.
+pub fn gen_dep_out_point_hex(index: usize) -> String {
+    util::bytes_to_hex(util::mock_out_point(index).as_slice())
+}
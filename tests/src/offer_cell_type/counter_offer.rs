@@ -0,0 +1,292 @@
+use super::common::*;
+use crate::util::{
+    accounts::*, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
+};
+use das_types_std::constants::*;
+use serde_json::{json, Value};
+
+pub const COUNTER_PRICE: u64 = 250_000_000_000;
+pub const COUNTER_OFFER_CAPACITY: u64 = OFFER_BASIC_CAPACITY + OFFER_PREPARED_FEE_CAPACITY;
+pub const COUNTER_OFFER_COST: u64 = COUNTER_OFFER_CAPACITY + SECONDARY_MARKET_COMMON_FEE;
+
+fn push_dep_simple_offer_cell(template: &mut TemplateGenerator, account: &str) -> usize {
+    push_dep_offer_cell(
+        template,
+        json!({
+            "capacity": PRICE + OFFER_PREPARED_FEE_CAPACITY,
+            "witness": {
+                "account": account,
+                "price": PRICE,
+                "message": "Take my money.🍀"
+            }
+        }),
+    )
+}
+
+fn push_input_simple_account_cell(template: &mut TemplateGenerator) {
+    push_input_account_cell(
+        template,
+        json!({
+            "lock": {
+                "owner_lock_args": SELLER,
+                "manager_lock_args": SELLER
+            },
+            "data": {
+                "account": ACCOUNT,
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+}
+
+fn push_output_simple_counter_offer(template: &mut TemplateGenerator, cell_partial: Value) {
+    let mut cell = json!({
+        "capacity": COUNTER_OFFER_CAPACITY,
+        "lock": {
+            "owner_lock_args": SELLER,
+            "manager_lock_args": SELLER
+        },
+        "data": {
+            "collection": {
+                "type": OFFER_COLLECTION_TYPE_COUNTER
+            }
+        },
+        "witness": {
+            "account": ACCOUNT,
+            "price": COUNTER_PRICE,
+            "message": "Make it a bit higher."
+        }
+    });
+    crate::util::merge_json(&mut cell, cell_partial);
+
+    push_output_offer_cell(template, cell);
+}
+
+fn before_each() -> (TemplateGenerator, u64, String) {
+    let mut template = init_with_timestamp("counter_offer");
+
+    // cell_deps
+    let offer_cell = push_dep_simple_offer_cell(&mut template, ACCOUNT);
+
+    // inputs
+    let total_input = 100_000_000_000;
+    push_input_simple_account_cell(&mut template);
+    push_input_balance_cell(&mut template, total_input, SELLER);
+
+    (template, total_input, gen_dep_out_point_hex(offer_cell))
+}
+
+fn push_output_simple_account_cell(template: &mut TemplateGenerator, status: AccountStatus) {
+    push_output_account_cell(
+        template,
+        json!({
+            "lock": {
+                "owner_lock_args": SELLER,
+                "manager_lock_args": SELLER
+            },
+            "data": {
+                "account": ACCOUNT,
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "status": (status as u8)
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_offer_counter_offer() {
+    let (mut template, total_input, out_point) = before_each();
+
+    // outputs
+    // The AccountCell is locked in the Selling status until the counter offer is accepted or canceled.
+    push_output_simple_account_cell(&mut template, AccountStatus::Selling);
+    push_output_simple_counter_offer(
+        &mut template,
+        json!({
+            "data": {
+                "collection": {
+                    // The out point of the OfferCell which the seller is countering.
+                    "args": out_point
+                }
+            }
+        }),
+    );
+    push_output_balance_cell(&mut template, total_input - COUNTER_OFFER_COST, SELLER);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_offer_counter_offer_invalid_out_point() {
+    let (mut template, total_input, _) = before_each();
+
+    // outputs
+    push_output_simple_account_cell(&mut template, AccountStatus::Selling);
+    push_output_simple_counter_offer(
+        &mut template,
+        json!({
+            "data": {
+                "collection": {
+                    // Simulate the args is not a valid out point.
+                    "args": "0x0000000000000000"
+                }
+            }
+        }),
+    );
+    push_output_balance_cell(&mut template, total_input - COUNTER_OFFER_COST, SELLER);
+
+    challenge_tx(template.as_json(), Error::OfferCellCounterOfferError);
+}
+
+#[test]
+fn challenge_offer_counter_offer_offer_cell_not_found() {
+    let (mut template, total_input, _) = before_each();
+
+    // outputs
+    push_output_simple_account_cell(&mut template, AccountStatus::Selling);
+    push_output_simple_counter_offer(
+        &mut template,
+        json!({
+            "data": {
+                "collection": {
+                    // Simulate the OfferCell being countered is not in cell_deps.
+                    "args": gen_input_out_point_hex(0)
+                }
+            }
+        }),
+    );
+    push_output_balance_cell(&mut template, total_input - COUNTER_OFFER_COST, SELLER);
+
+    challenge_tx(template.as_json(), Error::OfferCellCounterOfferError);
+}
+
+#[test]
+fn challenge_offer_counter_offer_offer_cell_on_other_account() {
+    let mut template = init_with_timestamp("counter_offer");
+
+    // cell_deps
+    // Simulate the OfferCell being countered is bidding on another account.
+    let offer_cell = push_dep_simple_offer_cell(&mut template, "zzzzz.bit");
+
+    // inputs
+    let total_input = 100_000_000_000;
+    push_input_simple_account_cell(&mut template);
+    push_input_balance_cell(&mut template, total_input, SELLER);
+
+    // outputs
+    push_output_simple_account_cell(&mut template, AccountStatus::Selling);
+    push_output_simple_counter_offer(
+        &mut template,
+        json!({
+            "data": {
+                "collection": {
+                    "args": gen_dep_out_point_hex(offer_cell)
+                }
+            }
+        }),
+    );
+    push_output_balance_cell(&mut template, total_input - COUNTER_OFFER_COST, SELLER);
+
+    challenge_tx(template.as_json(), Error::OfferCellAccountMismatch);
+}
+
+#[test]
+fn challenge_offer_counter_offer_account_mismatch() {
+    let (mut template, total_input, out_point) = before_each();
+
+    // outputs
+    push_output_simple_account_cell(&mut template, AccountStatus::Selling);
+    push_output_simple_counter_offer(
+        &mut template,
+        json!({
+            "data": {
+                "collection": {
+                    "args": out_point
+                }
+            },
+            "witness": {
+                // Simulate the counter offer is made for an account which is not owned by the sender.
+                "account": "zzzzz.bit"
+            }
+        }),
+    );
+    push_output_balance_cell(&mut template, total_input - COUNTER_OFFER_COST, SELLER);
+
+    challenge_tx(template.as_json(), Error::OfferCellAccountMismatch);
+}
+
+#[test]
+fn challenge_offer_counter_offer_account_cell_not_locked() {
+    let (mut template, total_input, out_point) = before_each();
+
+    // outputs
+    // Simulate the AccountCell is not locked by the counter offer.
+    push_output_simple_account_cell(&mut template, AccountStatus::Normal);
+    push_output_simple_counter_offer(
+        &mut template,
+        json!({
+            "data": {
+                "collection": {
+                    "args": out_point
+                }
+            }
+        }),
+    );
+    push_output_balance_cell(&mut template, total_input - COUNTER_OFFER_COST, SELLER);
+
+    challenge_tx(template.as_json(), Error::AccountCellStatusLocked);
+}
+
+#[test]
+fn challenge_offer_counter_offer_change_owner() {
+    let (mut template, total_input, out_point) = before_each();
+
+    // outputs
+    push_output_simple_account_cell(&mut template, AccountStatus::Selling);
+    push_output_simple_counter_offer(
+        &mut template,
+        json!({
+            // Simulate the counter offer is not owned by the owner of the AccountCell.
+            "lock": {
+                "owner_lock_args": BUYER,
+                "manager_lock_args": BUYER
+            },
+            "data": {
+                "collection": {
+                    "args": out_point
+                }
+            }
+        }),
+    );
+    push_output_balance_cell(&mut template, total_input - COUNTER_OFFER_COST, SELLER);
+
+    challenge_tx(template.as_json(), Error::OfferCellLockError);
+}
+
+#[test]
+fn challenge_offer_counter_offer_capacity_mismatched() {
+    let (mut template, total_input, out_point) = before_each();
+
+    // outputs
+    push_output_simple_account_cell(&mut template, AccountStatus::Selling);
+    push_output_simple_counter_offer(
+        &mut template,
+        json!({
+            // Simulate the counter offer locks less than the basic capacity and the prepared fee.
+            "capacity": COUNTER_OFFER_CAPACITY - 1,
+            "data": {
+                "collection": {
+                    "args": out_point
+                }
+            }
+        }),
+    );
+    push_output_balance_cell(&mut template, total_input - COUNTER_OFFER_COST + 1, SELLER);
+
+    challenge_tx(template.as_json(), Error::OfferCellCapacityError);
+}
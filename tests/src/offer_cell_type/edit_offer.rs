@@ -1,5 +1,7 @@
 use super::common::*;
-use crate::util::{accounts::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*};
+use crate::util::{
+    accounts::*, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
+};
 use das_types_std::constants::Source;
 use serde_json::json;
 
@@ -475,3 +477,158 @@ fn challenge_offer_edit_offer_change_owner() {
 
     challenge_tx(template.as_json(), Error::ChangeError);
 }
+
+#[test]
+fn test_offer_edit_offer_extend_expired_at() {
+    let mut template = before_each();
+    template.push_oracle_cell(1, OracleCellType::Time, TIMESTAMP);
+
+    // inputs
+    push_input_offer_cell(
+        &mut template,
+        json!({
+            "capacity": "200_100_000_000",
+            "data": {
+                "expired_at": TIMESTAMP + DAY_SEC
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": "200_000_000_000",
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    // outputs
+    push_output_offer_cell(
+        &mut template,
+        json!({
+            "capacity": "200_099_990_000",
+            "data": {
+                "expired_at": TIMESTAMP + DAY_SEC * 7
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": "200_000_000_000",
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_offer_edit_offer_expired_at_in_the_past() {
+    let mut template = before_each();
+    template.push_oracle_cell(1, OracleCellType::Time, TIMESTAMP);
+
+    // inputs
+    push_input_offer_cell(
+        &mut template,
+        json!({
+            "capacity": "200_100_000_000",
+            "data": {
+                "expired_at": TIMESTAMP + DAY_SEC
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": "200_000_000_000",
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    // outputs
+    push_output_offer_cell(
+        &mut template,
+        json!({
+            "capacity": "200_099_990_000",
+            "data": {
+                // Simulate changing the expired_at to the past.
+                "expired_at": TIMESTAMP - 1
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": "200_000_000_000",
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), Error::OfferCellExpirationError);
+}
+
+#[test]
+fn test_offer_edit_offer_add_expired_at_to_legacy_offer() {
+    let mut template = before_each();
+    template.push_oracle_cell(1, OracleCellType::Time, TIMESTAMP);
+
+    // inputs
+    push_input_offer_cell(
+        &mut template,
+        json!({
+            "capacity": "200_100_000_000",
+            "witness": {
+                "account": ACCOUNT,
+                "price": "200_000_000_000",
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    // outputs
+    push_output_offer_cell(
+        &mut template,
+        json!({
+            "capacity": "200_099_990_000",
+            "data": {
+                "expired_at": TIMESTAMP + DAY_SEC * 7
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": "200_000_000_000",
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_offer_edit_offer_remove_expired_at() {
+    let mut template = before_each();
+    template.push_oracle_cell(1, OracleCellType::Time, TIMESTAMP);
+
+    // inputs
+    push_input_offer_cell(
+        &mut template,
+        json!({
+            "capacity": "200_100_000_000",
+            "data": {
+                "expired_at": TIMESTAMP + DAY_SEC
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": "200_000_000_000",
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    // outputs
+    push_output_offer_cell(
+        &mut template,
+        json!({
+            "capacity": "200_099_990_000",
+            "witness": {
+                "account": ACCOUNT,
+                "price": "200_000_000_000",
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    test_tx(template.as_json());
+}
@@ -2,7 +2,7 @@ use super::common::*;
 use crate::util::{
     accounts::*, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
 };
-use das_types_std::constants::Source;
+use das_types_std::constants::{CharSetType, Source};
 use serde_json::json;
 
 pub const MAKE_OFFER_COST: u64 = PRICE + OFFER_PREPARED_FEE_CAPACITY + SECONDARY_MARKET_COMMON_FEE;
@@ -185,3 +185,138 @@ fn challenge_offer_make_offer_too_long_message() {
 
     challenge_tx(template.as_json(), Error::OfferCellMessageTooLong);
 }
+
+#[test]
+fn test_offer_make_offer_on_collection() {
+    let (mut template, total_input) = before_each();
+
+    push_output_offer_cell(
+        &mut template,
+        json!({
+            "capacity": PRICE + OFFER_PREPARED_FEE_CAPACITY,
+            "data": {
+                // Bid on any account with 4 digits.
+                "collection": {
+                    "type": OFFER_COLLECTION_TYPE_PATTERN,
+                    "args": format!("0x{:02x}{:02x}", CharSetType::Digit as u8, 4)
+                }
+            },
+            "witness": {
+                "account": "",
+                "price": PRICE,
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    push_output_balance_cell(&mut template, total_input - MAKE_OFFER_COST, BUYER);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_offer_make_offer_on_collection_with_account() {
+    let (mut template, total_input) = before_each();
+
+    push_output_offer_cell(
+        &mut template,
+        json!({
+            "capacity": PRICE + OFFER_PREPARED_FEE_CAPACITY,
+            "data": {
+                "collection": {
+                    "type": OFFER_COLLECTION_TYPE_PATTERN,
+                    "args": format!("0x{:02x}{:02x}", CharSetType::Digit as u8, 4)
+                }
+            },
+            "witness": {
+                // Simulate specifying an account when bidding on a collection of accounts.
+                "account": ACCOUNT,
+                "price": PRICE,
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    push_output_balance_cell(&mut template, total_input - MAKE_OFFER_COST, BUYER);
+
+    challenge_tx(template.as_json(), Error::OfferCellCollectionError);
+}
+
+#[test]
+fn challenge_offer_make_offer_on_undefined_collection() {
+    let (mut template, total_input) = before_each();
+
+    push_output_offer_cell(
+        &mut template,
+        json!({
+            "capacity": PRICE + OFFER_PREPARED_FEE_CAPACITY,
+            "data": {
+                "collection": {
+                    // Simulate using an undefined collection type.
+                    "type": 99,
+                    "args": "0x"
+                }
+            },
+            "witness": {
+                "account": "",
+                "price": PRICE,
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    push_output_balance_cell(&mut template, total_input - MAKE_OFFER_COST, BUYER);
+
+    challenge_tx(template.as_json(), Error::OfferCellCollectionError);
+}
+
+#[test]
+fn test_offer_make_offer_with_expired_at() {
+    let (mut template, total_input) = before_each();
+    template.push_oracle_cell(1, OracleCellType::Time, TIMESTAMP);
+
+    push_output_offer_cell(
+        &mut template,
+        json!({
+            "capacity": PRICE + OFFER_PREPARED_FEE_CAPACITY,
+            "data": {
+                "expired_at": TIMESTAMP + DAY_SEC
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": PRICE,
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    push_output_balance_cell(&mut template, total_input - MAKE_OFFER_COST, BUYER);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_offer_make_offer_expired_at_in_the_past() {
+    let (mut template, total_input) = before_each();
+    template.push_oracle_cell(1, OracleCellType::Time, TIMESTAMP);
+
+    push_output_offer_cell(
+        &mut template,
+        json!({
+            "capacity": PRICE + OFFER_PREPARED_FEE_CAPACITY,
+            "data": {
+                // Simulate creating an OfferCell which has been expired.
+                "expired_at": TIMESTAMP - 1
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": PRICE,
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    push_output_balance_cell(&mut template, total_input - MAKE_OFFER_COST, BUYER);
+
+    challenge_tx(template.as_json(), Error::OfferCellExpirationError);
+}
@@ -1,5 +1,8 @@
+mod accept_counter_offer;
 mod accept_offer;
 mod cancel_offer;
 mod common;
+mod counter_offer;
 mod edit_offer;
 mod make_offer;
+mod recycle_expired_offer_by_keeper;
@@ -0,0 +1,161 @@
+use super::common::*;
+use crate::util::{
+    accounts::*, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
+};
+use serde_json::json;
+
+fn before_each() -> TemplateGenerator {
+    let mut template = init_with_timestamp("recycle_expired_offer_by_keeper");
+
+    // inputs
+    push_input_offer_cell(
+        &mut template,
+        json!({
+            "capacity": "200_100_000_000",
+            "data": {
+                "expired_at": TIMESTAMP - 1
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": "200_000_000_000",
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    template
+}
+
+#[test]
+fn test_offer_recycle_expired_offer() {
+    let mut template = before_each();
+
+    // outputs
+    // The keeper can take the prepared fee as reward.
+    push_output_balance_cell(&mut template, 200_000_000_000, BUYER);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_offer_recycle_multiple_expired_offer() {
+    let mut template = before_each();
+
+    // inputs
+    push_input_offer_cell(
+        &mut template,
+        json!({
+            "capacity": "200_100_000_000",
+            "data": {
+                "expired_at": TIMESTAMP - 1
+            },
+            "witness": {
+                "account": "xxxxy.bit",
+                "price": "200_000_000_000",
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    // outputs
+    push_output_balance_cell(&mut template, 400_000_000_000, BUYER);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_offer_recycle_not_expired_offer() {
+    let mut template = init_with_timestamp("recycle_expired_offer_by_keeper");
+
+    // inputs
+    push_input_offer_cell(
+        &mut template,
+        json!({
+            "capacity": "200_100_000_000",
+            "data": {
+                // Simulate recycling the OfferCell which is still not expired.
+                "expired_at": TIMESTAMP + 1
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": "200_000_000_000",
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    // outputs
+    push_output_balance_cell(&mut template, 200_000_000_000, BUYER);
+
+    challenge_tx(template.as_json(), Error::OfferCellExpirationError);
+}
+
+#[test]
+fn challenge_offer_recycle_never_expired_offer() {
+    let mut template = init_with_timestamp("recycle_expired_offer_by_keeper");
+
+    // inputs
+    // Simulate recycling the OfferCell which has no expired_at.
+    push_input_offer_cell(
+        &mut template,
+        json!({
+            "capacity": "200_100_000_000",
+            "witness": {
+                "account": ACCOUNT,
+                "price": "200_000_000_000",
+                "message": "Take my money.🍀"
+            }
+        }),
+    );
+
+    // outputs
+    push_output_balance_cell(&mut template, 200_000_000_000, BUYER);
+
+    challenge_tx(template.as_json(), Error::OfferCellExpirationError);
+}
+
+#[test]
+fn challenge_offer_recycle_expired_offer_take_too_much() {
+    let mut template = before_each();
+
+    // outputs
+    // Simulate the keeper taking more than the prepared fee.
+    push_output_balance_cell(&mut template, 200_000_000_000 - 1, BUYER);
+
+    challenge_tx(template.as_json(), Error::ChangeError);
+}
+
+#[test]
+fn challenge_offer_recycle_expired_counter_offer() {
+    let mut template = init_with_timestamp("recycle_expired_offer_by_keeper");
+
+    // inputs
+    // Simulate recycling a counter offer, it locks an AccountCell, so it can only be canceled by the owner.
+    push_input_offer_cell(
+        &mut template,
+        json!({
+            "capacity": OFFER_BASIC_CAPACITY + OFFER_PREPARED_FEE_CAPACITY,
+            "lock": {
+                "owner_lock_args": SELLER,
+                "manager_lock_args": SELLER
+            },
+            "data": {
+                "expired_at": TIMESTAMP - 1,
+                "collection": {
+                    "type": OFFER_COLLECTION_TYPE_COUNTER,
+                    "args": gen_input_out_point_hex(0)
+                }
+            },
+            "witness": {
+                "account": ACCOUNT,
+                "price": "250_000_000_000",
+                "message": "Make it a bit higher."
+            }
+        }),
+    );
+
+    // outputs
+    push_output_balance_cell(&mut template, OFFER_BASIC_CAPACITY, SELLER);
+
+    challenge_tx(template.as_json(), Error::OfferCellCounterOfferError);
+}
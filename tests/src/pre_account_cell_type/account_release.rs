@@ -9,6 +9,7 @@ fn test_pre_register_shortest_registrable_account() {
     let account = "0j7p.bit";
     let mut template = init();
     template.push_config_cell_derived_by_account(account, Source::CellDep);
+    push_dep_confusable_proof_account_cell(&mut template);
 
     push_input_simple_apply_register_cell(&mut template, account);
     push_input_normal_cell(&mut template, 0, SUPER_LOCK_ARGS);
@@ -95,6 +96,7 @@ fn test_pre_register_10_chars_account() {
     let account = "1234567890.bit";
     let mut template = init();
     template.push_config_cell_derived_by_account(account, Source::CellDep);
+    push_dep_confusable_proof_account_cell(&mut template);
 
     push_input_simple_apply_register_cell(&mut template, account);
     // Simulate manually minting a three chars account with super lock.
@@ -125,6 +127,7 @@ fn test_pre_register_unreleased_account_with_super_lock() {
     let account = "g0xhlqew.bit";
     let mut template = init();
     template.push_config_cell_derived_by_account(account, Source::CellDep);
+    push_dep_confusable_proof_account_cell(&mut template);
 
     push_input_simple_apply_register_cell(&mut template, account);
     // Simulate manually minting a unreleased account with super lock.
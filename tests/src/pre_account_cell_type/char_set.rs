@@ -1,5 +1,8 @@
 use super::common::*;
-use crate::util::{self, constants::*, error::Error, template_common_cell::*, template_parser::*};
+use crate::util::{
+    self, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
+};
+use ckb_testtool::context::Context;
 use das_types_std::constants::*;
 use serde_json::json;
 
@@ -32,18 +35,9 @@ fn challenge_pre_register_invalid_char() {
     challenge_tx(template.as_json(), Error::PreRegisterAccountCharIsInvalid)
 }
 
-#[test]
-fn challenge_pre_register_unsupported_char_set() {
-    // Simulate registering an account with invalid character.
-    // ⚠️ Need to delete the emoji from char_set_emoji.txt first, otherwise the test can not pass.
-    let account = "✨das大001.bit";
-    let mut template = init();
-    template.push_config_cell_derived_by_account(account, Source::CellDep);
-
-    push_input_simple_apply_register_cell(&mut template, account);
-
+fn push_output_simple_pre_account_cell(template: &mut TemplateGenerator, account: &str) {
     push_output_pre_account_cell(
-        &mut template,
+        template,
         json!({
             "capacity": util::gen_register_fee(8, false),
             "witness": {
@@ -57,6 +51,131 @@ fn challenge_pre_register_unsupported_char_set() {
             }
         }),
     );
+}
+
+#[test]
+fn test_pre_register_zh_hans_char_set() {
+    let account = "✨das大001.bit";
+    let mut template = init();
+    template.push_config_cell(DataType::ConfigCellCharSetZhHans, Source::CellDep);
+    template.push_config_cell_derived_by_account(account, Source::CellDep);
+    push_dep_confusable_proof_account_cell(&mut template);
+
+    push_input_simple_apply_register_cell(&mut template, account);
+    push_output_simple_pre_account_cell(&mut template, account);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_pre_register_char_set_config_missing() {
+    // Simulate registering an account with a char set whose ConfigCell is not in cell_deps.
+    let account = "✨das大001.bit";
+    let mut template = init();
+    template.push_config_cell_derived_by_account(account, Source::CellDep);
+
+    push_input_simple_apply_register_cell(&mut template, account);
+    push_output_simple_pre_account_cell(&mut template, account);
+
+    challenge_tx(template.as_json(), Error::ConfigIsPartialMissing)
+}
+
+#[test]
+fn test_pre_register_account_with_many_confusable_chars() {
+    // Only the characters in the ConfigCells of the account's char sets are substituted, so 〇 is skipped here.
+    let account = "l00l00l.bit";
+    let mut template = init();
+    template.push_config_cell(DataType::ConfigCellCharSetZhHans, Source::CellDep);
+    template.push_config_cell_derived_by_account(account, Source::CellDep);
+    push_dep_confusable_proof_account_cell(&mut template);
+
+    push_input_simple_apply_register_cell(&mut template, account);
+    push_output_simple_pre_account_cell(&mut template, account);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_pre_register_confusable_account() {
+    // Simulate registering an account which is visually identical to a registered account.
+    let account = "b0b0b0b0b0.bit";
+    let mut template = init();
+    template.push_config_cell_derived_by_account(account, Source::CellDep);
+    push_dep_confusable_proof_account_cell(&mut template);
+    push_dep_account_cell(
+        &mut template,
+        json!({
+            "data": {
+                "account": "bobobobobo.bit",
+                "next": "0xffffffffffffffffffffffffffffffffffffffff"
+            },
+            "witness": {
+                "account": "bobobobobo.bit"
+            }
+        }),
+    );
+
+    push_input_simple_apply_register_cell(&mut template, account);
+    push_output_simple_pre_account_cell(&mut template, account);
+
+    challenge_tx(template.as_json(), Error::PreRegisterAccountIsConfusable)
+}
+
+#[test]
+fn challenge_pre_register_account_confusable_with_registered_variant() {
+    // Simulate registering an account without confusable characters while its variant has been registered.
+    let account = "bobobobobo.bit";
+    let mut template = init();
+    template.push_config_cell_derived_by_account(account, Source::CellDep);
+    push_dep_confusable_proof_account_cell(&mut template);
+    push_dep_account_cell(
+        &mut template,
+        json!({
+            "data": {
+                "account": "b0b0b0b0b0.bit",
+                "next": "0xffffffffffffffffffffffffffffffffffffffff"
+            },
+            "witness": {
+                "account": "b0b0b0b0b0.bit"
+            }
+        }),
+    );
+
+    push_input_simple_apply_register_cell(&mut template, account);
+    push_output_simple_pre_account_cell(&mut template, account);
+
+    challenge_tx(template.as_json(), Error::PreRegisterAccountIsConfusable)
+}
 
-    challenge_tx(template.as_json(), Error::PreRegisterFoundUndefinedCharSet)
+fn execute_pre_register_zh_hans(account: &str) -> u64 {
+    let mut template = init();
+    template.push_config_cell(DataType::ConfigCellCharSetZhHans, Source::CellDep);
+    template.push_config_cell_derived_by_account(account, Source::CellDep);
+
+    push_input_simple_apply_register_cell(&mut template, account);
+    push_output_simple_pre_account_cell(&mut template, account);
+
+    let mut parser = TemplateParser::from_data(Context::default(), template.as_json());
+    parser.parse();
+    let tx_view = parser.build_tx();
+    parser
+        .execute_tx(&tx_view)
+        .expect("Transaction verification should pass")
+}
+
+#[test]
+fn bench_pre_register_zh_hans_char_set_lookup() {
+    // The characters are at the beginning and the end of the sorted char_set_zh_hans.txt, and both accounts use the same
+    // ConfigCellPreservedAccountXX. If the characters are scanned linearly, the latter costs millions of cycles more.
+    let first_cycles = execute_pre_register_zh_hans("一丁七万丈三上下不与丐丑专且世丘丙业丛东.bit");
+    let last_cycles = execute_pre_register_zh_hans("鼎黄龄龟默鹦齿鹰齐黎黯鼠鼻鼓黑麦黔鹿龙麻.bit");
+
+    println!(
+        "Cycles of looking up the first characters: {}, the last characters: {}",
+        first_cycles, last_cycles
+    );
+    assert!(
+        last_cycles < first_cycles + 100_000,
+        "Looking up the characters in CharSet should not depend on their positions."
+    );
 }
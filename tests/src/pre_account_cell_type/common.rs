@@ -3,6 +3,7 @@ use das_types_std::constants::*;
 use serde_json::json;
 
 pub const ACCOUNT_SP_1: &str = "✨das🎉001.bit";
+pub const SPONSOR: &str = "0x0000000000000000000000000000000000009999";
 
 pub fn init() -> TemplateGenerator {
     let mut template = TemplateGenerator::new("pre_register", None);
@@ -46,6 +47,28 @@ pub fn init_for_refund() -> TemplateGenerator {
     template
 }
 
+pub fn init_for_register() -> TemplateGenerator {
+    let mut template = TemplateGenerator::new("register_account", None);
+
+    template.push_contract_cell("always_success", true);
+    template.push_contract_cell("fake-das-lock", true);
+    template.push_contract_cell("fake-secp256k1-blake160-signhash-all", true);
+    template.push_contract_cell("account-cell-type", false);
+    template.push_contract_cell("pre-account-cell-type", false);
+    template.push_contract_cell("income-cell-type", false);
+
+    template.push_oracle_cell(1, OracleCellType::Time, TIMESTAMP);
+
+    template.push_config_cell(DataType::ConfigCellAccount, Source::CellDep);
+    template.push_config_cell(DataType::ConfigCellMain, Source::CellDep);
+    template.push_config_cell(DataType::ConfigCellProfitRate, Source::CellDep);
+    template.push_config_cell(DataType::ConfigCellIncome, Source::CellDep);
+
+    push_dep_confusable_proof_account_cell(&mut template);
+
+    template
+}
+
 pub fn push_input_simple_apply_register_cell(template: &mut TemplateGenerator, account: &str) {
     push_input_apply_register_cell(
         template,
@@ -3,5 +3,7 @@ mod char_set;
 mod common;
 mod preserved_accounts;
 mod refund_pre_register;
+mod register_account;
 mod simple;
+mod sponsor;
 mod unavailable_accounts;
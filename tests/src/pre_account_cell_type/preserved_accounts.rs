@@ -36,6 +36,7 @@ fn test_pre_register_preserved_account_with_super_lock() {
     let account = "microsoft.bit";
     let mut template = init();
     template.push_config_cell_derived_by_account(account, Source::CellDep);
+    push_dep_confusable_proof_account_cell(&mut template);
 
     push_input_simple_apply_register_cell(&mut template, account);
     // Simulate manually minting a preserved account.
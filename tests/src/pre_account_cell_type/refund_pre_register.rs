@@ -1,5 +1,7 @@
 use super::common::*;
-use crate::util::{accounts::*, constants::*, error::Error, template_common_cell::*, template_parser::*};
+use crate::util::{
+    accounts::*, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
+};
 use serde_json::json;
 
 #[test]
@@ -110,6 +112,56 @@ fn test_pre_register_refund_multi_target() {
     test_tx(template.as_json())
 }
 
+fn push_input_sponsored_pre_account_cell(template: &mut TemplateGenerator) {
+    push_input_pre_account_cell(
+        template,
+        json!({
+            "capacity": 100_000_000_000u64,
+            "data": {
+                "sponsor_lock": {
+                    "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                    "args": SPONSOR
+                }
+            },
+            "witness": {
+                "account": "xxxxx.bit",
+                "created_at": TIMESTAMP - PRE_ACCOUNT_REFUND_WAITING_TIME,
+                "refund_lock": {
+                    "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                    "args": SPONSOR
+                },
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_pre_register_refund_sponsored() {
+    let mut template = init_for_refund();
+
+    // inputs
+    push_input_sponsored_pre_account_cell(&mut template);
+
+    // outputs
+    push_output_normal_cell(&mut template, 100_000_000_000, SPONSOR);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_pre_register_refund_sponsored_to_owner() {
+    let mut template = init_for_refund();
+
+    // inputs
+    push_input_sponsored_pre_account_cell(&mut template);
+
+    // outputs
+    // Simulate refunding a sponsored registration to the owner.
+    push_output_normal_cell(&mut template, 100_000_000_000, OWNER_WITHOUT_TYPE);
+
+    challenge_tx(template.as_json(), Error::InvalidTransactionStructure)
+}
+
 #[test]
 fn challenge_pre_register_cell_in_outputs() {
     let mut template = init_for_refund();
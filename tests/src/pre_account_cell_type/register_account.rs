@@ -0,0 +1,334 @@
+use super::common::*;
+use crate::util::{
+    self, constants::*, error::Error, template_common_cell::*, template_generator::TemplateGenerator,
+    template_parser::*,
+};
+use das_types_std::constants::*;
+use serde_json::json;
+
+fn push_input_prev_account_cell(template: &mut TemplateGenerator, account: &str, next: &str) {
+    push_input_account_cell(
+        template,
+        json!({
+            "capacity": util::gen_account_cell_capacity(8),
+            "lock": {
+                "owner_lock_args": "0x000000000000000000000000000000000000001111",
+                "manager_lock_args": "0x000000000000000000000000000000000000001111"
+            },
+            "data": {
+                "account": account,
+                "next": next
+            },
+            "witness": {
+                "account": account,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+}
+
+fn push_input_simple_pre_account_cell(template: &mut TemplateGenerator, created_at: u64) {
+    push_input_pre_account_cell_with_capacity(template, created_at, util::gen_register_fee(8, true));
+}
+
+fn push_input_pre_account_cell_with_capacity(template: &mut TemplateGenerator, created_at: u64, capacity: u64) {
+    push_input_pre_account_cell(
+        template,
+        json!({
+            "capacity": capacity,
+            "witness": {
+                "account": "das00005.bit",
+                "owner_lock_args": "0x05ffff00000000000000000000000000000000000505ffff000000000000000000000000000000000005",
+                "inviter_lock": {
+                    "code_hash": "{{fake-das-lock}}",
+                    "args": "0x1111000000000000000000000000000000000000"
+                },
+                "channel_lock": {
+                    "code_hash": "{{fake-das-lock}}",
+                    "args": "0x2211000000000000000000000000000000000000"
+                },
+                "created_at": created_at
+            }
+        }),
+    );
+}
+
+fn push_output_prev_account_cell(template: &mut TemplateGenerator, account: &str, next: &str) {
+    push_output_account_cell(
+        template,
+        json!({
+            "capacity": util::gen_account_cell_capacity(8),
+            "lock": {
+                "owner_lock_args": "0x000000000000000000000000000000000000001111",
+                "manager_lock_args": "0x000000000000000000000000000000000000001111"
+            },
+            "data": {
+                "account": account,
+                "next": next
+            },
+            "witness": {
+                "account": account,
+                "status": (AccountStatus::Normal as u8)
+            }
+        }),
+    );
+}
+
+fn push_output_new_account_cell(template: &mut TemplateGenerator, next: &str) {
+    push_output_account_cell(
+        template,
+        json!({
+            "capacity": util::gen_account_cell_capacity(8),
+            "lock": {
+                "owner_lock_args": "0x05ffff000000000000000000000000000000000005",
+                "manager_lock_args": "0x05ffff000000000000000000000000000000000005"
+            },
+            "data": {
+                "account": "das00005.bit",
+                "next": next,
+                "expired_at": TIMESTAMP + YEAR_SEC
+            },
+            "witness": {
+                "account": "das00005.bit",
+                "status": (AccountStatus::Normal as u8),
+                "registered_at": TIMESTAMP
+            }
+        }),
+    );
+}
+
+fn push_output_income_cell_with_profit(template: &mut TemplateGenerator) {
+    // There is no proposer, so the profit of proposing and confirming is taken by the keeper freely.
+    push_output_income_cell(
+        template,
+        json!({
+            "witness": {
+                "records": [
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-das-lock}}",
+                            "args": "0x1111000000000000000000000000000000000000"
+                        },
+                        "capacity": 38000000000u64
+                    },
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-das-lock}}",
+                            "args": "0x2211000000000000000000000000000000000000"
+                        },
+                        "capacity": 38000000000u64
+                    },
+                    {
+                        "belong_to": {
+                            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                            "args": DAS_WALLET_LOCK_ARGS
+                        },
+                        "capacity": 380000000000u64
+                    }
+                ]
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_pre_register_register_account() {
+    let mut template = init_for_register();
+
+    // inputs
+    push_input_prev_account_cell(&mut template, "das00012.bit", "das00002.bit");
+    push_input_simple_pre_account_cell(&mut template, TIMESTAMP - HOUR_SEC);
+
+    // outputs
+    push_output_prev_account_cell(&mut template, "das00012.bit", "das00005.bit");
+    push_output_new_account_cell(&mut template, "das00002.bit");
+    push_output_income_cell_with_profit(&mut template);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn test_pre_register_register_account_with_competing_cells() {
+    let mut template = init_for_register();
+
+    // cell_deps
+    // A newer PreAccountCell which wants the same account.
+    push_dep_pre_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "account": "das00005.bit",
+                "created_at": TIMESTAMP
+            }
+        }),
+    );
+    // An older PreAccountCell which has been expired, it should be ignored.
+    push_dep_pre_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "account": "das00005.bit",
+                "created_at": TIMESTAMP - DAY_SEC - HOUR_SEC
+            }
+        }),
+    );
+
+    // inputs
+    push_input_prev_account_cell(&mut template, "das00012.bit", "das00002.bit");
+    push_input_simple_pre_account_cell(&mut template, TIMESTAMP - HOUR_SEC);
+
+    // outputs
+    push_output_prev_account_cell(&mut template, "das00012.bit", "das00005.bit");
+    push_output_new_account_cell(&mut template, "das00002.bit");
+    push_output_income_cell_with_profit(&mut template);
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_pre_register_register_account_skip_earlier_pre_account_cell() {
+    let mut template = init_for_register();
+
+    // cell_deps
+    // Simulate registering the account while an older PreAccountCell wants the same account.
+    push_dep_pre_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "account": "das00005.bit",
+                "created_at": TIMESTAMP - HOUR_SEC * 2
+            }
+        }),
+    );
+
+    // inputs
+    push_input_prev_account_cell(&mut template, "das00012.bit", "das00002.bit");
+    push_input_simple_pre_account_cell(&mut template, TIMESTAMP - HOUR_SEC);
+
+    // outputs
+    push_output_prev_account_cell(&mut template, "das00012.bit", "das00005.bit");
+    push_output_new_account_cell(&mut template, "das00002.bit");
+    push_output_income_cell_with_profit(&mut template);
+
+    challenge_tx(template.as_json(), Error::PreRegisterPreAccountCellNotEarliest)
+}
+
+#[test]
+fn challenge_pre_register_register_account_not_between() {
+    let mut template = init_for_register();
+
+    // inputs
+    // Simulate the new account is not between the previous AccountCell and its next.
+    push_input_prev_account_cell(&mut template, "das00009.bit", "das00002.bit");
+    push_input_simple_pre_account_cell(&mut template, TIMESTAMP - HOUR_SEC);
+
+    // outputs
+    push_output_prev_account_cell(&mut template, "das00009.bit", "das00005.bit");
+    push_output_new_account_cell(&mut template, "das00002.bit");
+    push_output_income_cell_with_profit(&mut template);
+
+    challenge_tx(template.as_json(), Error::PreRegisterPrevAccountCellError)
+}
+
+#[test]
+fn challenge_pre_register_register_account_prev_next_not_updated() {
+    let mut template = init_for_register();
+
+    // inputs
+    push_input_prev_account_cell(&mut template, "das00012.bit", "das00002.bit");
+    push_input_simple_pre_account_cell(&mut template, TIMESTAMP - HOUR_SEC);
+
+    // outputs
+    // Simulate the previous AccountCell still links to its original next.
+    push_output_prev_account_cell(&mut template, "das00012.bit", "das00002.bit");
+    push_output_new_account_cell(&mut template, "das00002.bit");
+    push_output_income_cell_with_profit(&mut template);
+
+    challenge_tx(template.as_json(), Error::ProposalConfirmNewAccountCellDataError)
+}
+
+#[test]
+fn challenge_pre_register_register_account_new_next_error() {
+    let mut template = init_for_register();
+
+    // inputs
+    push_input_prev_account_cell(&mut template, "das00012.bit", "das00002.bit");
+    push_input_simple_pre_account_cell(&mut template, TIMESTAMP - HOUR_SEC);
+
+    // outputs
+    push_output_prev_account_cell(&mut template, "das00012.bit", "das00005.bit");
+    // Simulate the new AccountCell does not inherit the next of the previous AccountCell.
+    push_output_new_account_cell(&mut template, "das00013.bit");
+    push_output_income_cell_with_profit(&mut template);
+
+    challenge_tx(template.as_json(), Error::ProposalConfirmNewAccountCellDataError)
+}
+
+#[test]
+fn challenge_pre_register_register_account_expired() {
+    let mut template = init_for_register();
+
+    // inputs
+    push_input_prev_account_cell(&mut template, "das00012.bit", "das00002.bit");
+    // Simulate the PreAccountCell has been expired.
+    push_input_simple_pre_account_cell(&mut template, TIMESTAMP - DAY_SEC - 1);
+
+    // outputs
+    push_output_prev_account_cell(&mut template, "das00012.bit", "das00005.bit");
+    push_output_new_account_cell(&mut template, "das00002.bit");
+    push_output_income_cell_with_profit(&mut template);
+
+    challenge_tx(template.as_json(), Error::PreRegisterPreAccountCellExpired)
+}
+
+#[test]
+fn challenge_pre_register_register_account_capacity_not_enough() {
+    let mut template = init_for_register();
+
+    // inputs
+    push_input_prev_account_cell(&mut template, "das00012.bit", "das00002.bit");
+    // Simulate the PreAccountCell can not even pay for the storage of the new AccountCell.
+    push_input_pre_account_cell_with_capacity(
+        &mut template,
+        TIMESTAMP - HOUR_SEC,
+        util::gen_account_cell_capacity(8) - 1,
+    );
+
+    // outputs
+    push_output_prev_account_cell(&mut template, "das00012.bit", "das00005.bit");
+    push_output_new_account_cell(&mut template, "das00002.bit");
+    push_output_income_cell_with_profit(&mut template);
+
+    challenge_tx(template.as_json(), Error::PreRegisterCKBInsufficient)
+}
+
+#[test]
+fn challenge_pre_register_register_account_confusable() {
+    let mut template = init_for_register();
+
+    // cell_deps
+    // Simulate an account visually identical to the new account has been registered after the PreAccountCell created.
+    push_dep_account_cell(
+        &mut template,
+        json!({
+            "data": {
+                "account": "dasoooo5.bit",
+                "next": "0xffffffffffffffffffffffffffffffffffffffff"
+            },
+            "witness": {
+                "account": "dasoooo5.bit"
+            }
+        }),
+    );
+
+    // inputs
+    push_input_prev_account_cell(&mut template, "das00012.bit", "das00002.bit");
+    push_input_simple_pre_account_cell(&mut template, TIMESTAMP - HOUR_SEC);
+
+    // outputs
+    push_output_prev_account_cell(&mut template, "das00012.bit", "das00005.bit");
+    push_output_new_account_cell(&mut template, "das00002.bit");
+    push_output_income_cell_with_profit(&mut template);
+
+    challenge_tx(template.as_json(), Error::PreRegisterAccountIsConfusable)
+}
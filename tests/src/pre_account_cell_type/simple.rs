@@ -59,6 +59,7 @@ fn push_output_simple_pre_account_cell(template: &mut TemplateGenerator) {
 #[test]
 fn test_pre_register_simple() {
     let mut template = before_each();
+    push_dep_confusable_proof_account_cell(&mut template);
 
     // outputs
     push_output_simple_pre_account_cell(&mut template);
@@ -0,0 +1,154 @@
+use super::common::*;
+use crate::util::{
+    self, accounts::*, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
+};
+use das_types_std::constants::*;
+use serde_json::json;
+
+fn before_each() -> TemplateGenerator {
+    let mut template = init();
+    template.push_config_cell_derived_by_account(ACCOUNT_SP_1, Source::CellDep);
+
+    // inputs
+    push_input_apply_register_cell(
+        &mut template,
+        json!({
+            "data": {
+                "account": ACCOUNT_SP_1,
+                "height": HEIGHT - 4,
+                "timestamp": TIMESTAMP - 60,
+            }
+        }),
+    );
+
+    template
+}
+
+fn push_output_sponsored_pre_account_cell(template: &mut TemplateGenerator, refund_lock_args: &str) {
+    push_output_pre_account_cell(
+        template,
+        json!({
+            "capacity": util::gen_register_fee(8, false),
+            "data": {
+                "sponsor_lock": {
+                    "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                    "args": SPONSOR
+                }
+            },
+            "witness": {
+                "account": ACCOUNT_SP_1,
+                "created_at": TIMESTAMP,
+                "refund_lock": {
+                    "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+                    "args": refund_lock_args
+                },
+                "price": {
+                    "length": 8,
+                    "new": ACCOUNT_PRICE_5_CHAR,
+                    "renew": ACCOUNT_PRICE_5_CHAR
+                },
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_pre_register_sponsored() {
+    let mut template = before_each();
+    push_dep_confusable_proof_account_cell(&mut template);
+    push_input_normal_cell(
+        &mut template,
+        util::gen_register_fee(8, false) + 100_000_000_000,
+        SPONSOR,
+    );
+
+    // outputs
+    push_output_sponsored_pre_account_cell(&mut template, SPONSOR);
+    push_output_normal_cell(&mut template, 100_000_000_000, SPONSOR);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_pre_register_sponsored_refund_to_owner() {
+    let mut template = before_each();
+    push_input_normal_cell(&mut template, util::gen_register_fee(8, false), SPONSOR);
+
+    // outputs
+    // Simulate the refund_lock is not the sponsor.
+    push_output_sponsored_pre_account_cell(&mut template, OWNER_WITHOUT_TYPE);
+
+    challenge_tx(template.as_json(), Error::PreRegisterSponsorError)
+}
+
+#[test]
+fn challenge_pre_register_sponsored_without_sponsor_inputs() {
+    let mut template = before_each();
+    // Simulate the registration is paid by someone else instead of the sponsor.
+    push_input_normal_cell(&mut template, util::gen_register_fee(8, false), OWNER_WITHOUT_TYPE);
+
+    // outputs
+    push_output_sponsored_pre_account_cell(&mut template, SPONSOR);
+
+    challenge_tx(template.as_json(), Error::PreRegisterSponsorError)
+}
+
+#[test]
+fn challenge_pre_register_sponsored_pay_not_enough() {
+    let mut template = before_each();
+    push_input_normal_cell(
+        &mut template,
+        util::gen_register_fee(8, false) + 100_000_000_000,
+        SPONSOR,
+    );
+
+    // outputs
+    push_output_sponsored_pre_account_cell(&mut template, SPONSOR);
+    // Simulate the sponsor takes back more change than it should.
+    push_output_normal_cell(&mut template, 100_000_000_001, SPONSOR);
+
+    challenge_tx(template.as_json(), Error::PreRegisterSponsorError)
+}
+
+#[test]
+fn challenge_pre_register_sponsored_by_always_success_lock() {
+    let mut template = before_each();
+    // Simulate the sponsor_lock can be unlocked by anyone.
+    template.push_input(
+        json!({
+            "capacity": util::gen_register_fee(8, false).to_string(),
+            "lock": {
+                "code_hash": "{{always_success}}"
+            }
+        }),
+        None,
+    );
+    template.push_empty_witness();
+
+    // outputs
+    push_output_pre_account_cell(
+        &mut template,
+        json!({
+            "capacity": util::gen_register_fee(8, false),
+            "data": {
+                "sponsor_lock": {
+                    "code_hash": "{{always_success}}"
+                }
+            },
+            "witness": {
+                "account": ACCOUNT_SP_1,
+                "created_at": TIMESTAMP,
+                "refund_lock": {
+                    "code_hash": "{{always_success}}"
+                },
+                "price": {
+                    "length": 8,
+                    "new": ACCOUNT_PRICE_5_CHAR,
+                    "renew": ACCOUNT_PRICE_5_CHAR
+                },
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), Error::PreRegisterSponsorError)
+}
@@ -1,7 +1,7 @@
 use crate::util::{self, accounts::*, constants::*, template_generator::*};
 use ckb_testtool::ckb_types::prelude::Pack;
 use das_sorted_list::DasSortedList;
-use das_types_std::constants::*;
+use das_types_std::{constants::*, packed::Bytes};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 
@@ -133,7 +133,11 @@ pub fn account_to_id_bytes(account: &str) -> Vec<u8> {
 }
 
 pub fn init(action: &str) -> TemplateGenerator {
-    let mut template = TemplateGenerator::new(action, None);
+    init_with_params(action, None)
+}
+
+pub fn init_with_params(action: &str, params_opt: Option<Bytes>) -> TemplateGenerator {
+    let mut template = TemplateGenerator::new(action, params_opt);
 
     template.push_contract_cell("always_success", true);
     template.push_contract_cell("fake-das-lock", true);
@@ -150,7 +154,15 @@ pub fn init(action: &str) -> TemplateGenerator {
 }
 
 pub fn init_with_confirm() -> TemplateGenerator {
-    let mut template = init("confirm_proposal");
+    init_with_confirm_params(None)
+}
+
+pub fn init_with_confirm_slice(slice_index: u8) -> TemplateGenerator {
+    init_with_confirm_params(Some(Bytes::from(vec![slice_index])))
+}
+
+fn init_with_confirm_params(params_opt: Option<Bytes>) -> TemplateGenerator {
+    let mut template = init_with_params("confirm_proposal", params_opt);
 
     template.push_contract_cell("account-cell-type", false);
     template.push_contract_cell("pre-account-cell-type", false);
@@ -4,51 +4,81 @@ use crate::util::{
     template_parser::*,
 };
 use das_types_std::constants::*;
-use serde_json::json;
+use serde_json::{json, Value};
+
+fn gen_proposal_witness_with_slices() -> Value {
+    json!({
+        "proposer_lock": {
+            "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
+            "args": COMMON_PROPOSER
+        },
+        "created_at_height": HEIGHT - 4,
+        "slices": [
+            [
+                {
+                    "account_id": "das00012.bit",
+                    "item_type": ProposalSliceItemType::Exist as u8,
+                    "next": "das00005.bit"
+                },
+                {
+                    "account_id": "das00005.bit",
+                    "item_type": ProposalSliceItemType::New as u8,
+                    "next": "das00002.bit"
+                },
+            ],
+            [
+                {
+                    "account_id": "das00004.bit",
+                    "item_type": ProposalSliceItemType::Proposed as u8,
+                    "next": "das00018.bit"
+                },
+                {
+                    "account_id": "das00018.bit",
+                    "item_type": ProposalSliceItemType::New as u8,
+                    "next": "das00008.bit"
+                },
+                {
+                    "account_id": "das00008.bit",
+                    "item_type": ProposalSliceItemType::New as u8,
+                    "next": "das00011.bit"
+                },
+            ]
+        ]
+    })
+}
 
 fn push_input_proposal_cell_with_slices(template: &mut TemplateGenerator) {
     push_input_proposal_cell(
         template,
         json!({
             "capacity": "20_000_000_000",
-            "witness": {
-                "proposer_lock": {
-                    "code_hash": "{{fake-secp256k1-blake160-signhash-all}}",
-                    "args": COMMON_PROPOSER
-                },
-                "created_at_height": HEIGHT - 4,
-                "slices": [
-                    [
-                        {
-                            "account_id": "das00012.bit",
-                            "item_type": ProposalSliceItemType::Exist as u8,
-                            "next": "das00005.bit"
-                        },
-                        {
-                            "account_id": "das00005.bit",
-                            "item_type": ProposalSliceItemType::New as u8,
-                            "next": "das00002.bit"
-                        },
-                    ],
-                    [
-                        {
-                            "account_id": "das00004.bit",
-                            "item_type": ProposalSliceItemType::Proposed as u8,
-                            "next": "das00018.bit"
-                        },
-                        {
-                            "account_id": "das00018.bit",
-                            "item_type": ProposalSliceItemType::New as u8,
-                            "next": "das00008.bit"
-                        },
-                        {
-                            "account_id": "das00008.bit",
-                            "item_type": ProposalSliceItemType::New as u8,
-                            "next": "das00011.bit"
-                        },
-                    ]
-                ]
-            }
+            "witness": gen_proposal_witness_with_slices()
+        }),
+    );
+}
+
+fn push_input_proposal_cell_with_confirmed_slices(template: &mut TemplateGenerator, confirmed_slices: Value) {
+    push_input_proposal_cell(
+        template,
+        json!({
+            "capacity": "20_000_000_000",
+            "data": {
+                "confirmed_slices": confirmed_slices
+            },
+            "witness": gen_proposal_witness_with_slices()
+        }),
+    );
+}
+
+fn push_output_proposal_cell_with_confirmed_slices(template: &mut TemplateGenerator, confirmed_slices: Value) {
+    push_output_proposal_cell(
+        template,
+        json!({
+            "capacity": "20_000_000_000",
+            "data": {
+                "confirmed_slices": confirmed_slices
+            },
+            "witness": gen_proposal_witness_with_slices()
         }),
     );
 }
@@ -753,3 +783,166 @@ fn challenge_proposal_confirm_new_account_cell_capacity() {
 
     challenge_tx(template.as_json(), Error::ProposalConfirmNewAccountCellCapacityError);
 }
+
+fn push_output_income_cell_with_slice_0_profit(template: &mut TemplateGenerator) {
+    let lock_scripts = gen_lock_scripts();
+
+    // Carry profits of the new account in the slice 0 only.
+    push_output_income_cell(
+        template,
+        json!({
+            "witness": {
+                "records": [
+                    {
+                        "belong_to": lock_scripts.inviter_1,
+                        "capacity": 38000000000u64
+                    },
+                    {
+                        "belong_to": lock_scripts.channel_1,
+                        "capacity": 38000000000u64
+                    },
+                    {
+                        "belong_to": lock_scripts.proposer,
+                        "capacity": 19000000000u64
+                    },
+                    {
+                        "belong_to": lock_scripts.das_wallet,
+                        "capacity": 380000000000u64
+                    }
+                ]
+            }
+        }),
+    );
+}
+
+fn push_output_income_cell_with_slice_1_profit(template: &mut TemplateGenerator) {
+    let lock_scripts = gen_lock_scripts();
+
+    // Carry profits of the new accounts in the slice 1 only.
+    push_output_income_cell(
+        template,
+        json!({
+            "witness": {
+                "records": [
+                    {
+                        "belong_to": lock_scripts.inviter_2,
+                        "capacity": 38000000000u64 * 2
+                    },
+                    {
+                        "belong_to": lock_scripts.channel_2,
+                        "capacity": 38000000000u64 * 2
+                    },
+                    {
+                        "belong_to": lock_scripts.proposer,
+                        "capacity": 19000000000u64 * 2
+                    },
+                    {
+                        "belong_to": lock_scripts.das_wallet,
+                        "capacity": 380000000000u64 * 2
+                    }
+                ]
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_proposal_confirm_slice() {
+    let mut template = init_with_confirm_slice(0);
+
+    // inputs
+    push_input_proposal_cell_with_slices(&mut template);
+    push_input_slice_0(&mut template);
+
+    // outputs
+    push_output_slice_0(&mut template);
+    push_output_income_cell_with_slice_0_profit(&mut template);
+    // The ProposalCell is kept with the progress of confirmation.
+    push_output_proposal_cell_with_confirmed_slices(&mut template, json!([0]));
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_proposal_confirm_last_slice() {
+    let mut template = init_with_confirm_slice(1);
+
+    // inputs
+    push_input_proposal_cell_with_confirmed_slices(&mut template, json!([0]));
+    push_input_slice_1(&mut template);
+
+    // outputs
+    push_output_slice_1(&mut template);
+    push_output_income_cell_with_slice_1_profit(&mut template);
+    // The ProposalCell is refunded since all of its slices are confirmed.
+    push_output_normal_cell_with_refund(&mut template);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_proposal_confirm_remaining_slices() {
+    let mut template = init_with_confirm();
+
+    // inputs
+    push_input_proposal_cell_with_confirmed_slices(&mut template, json!([0]));
+    push_input_slice_1(&mut template);
+
+    // outputs
+    push_output_slice_1(&mut template);
+    push_output_income_cell_with_slice_1_profit(&mut template);
+    push_output_normal_cell_with_refund(&mut template);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_proposal_confirm_slice_confirmed() {
+    let mut template = init_with_confirm_slice(0);
+
+    // inputs
+    // Simulate confirming a slice which has been confirmed.
+    push_input_proposal_cell_with_confirmed_slices(&mut template, json!([0]));
+    push_input_slice_0(&mut template);
+
+    // outputs
+    push_output_slice_0(&mut template);
+    push_output_income_cell_with_slice_0_profit(&mut template);
+    push_output_proposal_cell_with_confirmed_slices(&mut template, json!([0]));
+
+    challenge_tx(template.as_json(), Error::ProposalSliceConfirmError);
+}
+
+#[test]
+fn challenge_proposal_confirm_slice_progress_mismatch() {
+    let mut template = init_with_confirm_slice(0);
+
+    // inputs
+    push_input_proposal_cell_with_slices(&mut template);
+    push_input_slice_0(&mut template);
+
+    // outputs
+    push_output_slice_0(&mut template);
+    push_output_income_cell_with_slice_0_profit(&mut template);
+    // Simulate marking the slice which is not confirmed as confirmed.
+    push_output_proposal_cell_with_confirmed_slices(&mut template, json!([0, 1]));
+
+    challenge_tx(template.as_json(), Error::ProposalSliceConfirmError);
+}
+
+#[test]
+fn challenge_proposal_confirm_slice_not_kept() {
+    let mut template = init_with_confirm_slice(0);
+
+    // inputs
+    push_input_proposal_cell_with_slices(&mut template);
+    push_input_slice_0(&mut template);
+
+    // outputs
+    push_output_slice_0(&mut template);
+    push_output_income_cell_with_slice_0_profit(&mut template);
+    // Simulate refunding the ProposalCell before all of its slices are confirmed.
+    push_output_normal_cell_with_refund(&mut template);
+
+    challenge_tx(template.as_json(), Error::InvalidTransactionStructure);
+}
@@ -8,11 +8,16 @@ use serde_json::json;
 
 fn before_each() -> TemplateGenerator {
     let mut template = init("propose");
+    push_common_cells(&mut template);
 
+    template
+}
+
+fn push_common_cells(template: &mut TemplateGenerator) {
     // cell_deps
     // slices[0]
     push_dep_account_cell(
-        &mut template,
+        template,
         json!({
             "data": {
                 "account": "das00012.bit",
@@ -24,7 +29,7 @@ fn before_each() -> TemplateGenerator {
         }),
     );
     push_dep_pre_account_cell(
-        &mut template,
+        template,
         json!({
             "witness": {
                 "account": "das00009.bit",
@@ -34,7 +39,7 @@ fn before_each() -> TemplateGenerator {
         }),
     );
     push_dep_pre_account_cell(
-        &mut template,
+        template,
         json!({
             "witness": {
                 "account": "das00002.bit",
@@ -45,7 +50,7 @@ fn before_each() -> TemplateGenerator {
     );
     // slices[1]
     push_dep_account_cell(
-        &mut template,
+        template,
         json!({
             "data": {
                 "account": "das00004.bit",
@@ -57,7 +62,7 @@ fn before_each() -> TemplateGenerator {
         }),
     );
     push_dep_pre_account_cell(
-        &mut template,
+        template,
         json!({
             "witness": {
                 "account": "das00018.bit",
@@ -66,20 +71,15 @@ fn before_each() -> TemplateGenerator {
             }
         }),
     );
+    push_dep_confusable_proof_account_cell(template);
 
     // inputs
-    push_input_normal_cell(&mut template, 100_000_000_000, COMMON_PROPOSER);
-
-    template
+    push_input_normal_cell(template, 100_000_000_000, COMMON_PROPOSER);
 }
 
-#[test]
-fn test_proposal_create() {
-    let mut template = before_each();
-
-    // outputs
+fn push_output_simple_proposal_cell(template: &mut TemplateGenerator) {
     push_output_proposal_cell(
-        &mut template,
+        template,
         json!({
             "witness": {
                 "slices": [
@@ -116,10 +116,123 @@ fn test_proposal_create() {
             }
         }),
     );
+}
+
+#[test]
+fn test_proposal_create() {
+    let mut template = before_each();
+
+    // outputs
+    push_output_simple_proposal_cell(&mut template);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_proposal_create_with_competing_cells() {
+    let mut template = before_each();
+
+    // cell_deps
+    // A newer PreAccountCell which wants the same account.
+    push_dep_pre_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "account": "das00009.bit",
+                "owner_lock_args": "0x050000000000000000000000000000000000005555",
+                "created_at": TIMESTAMP
+            }
+        }),
+    );
+    // An older PreAccountCell which has been expired, it should be ignored.
+    push_dep_pre_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "account": "das00018.bit",
+                "owner_lock_args": "0x050000000000000000000000000000000000006666",
+                "created_at": TIMESTAMP - DAY_SEC - HOUR_SEC
+            }
+        }),
+    );
+
+    // outputs
+    push_output_simple_proposal_cell(&mut template);
 
     test_tx(template.as_json());
 }
 
+#[test]
+fn challenge_proposal_create_skip_earlier_pre_account_cell() {
+    let mut template = before_each();
+
+    // cell_deps
+    // Simulate the proposal skips an older PreAccountCell which wants the same account.
+    push_dep_pre_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "account": "das00009.bit",
+                "owner_lock_args": "0x050000000000000000000000000000000000005555",
+                "created_at": TIMESTAMP - HOUR_SEC * 2
+            }
+        }),
+    );
+
+    // outputs
+    push_output_simple_proposal_cell(&mut template);
+
+    challenge_tx(template.as_json(), Error::ProposalPreAccountCellNotEarliest);
+}
+
+#[test]
+fn challenge_proposal_create_confusable_account() {
+    let mut template = before_each();
+
+    // cell_deps
+    // Simulate an account visually identical to das00009.bit has been registered after its PreAccountCell created.
+    push_dep_account_cell(
+        &mut template,
+        json!({
+            "data": {
+                "account": "daso0009.bit",
+                "next": "0xffffffffffffffffffffffffffffffffffffffff"
+            },
+            "witness": {
+                "account": "daso0009.bit"
+            }
+        }),
+    );
+
+    // outputs
+    push_output_simple_proposal_cell(&mut template);
+
+    challenge_tx(template.as_json(), Error::PreRegisterAccountIsConfusable);
+}
+
+#[test]
+fn challenge_proposal_create_unrelated_competing_cell() {
+    let mut template = before_each();
+
+    // cell_deps
+    // Simulate a competing PreAccountCell wants an account which is not in the proposal.
+    push_dep_pre_account_cell(
+        &mut template,
+        json!({
+            "witness": {
+                "account": "das00013.bit",
+                "owner_lock_args": "0x050000000000000000000000000000000000005555",
+                "created_at": TIMESTAMP
+            }
+        }),
+    );
+
+    // outputs
+    push_output_simple_proposal_cell(&mut template);
+
+    challenge_tx(template.as_json(), Error::ProposalSliceRelatedCellMissing);
+}
+
 #[test]
 fn test_proposal_exist_account_misunderstand() {
     let mut template = init("propose");
@@ -169,6 +282,7 @@ fn test_proposal_exist_account_misunderstand() {
             }
         }),
     );
+    push_dep_confusable_proof_account_cell(&mut template);
 
     // inputs
     push_input_normal_cell(&mut template, 100_000_000_000, COMMON_PROPOSER);
@@ -1161,3 +1275,52 @@ fn challenge_proposal_create_exist_account_4() {
 
     challenge_tx(template.as_json(), Error::ProposalSliceItemMustBeUniqueAccount)
 }
+
+#[test]
+fn challenge_proposal_create_bond_not_enough() {
+    let mut template = before_each();
+
+    // outputs
+    push_output_proposal_cell(
+        &mut template,
+        json!({
+            // Simulate the keeper do not lock enough bond in the ProposalCell.
+            "capacity": PROPOSAL_BOND_CAPACITY - 1,
+            "witness": {
+                "slices": [
+                    [
+                        {
+                            "account_id": "das00012.bit",
+                            "item_type": ProposalSliceItemType::Exist as u8,
+                            "next": "das00009.bit"
+                        },
+                        {
+                            "account_id": "das00009.bit",
+                            "item_type": ProposalSliceItemType::New as u8,
+                            "next": "das00002.bit"
+                        },
+                        {
+                            "account_id": "das00002.bit",
+                            "item_type": ProposalSliceItemType::New as u8,
+                            "next": "das00013.bit"
+                        },
+                    ],
+                    [
+                        {
+                            "account_id": "das00004.bit",
+                            "item_type": ProposalSliceItemType::Exist as u8,
+                            "next": "das00018.bit"
+                        },
+                        {
+                            "account_id": "das00018.bit",
+                            "item_type": ProposalSliceItemType::New as u8,
+                            "next": "das00011.bit"
+                        },
+                    ]
+                ]
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), Error::ProposalBondCapacityError);
+}
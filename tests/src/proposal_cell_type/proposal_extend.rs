@@ -84,6 +84,7 @@ fn before_each() -> TemplateGenerator {
             }
         }),
     );
+    push_dep_confusable_proof_account_cell(&mut template);
 
     // inputs
     push_input_normal_cell(&mut template, 100_000_000_000, COMMON_PROPOSER);
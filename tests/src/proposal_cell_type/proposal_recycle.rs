@@ -57,7 +57,10 @@ fn test_proposal_recycle() {
     let mut template = before_each(HEIGHT - 6);
 
     // outputs
-    push_output_normal_cell(&mut template, 20_000_000_000, COMMON_PROPOSER);
+    // A part of the bond is slashed to the DAS wallet, the rest is refunded to the proposer.
+    let slashed = PROPOSAL_BOND_CAPACITY / RATE_BASE * PROPOSAL_RECYCLE_SLASH_RATE;
+    push_output_normal_cell(&mut template, 20_000_000_000 - slashed - 10000, COMMON_PROPOSER);
+    push_output_normal_cell(&mut template, slashed, DAS_WALLET_LOCK_ARGS);
 
     test_tx(template.as_json());
 }
@@ -67,17 +70,45 @@ fn challenge_proposal_recycle_too_early() {
     let mut template = before_each(HEIGHT - 5);
 
     // outputs
-    push_output_normal_cell(&mut template, 20_000_000_000, COMMON_PROPOSER);
+    let slashed = PROPOSAL_BOND_CAPACITY / RATE_BASE * PROPOSAL_RECYCLE_SLASH_RATE;
+    push_output_normal_cell(&mut template, 20_000_000_000 - slashed - 10000, COMMON_PROPOSER);
+    push_output_normal_cell(&mut template, slashed, DAS_WALLET_LOCK_ARGS);
 
     challenge_tx(template.as_json(), Error::ProposalRecycleNeedWaitLonger);
 }
 
+#[test]
+fn challenge_proposal_recycle_without_slash() {
+    let mut template = before_each(HEIGHT - 6);
+
+    // outputs
+    // Simulate the bond is fully refunded without paying the slashed part to the DAS wallet.
+    push_output_normal_cell(&mut template, 20_000_000_000, COMMON_PROPOSER);
+
+    challenge_tx(template.as_json(), Error::ProposalConfirmRefundError);
+}
+
+#[test]
+fn challenge_proposal_recycle_slash_to_recycler() {
+    let mut template = before_each(HEIGHT - 6);
+
+    // outputs
+    // Simulate the slashed part is taken by the recycler, who may be the proposer with another lock.
+    let slashed = PROPOSAL_BOND_CAPACITY / RATE_BASE * PROPOSAL_RECYCLE_SLASH_RATE;
+    push_output_normal_cell(&mut template, 20_000_000_000 - slashed - 10000, COMMON_PROPOSER);
+    push_output_normal_cell(&mut template, slashed, "0x0000000000000000000000000000000000002233");
+
+    challenge_tx(template.as_json(), Error::ProposalConfirmRefundError);
+}
+
 #[test]
 fn challenge_proposal_recycle_refund_capacity() {
     let mut template = before_each(HEIGHT - 6);
 
     // outputs
-    push_output_normal_cell(&mut template, 20_000_000_000 - 10000 - 1, COMMON_PROPOSER);
+    let slashed = PROPOSAL_BOND_CAPACITY / RATE_BASE * PROPOSAL_RECYCLE_SLASH_RATE;
+    push_output_normal_cell(&mut template, 20_000_000_000 - slashed - 10000 - 1, COMMON_PROPOSER);
+    push_output_normal_cell(&mut template, slashed, DAS_WALLET_LOCK_ARGS);
 
     challenge_tx(template.as_json(), Error::ProposalConfirmRefundError);
 }
@@ -87,11 +118,42 @@ fn challenge_proposal_recycle_refund_owner() {
     let mut template = before_each(HEIGHT - 6);
 
     // outputs
+    let slashed = PROPOSAL_BOND_CAPACITY / RATE_BASE * PROPOSAL_RECYCLE_SLASH_RATE;
     push_output_normal_cell(
         &mut template,
-        20_000_000_000,
+        20_000_000_000 - slashed - 10000,
         "0x0000000000000000000000000000000000002233",
     );
+    push_output_normal_cell(&mut template, slashed, DAS_WALLET_LOCK_ARGS);
+
+    challenge_tx(template.as_json(), Error::ProposalConfirmRefundError);
+}
+
+#[test]
+fn test_proposal_recycle_by_proposer() {
+    let mut template = before_each(HEIGHT - 6);
+
+    // inputs
+    push_input_normal_cell(&mut template, 10_000_000_000, COMMON_PROPOSER);
+
+    // outputs
+    let slashed = PROPOSAL_BOND_CAPACITY / RATE_BASE * PROPOSAL_RECYCLE_SLASH_RATE;
+    push_output_normal_cell(&mut template, 30_000_000_000 - slashed - 10000, COMMON_PROPOSER);
+    push_output_normal_cell(&mut template, slashed, DAS_WALLET_LOCK_ARGS);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_proposal_recycle_by_proposer_without_slash() {
+    let mut template = before_each(HEIGHT - 6);
+
+    // inputs
+    push_input_normal_cell(&mut template, 10_000_000_000, COMMON_PROPOSER);
+
+    // outputs
+    // Simulate the proposer recycles their own ProposalCell to avoid the slash.
+    push_output_normal_cell(&mut template, 30_000_000_000 - 10000, COMMON_PROPOSER);
 
     challenge_tx(template.as_json(), Error::ProposalConfirmRefundError);
 }
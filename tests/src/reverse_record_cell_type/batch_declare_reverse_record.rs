@@ -0,0 +1,140 @@
+use super::common::*;
+use crate::util::{constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*};
+
+const SPONSOR: &str = "0x050000000000000000000000000000000000005555";
+const DECLARER_1: &str = "0x050000000000000000000000000000000000001111";
+const DECLARER_2: &str = "0x050000000000000000000000000000000000002222";
+const RECORD_CAPACITY: u64 = REVERSE_RECORD_BASIC_CAPACITY + REVERSE_RECORD_PREPARED_FEE_CAPACITY;
+const SPONSOR_INPUT: u64 = 1_000_000_000_000;
+const DECLARER_INPUT: u64 = 100_000_000_000;
+
+fn before_each() -> TemplateGenerator {
+    let mut template = init("batch_declare_reverse_record");
+
+    // inputs
+    push_input_balance_cell(&mut template, SPONSOR_INPUT, SPONSOR);
+    push_input_balance_cell(&mut template, DECLARER_INPUT, DECLARER_1);
+    push_input_balance_cell(&mut template, DECLARER_INPUT, DECLARER_2);
+
+    template
+}
+
+fn push_output_sponsor_change(template: &mut TemplateGenerator, record_count: u64) {
+    push_output_balance_cell(
+        template,
+        SPONSOR_INPUT - (RECORD_CAPACITY + REVERSE_RECORD_COMMON_FEE) * record_count,
+        SPONSOR,
+    );
+}
+
+#[test]
+fn test_reverse_record_batch_declare() {
+    let mut template = before_each();
+
+    // outputs
+    push_output_reverse_record_cell(&mut template, RECORD_CAPACITY, DECLARER_1, "xxxxx.bit");
+    push_output_reverse_record_cell(&mut template, RECORD_CAPACITY, DECLARER_2, "yyyyy.bit");
+    push_output_balance_cell(&mut template, DECLARER_INPUT, DECLARER_1);
+    push_output_balance_cell(&mut template, DECLARER_INPUT, DECLARER_2);
+    push_output_sponsor_change(&mut template, 2);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_reverse_record_batch_declare_typed_data_signed() {
+    let mut template = before_each();
+
+    // outputs
+    push_output_reverse_record_cell(&mut template, RECORD_CAPACITY, DECLARER_1, "xxxxx.bit");
+    push_output_reverse_record_cell(&mut template, RECORD_CAPACITY, DECLARER_2, "yyyyy.bit");
+    push_output_balance_cell(&mut template, DECLARER_INPUT, DECLARER_1);
+    push_output_balance_cell(&mut template, DECLARER_INPUT, DECLARER_2);
+    push_output_sponsor_change(&mut template, 2);
+
+    template.set_das_message(
+        "DECLARE REVERSE RECORDS: FROM 0x0000000000000000000000000000000000001111 TO xxxxx.bit, FROM 0x0000000000000000000000000000000000002222 TO yyyyy.bit",
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_reverse_record_batch_declare_typed_data_message_mismatched() {
+    let mut template = before_each();
+
+    // outputs
+    push_output_reverse_record_cell(&mut template, RECORD_CAPACITY, DECLARER_1, "xxxxx.bit");
+    push_output_reverse_record_cell(&mut template, RECORD_CAPACITY, DECLARER_2, "yyyyy.bit");
+    push_output_balance_cell(&mut template, DECLARER_INPUT, DECLARER_1);
+    push_output_balance_cell(&mut template, DECLARER_INPUT, DECLARER_2);
+    push_output_sponsor_change(&mut template, 2);
+
+    // Simulate signing the DAS_MESSAGE without the lock→account pairs.
+    template.set_das_message("DECLARE 2 REVERSE RECORDS");
+
+    challenge_tx(template.as_json(), Error::EIP712SignatureError)
+}
+
+#[test]
+fn challenge_reverse_record_batch_declare_without_declarer_cells() {
+    let mut template = init("batch_declare_reverse_record");
+
+    // inputs
+    push_input_balance_cell(&mut template, SPONSOR_INPUT, SPONSOR);
+    push_input_balance_cell(&mut template, DECLARER_INPUT, DECLARER_1);
+
+    // outputs
+    push_output_reverse_record_cell(&mut template, RECORD_CAPACITY, DECLARER_1, "xxxxx.bit");
+    // Simulate declaring a reverse record for a lock which does not sign the transaction.
+    push_output_reverse_record_cell(&mut template, RECORD_CAPACITY, DECLARER_2, "yyyyy.bit");
+    push_output_balance_cell(&mut template, DECLARER_INPUT, DECLARER_1);
+    push_output_sponsor_change(&mut template, 2);
+
+    challenge_tx(template.as_json(), Error::ReverseRecordCellLockError)
+}
+
+#[test]
+fn challenge_reverse_record_batch_declare_duplicate_locks() {
+    let mut template = before_each();
+
+    // outputs
+    push_output_reverse_record_cell(&mut template, RECORD_CAPACITY, DECLARER_1, "xxxxx.bit");
+    // Simulate declaring multiple reverse records for the same lock.
+    push_output_reverse_record_cell(&mut template, RECORD_CAPACITY, DECLARER_1, "yyyyy.bit");
+    push_output_balance_cell(&mut template, DECLARER_INPUT, DECLARER_1);
+    push_output_balance_cell(&mut template, DECLARER_INPUT, DECLARER_2);
+    push_output_sponsor_change(&mut template, 2);
+
+    challenge_tx(template.as_json(), Error::ReverseRecordCellLockError)
+}
+
+#[test]
+fn challenge_reverse_record_batch_declare_capacity() {
+    let mut template = before_each();
+
+    // outputs
+    push_output_reverse_record_cell(&mut template, RECORD_CAPACITY, DECLARER_1, "xxxxx.bit");
+    // Simulate the ReverseRecordCell.capacity is not satisfied the basic requirement.
+    push_output_reverse_record_cell(&mut template, RECORD_CAPACITY - 1, DECLARER_2, "yyyyy.bit");
+    push_output_balance_cell(&mut template, DECLARER_INPUT, DECLARER_1);
+    push_output_balance_cell(&mut template, DECLARER_INPUT, DECLARER_2);
+    push_output_sponsor_change(&mut template, 2);
+
+    challenge_tx(template.as_json(), Error::ReverseRecordCellCapacityError)
+}
+
+#[test]
+fn challenge_reverse_record_batch_declare_declarer_change() {
+    let mut template = before_each();
+
+    // outputs
+    push_output_reverse_record_cell(&mut template, RECORD_CAPACITY, DECLARER_1, "xxxxx.bit");
+    push_output_reverse_record_cell(&mut template, RECORD_CAPACITY, DECLARER_2, "yyyyy.bit");
+    push_output_balance_cell(&mut template, DECLARER_INPUT, DECLARER_1);
+    // Simulate the declarer pays for the transaction.
+    push_output_balance_cell(&mut template, DECLARER_INPUT - REVERSE_RECORD_COMMON_FEE, DECLARER_2);
+    push_output_sponsor_change(&mut template, 2);
+
+    challenge_tx(template.as_json(), Error::ChangeError)
+}
@@ -1,6 +1,6 @@
 use crate::util::{self, template_generator::*};
 use das_types_std::{constants::*, packed::*};
-use serde_json::json;
+use serde_json::{json, Value};
 
 pub fn init(action: &str) -> TemplateGenerator {
     let mut template = TemplateGenerator::new(action, Some(Bytes::from(vec![0])));
@@ -17,36 +17,71 @@ pub fn init(action: &str) -> TemplateGenerator {
 }
 
 pub fn push_dep_account_cell(template: &mut TemplateGenerator, account: &str) {
-    template.push_dep(
+    push_dep_account_cell_with_owner(
+        template,
+        account,
+        "0x050000000000000000000000000000000000009999",
+        json!({}),
+    );
+}
+
+pub fn push_dep_account_cell_with_owner(
+    template: &mut TemplateGenerator,
+    account: &str,
+    owner: &str,
+    cell_partial: Value,
+) {
+    let mut cell = json!({
+        "capacity": util::gen_account_cell_capacity(8),
+        "lock": {
+            "owner_lock_args": owner,
+            "manager_lock_args": owner
+        },
+        "type": {
+            "code_hash": "{{account-cell-type}}"
+        },
+        "data": {
+            "account": account,
+            "next": "yyyyy.bit",
+            "expired_at": u64::MAX,
+        },
+        "witness": {
+            "account": account,
+            "registered_at": 0,
+            "last_transfer_account_at": 0,
+            "last_edit_manager_at": 0,
+            "last_edit_records_at": 0,
+            "status": (AccountStatus::Normal as u8),
+            "records": []
+        }
+    });
+    util::merge_json(&mut cell, cell_partial);
+
+    template.push_dep(cell, Some(2));
+}
+
+pub fn push_input_reverse_record_cell(template: &mut TemplateGenerator, capacity: u64, owner: &str, account: &str) {
+    template.push_input(
         json!({
-            "capacity": util::gen_account_cell_capacity(8),
+            "capacity": capacity.to_string(),
             "lock": {
-                "owner_lock_args": "0x050000000000000000000000000000000000009999",
-                "manager_lock_args": "0x050000000000000000000000000000000000009999"
+                "owner_lock_args": owner,
+                "manager_lock_args": owner,
             },
             "type": {
-                "code_hash": "{{account-cell-type}}"
+                "code_hash": "{{reverse-record-cell-type}}"
             },
             "data": {
-                "account": account,
-                "next": "yyyyy.bit",
-                "expired_at": 0,
-            },
-            "witness": {
-                "account": account,
-                "registered_at": 0,
-                "last_transfer_account_at": 0,
-                "last_edit_manager_at": 0,
-                "last_edit_records_at": 0,
-                "status": (AccountStatus::Normal as u8)
+                "account": account
             }
         }),
-        Some(2),
+        None,
     );
+    template.push_das_lock_witness("0000000000000000000000000000000000000000000000000000000000000000");
 }
 
-pub fn push_input_reverse_record_cell(template: &mut TemplateGenerator, capacity: u64, owner: &str, account: &str) {
-    template.push_input(
+pub fn push_output_reverse_record_cell(template: &mut TemplateGenerator, capacity: u64, owner: &str, account: &str) {
+    template.push_output(
         json!({
             "capacity": capacity.to_string(),
             "lock": {
@@ -62,10 +97,14 @@ pub fn push_input_reverse_record_cell(template: &mut TemplateGenerator, capacity
         }),
         None,
     );
-    template.push_das_lock_witness("0000000000000000000000000000000000000000000000000000000000000000");
 }
 
-pub fn push_output_reverse_record_cell(template: &mut TemplateGenerator, capacity: u64, owner: &str, account: &str) {
+pub fn push_output_verified_reverse_record_cell(
+    template: &mut TemplateGenerator,
+    capacity: u64,
+    owner: &str,
+    account: &str,
+) {
     template.push_output(
         json!({
             "capacity": capacity.to_string(),
@@ -77,7 +116,8 @@ pub fn push_output_reverse_record_cell(template: &mut TemplateGenerator, capacit
                 "code_hash": "{{reverse-record-cell-type}}"
             },
             "data": {
-                "account": account
+                "account": account,
+                "verified": true
             }
         }),
         None,
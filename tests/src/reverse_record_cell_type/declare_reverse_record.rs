@@ -1,5 +1,9 @@
 use super::common::{push_dep_account_cell, *};
-use crate::util::{constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*};
+use crate::util::{
+    accounts::*, constants::*, error::Error, template_common_cell::*, template_generator::*, template_parser::*,
+};
+use das_types_std::constants::*;
+use serde_json::json;
 
 fn before_each() -> (TemplateGenerator, &'static str, &'static str, u64) {
     let mut template = init("declare_reverse_record");
@@ -224,3 +228,231 @@ fn challenge_reverse_record_declare_change_capacity() {
 
     challenge_tx(template.as_json(), Error::ChangeError)
 }
+
+fn before_each_verified() -> (TemplateGenerator, &'static str, u64) {
+    let mut template = init("declare_reverse_record");
+    let owner = "0x050000000000000000000000000000000000001111";
+
+    // cell_deps
+    template.push_oracle_cell(1, OracleCellType::Time, TIMESTAMP);
+    template.push_config_cell(DataType::ConfigCellAccount, Source::CellDep);
+
+    // inputs
+    let total_input = 100_000_000_000;
+    push_input_balance_cell(&mut template, total_input, owner);
+
+    (template, owner, total_input)
+}
+
+fn push_outputs_verified(template: &mut TemplateGenerator, owner: &str, account: &str, total_input: u64) {
+    push_output_verified_reverse_record_cell(
+        template,
+        REVERSE_RECORD_BASIC_CAPACITY + REVERSE_RECORD_PREPARED_FEE_CAPACITY,
+        owner,
+        account,
+    );
+    push_output_balance_cell(
+        template,
+        total_input - REVERSE_RECORD_BASIC_CAPACITY - REVERSE_RECORD_PREPARED_FEE_CAPACITY - REVERSE_RECORD_COMMON_FEE,
+        owner,
+    );
+}
+
+fn push_sub_account_with_owner(template: &mut TemplateGenerator, owner: &str, expired_at: u64) {
+    template.push_sub_account_witness(
+        SubAccountActionType::Insert,
+        json!({
+            "sub_account": {
+                "lock": {
+                    "owner_lock_args": owner,
+                    "manager_lock_args": owner
+                },
+                "account": SUB_ACCOUNT_1,
+                "suffix": SUB_ACCOUNT_SUFFIX,
+                "registered_at": TIMESTAMP,
+                "expired_at": expired_at,
+            }
+        }),
+    );
+
+    let current_root = template.smt_with_history.current_root();
+    template.push_dep(
+        json!({
+            "capacity": SUB_ACCOUNT_BASIC_CAPACITY + SUB_ACCOUNT_PREPARED_FEE_CAPACITY,
+            "lock": {
+                "code_hash": "{{always_success}}"
+            },
+            "type": {
+                "code_hash": "{{sub-account-cell-type}}",
+                "args": ACCOUNT
+            },
+            "data": {
+                "root": String::from("0x") + &hex::encode(&current_root)
+            }
+        }),
+        None,
+    );
+}
+
+#[test]
+fn test_reverse_record_declare_verified_by_owner() {
+    let (mut template, owner, total_input) = before_each_verified();
+
+    // cell_deps
+    push_dep_account_cell_with_owner(&mut template, ACCOUNT, owner, json!({}));
+
+    // outputs
+    push_outputs_verified(&mut template, owner, ACCOUNT, total_input);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_reverse_record_declare_verified_by_address_record() {
+    let (mut template, owner, total_input) = before_each_verified();
+
+    // cell_deps
+    // Simulate the declarer is only in the address records, which can be edited by the manager.
+    push_dep_account_cell_with_owner(
+        &mut template,
+        ACCOUNT,
+        "0x050000000000000000000000000000000000009999",
+        json!({
+            "witness": {
+                "records": [
+                    {
+                        "type": "address",
+                        "key": "eth",
+                        "label": "Personal",
+                        "value": "0x0000000000000000000000000000000000001111",
+                    }
+                ]
+            }
+        }),
+    );
+
+    // outputs
+    push_outputs_verified(&mut template, owner, ACCOUNT, total_input);
+
+    challenge_tx(template.as_json(), Error::ReverseRecordCellOwnershipError)
+}
+
+#[test]
+fn challenge_reverse_record_declare_verified_not_owner() {
+    let (mut template, owner, total_input) = before_each_verified();
+
+    // cell_deps
+    // Simulate the account is owned by someone else.
+    push_dep_account_cell_with_owner(
+        &mut template,
+        ACCOUNT,
+        "0x050000000000000000000000000000000000009999",
+        json!({}),
+    );
+
+    // outputs
+    push_outputs_verified(&mut template, owner, ACCOUNT, total_input);
+
+    challenge_tx(template.as_json(), Error::ReverseRecordCellOwnershipError)
+}
+
+#[test]
+fn challenge_reverse_record_declare_verified_without_account_cell() {
+    let (mut template, owner, total_input) = before_each_verified();
+
+    // cell_deps
+    // Simulate the AccountCell in cell_deps is not the declared account.
+    push_dep_account_cell_with_owner(&mut template, "yyyyy.bit", owner, json!({}));
+
+    // outputs
+    push_outputs_verified(&mut template, owner, ACCOUNT, total_input);
+
+    challenge_tx(template.as_json(), Error::ReverseRecordCellOwnershipError)
+}
+
+#[test]
+fn challenge_reverse_record_declare_verified_account_expired() {
+    let (mut template, owner, total_input) = before_each_verified();
+
+    // cell_deps
+    // Simulate the account has expired and passed the grace period, so the owner may not be the owner anymore.
+    push_dep_account_cell_with_owner(
+        &mut template,
+        ACCOUNT,
+        owner,
+        json!({
+            "data": {
+                "expired_at": 0
+            }
+        }),
+    );
+
+    // outputs
+    push_outputs_verified(&mut template, owner, ACCOUNT, total_input);
+
+    challenge_tx(template.as_json(), Error::AccountCellHasExpired)
+}
+
+#[test]
+fn challenge_reverse_record_declare_verified_account_not_normal() {
+    let (mut template, owner, total_input) = before_each_verified();
+
+    // cell_deps
+    // Simulate the account is on sale, so it may be transferred at any time.
+    push_dep_account_cell_with_owner(
+        &mut template,
+        ACCOUNT,
+        owner,
+        json!({
+            "witness": {
+                "status": (AccountStatus::Selling as u8)
+            }
+        }),
+    );
+
+    // outputs
+    push_outputs_verified(&mut template, owner, ACCOUNT, total_input);
+
+    challenge_tx(template.as_json(), Error::AccountCellStatusLocked)
+}
+
+#[test]
+fn test_reverse_record_declare_verified_sub_account() {
+    let (mut template, owner, total_input) = before_each_verified();
+
+    // cell_deps
+    push_sub_account_with_owner(&mut template, owner, u64::MAX);
+
+    // outputs
+    push_outputs_verified(&mut template, owner, SUB_ACCOUNT_1, total_input);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_reverse_record_declare_verified_sub_account_not_owner() {
+    let (mut template, owner, total_input) = before_each_verified();
+
+    // cell_deps
+    // Simulate the sub-account is owned by someone else.
+    push_sub_account_with_owner(&mut template, "0x050000000000000000000000000000000000009999", u64::MAX);
+
+    // outputs
+    push_outputs_verified(&mut template, owner, SUB_ACCOUNT_1, total_input);
+
+    challenge_tx(template.as_json(), Error::ReverseRecordCellOwnershipError)
+}
+
+#[test]
+fn challenge_reverse_record_declare_verified_sub_account_expired() {
+    let (mut template, owner, total_input) = before_each_verified();
+
+    // cell_deps
+    // Simulate the sub-account has expired and passed the grace period.
+    push_sub_account_with_owner(&mut template, owner, 0);
+
+    // outputs
+    push_outputs_verified(&mut template, owner, SUB_ACCOUNT_1, total_input);
+
+    challenge_tx(template.as_json(), Error::AccountCellHasExpired)
+}
@@ -1,3 +1,4 @@
+mod batch_declare_reverse_record;
 mod common;
 mod declare_reverse_record;
 mod redeclare_reverse_record;
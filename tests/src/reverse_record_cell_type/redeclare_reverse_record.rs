@@ -101,3 +101,96 @@ fn challenge_reverse_record_redeclare_capacity() {
 
     challenge_tx(template.as_json(), Error::ReverseRecordCellCapacityError)
 }
+
+#[test]
+fn test_reverse_record_redeclare_with_balance_cells() {
+    let (mut template, account, owner) = before_each();
+
+    // inputs
+    let total_input = 100_000_000_000;
+    push_input_balance_cell(&mut template, total_input, owner);
+
+    // outputs
+    push_output_reverse_record_cell(
+        &mut template,
+        REVERSE_RECORD_BASIC_CAPACITY + REVERSE_RECORD_PREPARED_FEE_CAPACITY,
+        owner,
+        account,
+    );
+    push_output_balance_cell(&mut template, total_input - REVERSE_RECORD_COMMON_FEE, owner);
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn test_reverse_record_redeclare_top_up() {
+    let mut template = init("redeclare_reverse_record");
+    let owner = "0x050000000000000000000000000000000000001111";
+
+    // inputs
+    // The ReverseRecordCell.capacity has been drained by previous redeclarations.
+    push_input_reverse_record_cell(&mut template, REVERSE_RECORD_BASIC_CAPACITY, owner, "xxxxx.bit");
+    let total_input = 100_000_000_000;
+    push_input_balance_cell(&mut template, total_input, owner);
+
+    // outputs
+    push_output_reverse_record_cell(
+        &mut template,
+        REVERSE_RECORD_BASIC_CAPACITY + REVERSE_RECORD_PREPARED_FEE_CAPACITY,
+        owner,
+        "xxxxx.bit",
+    );
+    push_output_balance_cell(
+        &mut template,
+        total_input - REVERSE_RECORD_PREPARED_FEE_CAPACITY - REVERSE_RECORD_COMMON_FEE,
+        owner,
+    );
+
+    test_tx(template.as_json());
+}
+
+#[test]
+fn challenge_reverse_record_redeclare_with_balance_cells_capacity() {
+    let (mut template, account, owner) = before_each();
+
+    // inputs
+    let total_input = 100_000_000_000;
+    push_input_balance_cell(&mut template, total_input, owner);
+
+    // outputs
+    push_output_reverse_record_cell(
+        &mut template,
+        // Simulate the ReverseRecordCell.capacity decreases while the fee is paid by BalanceCells.
+        REVERSE_RECORD_BASIC_CAPACITY + REVERSE_RECORD_PREPARED_FEE_CAPACITY - REVERSE_RECORD_COMMON_FEE,
+        owner,
+        account,
+    );
+    push_output_balance_cell(&mut template, total_input, owner);
+
+    challenge_tx(template.as_json(), Error::ReverseRecordCellCapacityError)
+}
+
+#[test]
+fn challenge_reverse_record_redeclare_with_balance_cells_change() {
+    let (mut template, account, owner) = before_each();
+
+    // inputs
+    let total_input = 100_000_000_000;
+    push_input_balance_cell(&mut template, total_input, owner);
+
+    // outputs
+    push_output_reverse_record_cell(
+        &mut template,
+        REVERSE_RECORD_BASIC_CAPACITY + REVERSE_RECORD_PREPARED_FEE_CAPACITY,
+        owner,
+        account,
+    );
+    push_output_balance_cell(
+        &mut template,
+        // Simulate transfer changes less than the user should get.
+        total_input - REVERSE_RECORD_COMMON_FEE - 1,
+        owner,
+    );
+
+    challenge_tx(template.as_json(), Error::ChangeError)
+}
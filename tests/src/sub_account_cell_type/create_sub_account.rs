@@ -128,6 +128,51 @@ fn test_sub_account_create() {
     test_tx(template.as_json())
 }
 
+fn push_simple_sub_account_witness(template: &mut TemplateGenerator) {
+    template.push_sub_account_witness(
+        SubAccountActionType::Insert,
+        json!({
+            "sub_account": {
+                "lock": {
+                    "owner_lock_args": OWNER_1,
+                    "manager_lock_args": MANAGER_1
+                },
+                "account": SUB_ACCOUNT_1,
+                "suffix": SUB_ACCOUNT_SUFFIX,
+                "registered_at": TIMESTAMP,
+                "expired_at": TIMESTAMP + YEAR_SEC,
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_sub_account_create_typed_data_signed() {
+    let mut template = before_each();
+
+    // outputs
+    push_simple_sub_account_witness(&mut template);
+    push_common_output_cells(&mut template);
+
+    template.set_das_message("CREATE SUB-ACCOUNTS 00000.xxxxx.bit(1 YEARS, 1 CKB)");
+
+    test_tx(template.as_json())
+}
+
+#[test]
+fn challenge_sub_account_create_typed_data_message_mismatched() {
+    let mut template = before_each();
+
+    // outputs
+    push_simple_sub_account_witness(&mut template);
+    push_common_output_cells(&mut template);
+
+    // Simulate signing the DAS_MESSAGE without the years and the cost of sub-accounts.
+    template.set_das_message("CREATE SUB-ACCOUNTS 00000.xxxxx.bit");
+
+    challenge_tx(template.as_json(), Error::EIP712SignatureError)
+}
+
 #[test]
 fn challenge_sub_account_create_invalid_char() {
     let mut template = before_each();
@@ -348,6 +348,52 @@ fn challenge_sub_account_edit_records_invalid_key() {
     challenge_tx(template.as_json(), Error::AccountCellRecordKeyInvalid)
 }
 
+#[test]
+fn challenge_sub_account_edit_records_invalid_evm_address() {
+    let mut template = before_each();
+
+    // outputs
+    template.push_sub_account_witness(
+        SubAccountActionType::Edit,
+        json!({
+            "sub_account": {
+                "lock": {
+                    "owner_lock_args": OWNER_1,
+                    "manager_lock_args": MANAGER_1
+                },
+                "account": SUB_ACCOUNT_1,
+                "suffix": SUB_ACCOUNT_SUFFIX,
+                "registered_at": TIMESTAMP,
+                "expired_at": u64::MAX,
+            },
+            "edit_key": "records",
+            "edit_value": [
+                {
+                    "type": "address",
+                    "key": "eth",
+                    "label": "Personal",
+                    // Simulate the EIP-55 checksum of the address is broken.
+                    "value": "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD",
+                }
+            ]
+        }),
+    );
+    let current_root = template.smt_with_history.current_root();
+    push_output_sub_account_cell(
+        &mut template,
+        json!({
+            "type": {
+                "args": ACCOUNT_1
+            },
+            "data": {
+                "root": String::from("0x") + &hex::encode(&current_root)
+            }
+        }),
+    );
+
+    challenge_tx(template.as_json(), Error::AccountCellRecordValueInvalid)
+}
+
 #[test]
 fn challenge_sub_account_profit_changed() {
     let mut template = before_each();
@@ -14,6 +14,7 @@ pub const ACCOUNT_BASIC_CAPACITY: u64 = 20_600_000_000;
 pub const ACCOUNT_PREPARED_FEE_CAPACITY: u64 = 100_000_000;
 pub const ACCOUNT_OPERATE_FEE: u64 = 10_000;
 pub const ACCOUNT_RELEASED_LENGTH: usize = 5;
+pub const ACCOUNT_STATUS_LEASED: u8 = u8::MAX;
 
 pub const ACCOUNT_PRICE_1_CHAR: u64 = 2000_000_000;
 pub const ACCOUNT_PRICE_2_CHAR: u64 = 1000_000_000;
@@ -29,7 +30,13 @@ pub const HEIGHT: u64 = 1000000u64;
 pub const PRE_ACCOUNT_REFUND_WAITING_TIME: u64 = 86400;
 pub const PRE_ACCOUNT_REFUND_AVAILABLE_FEE: u64 = 86400;
 
+pub const PROPOSAL_BOND_CAPACITY: u64 = 20_000_000_000;
+pub const PROPOSAL_RECYCLE_SLASH_RATE: u64 = 5_000;
+
 pub const INCOME_BASIC_CAPACITY: u64 = 20_000_000_000;
+pub const INCOME_PAYOUT_PLAIN: u8 = 1;
+pub const INCOME_PAYOUT_BALANCE_TYPE: u8 = 2;
+pub const INCOME_PAYOUT_DAO_DEPOSIT: u8 = 3;
 
 pub const SALE_BUYER_INVITER_PROFIT_RATE: u64 = 100;
 pub const SALE_BUYER_CHANNEL_PROFIT_RATE: u64 = 100;
@@ -40,11 +47,17 @@ pub const ACCOUNT_SALE_PREPARED_FEE_CAPACITY: u64 = 100_000_000;
 pub const OFFER_BASIC_CAPACITY: u64 = 20_000_000_000;
 pub const OFFER_PREPARED_FEE_CAPACITY: u64 = 100_000_000;
 pub const OFFER_PREPARED_MESSAGE_BYTES_LIMIT: u64 = 5000;
+pub const OFFER_COLLECTION_TYPE_PATTERN: u8 = 1;
+pub const OFFER_COLLECTION_TYPE_MERKLE_ROOT: u8 = 2;
+pub const OFFER_COLLECTION_TYPE_COUNTER: u8 = 3;
+pub const OFFER_COLLECTION_ANY_CHAR_SET: u8 = u8::MAX;
+pub const OFFER_COLLECTION_ANY_LENGTH: u8 = 0;
 pub const SECONDARY_MARKET_COMMON_FEE: u64 = 10_000;
 
 pub const REVERSE_RECORD_BASIC_CAPACITY: u64 = 20_000_000_000;
 pub const REVERSE_RECORD_PREPARED_FEE_CAPACITY: u64 = 100_000_000;
 pub const REVERSE_RECORD_COMMON_FEE: u64 = 10_000;
+pub const REVERSE_RECORD_VERIFIED: u8 = 1;
 
 pub const SUB_ACCOUNT_BASIC_CAPACITY: u64 = 20_000_000_000;
 pub const SUB_ACCOUNT_PREPARED_FEE_CAPACITY: u64 = 1_000_000_000;
@@ -7,6 +7,7 @@ pub mod smt;
 pub mod template_common_cell;
 pub mod template_generator;
 pub mod template_parser;
+pub mod typed_data;
 
 mod util;
 
@@ -178,6 +178,20 @@ pub fn push_dep_account_cell(template: &mut TemplateGenerator, cell_partial: Val
     template.push_dep(cell, Some(2));
 }
 
+/// Prove that no account which is visually identical to the registering account has been registered, it covers all
+/// account IDs, so it works for any account with confusable characters.
+pub fn push_dep_confusable_proof_account_cell(template: &mut TemplateGenerator) {
+    push_dep_account_cell(
+        template,
+        json!({
+            "data": {
+                "id": "0x0000000000000000000000000000000000000000",
+                "next": "0xffffffffffffffffffffffffffffffffffffffff"
+            }
+        }),
+    );
+}
+
 pub fn push_input_account_cell(template: &mut TemplateGenerator, cell_partial: Value) {
     let mut cell = json!({
         "capacity": util::gen_account_cell_capacity(5),
@@ -218,6 +218,15 @@ fn parse_json_hex(field_name: &str, field: &Value) -> Vec<u8> {
     }
 }
 
+/// Parse the value of records in JSON, a string is always the utf-8 bytes of the text, binary values should be written as
+/// `{ "hex": "0x..." }`.
+fn parse_json_record_value(field_name: &str, field: &Value) -> Vec<u8> {
+    match field.as_str() {
+        Some(text) => text.as_bytes().to_vec(),
+        None => parse_json_hex(&format!("{}.hex", field_name), &field["hex"]),
+    }
+}
+
 /// Parse hex string in JSON, if it is not exist return the default value.
 fn parse_json_hex_with_default(field_name: &str, field: &Value, default: Vec<u8>) -> Vec<u8> {
     if field.is_null() {
@@ -376,7 +385,7 @@ fn parse_json_str_to_account_id_mol(field_name: &str, field: &Value) -> AccountI
 ///         "type": "xxxxx",
 ///         "key": ""yyyyy,
 ///         "label": "zzzzz",
-///         "value": "0x...",
+///         "value": "text" | { "hex": "0x..." },
 ///         "ttl": null | u32
 ///     },
 ///     ...
@@ -400,8 +409,8 @@ fn parse_json_to_records_mol(field_name: &str, field: &Value) -> Records {
             .record_label(Bytes::from(
                 parse_json_str(&format!("{}[].label", field_name), &record["label"]).as_bytes(),
             ))
-            .record_value(Bytes::from(parse_json_hex(
-                "cell.witness.records[].value",
+            .record_value(Bytes::from(parse_json_record_value(
+                &format!("{}[].value", field_name),
                 &record["value"],
             )))
             .record_ttl(Uint32::from(parse_json_u32(
@@ -510,6 +519,7 @@ pub struct TemplateGenerator {
     pub preserved_account_groups: HashMap<u32, (Vec<u8>, Vec<u8>)>,
     pub charsets: HashMap<u32, (Bytes, Vec<u8>)>,
     pub smt_with_history: SMTWithHistory,
+    pub das_message: Option<String>,
 }
 
 impl TemplateGenerator {
@@ -538,9 +548,16 @@ impl TemplateGenerator {
             preserved_account_groups: HashMap::new(),
             charsets: HashMap::new(),
             smt_with_history: SMTWithHistory::new(),
+            das_message: None,
         }
     }
 
+    /// Set the expected DAS_MESSAGE, then the typed data hashes of the inputs with ETHTypedData das-lock will be signed
+    /// when the transaction is parsed.
+    pub fn set_das_message(&mut self, das_message: &str) {
+        self.das_message = Some(das_message.to_string());
+    }
+
     pub fn get_price(&self, account_length: usize) -> &PriceConfig {
         let key = if account_length > 8 { 8u8 } else { account_length as u8 };
         self.prices.get(&key).unwrap()
@@ -984,6 +1001,9 @@ impl TemplateGenerator {
                 charsets.push(key);
             }
         }
+        // The characters in CharSet are required to be sorted by their bytes without duplication.
+        charsets.sort();
+        charsets.dedup();
 
         // Join all record keys with 0x00 byte as entity.
         let mut raw = Vec::new();
@@ -1336,6 +1356,7 @@ impl TemplateGenerator {
                         push_cell!(DataType::ProposalCellData, gen_proposal_cell, version_opt, cell)
                     }
                     "reverse-record-cell-type" => push_cell!(gen_reverse_record_cell, cell),
+                    "dao" => push_cell!(gen_custom_cell, cell),
                     "test-env" => push_cell!(gen_custom_cell, cell),
                     "playground" => push_cell!(gen_custom_cell, cell),
                     _ => panic!("Unknown type ID {}", type_id),
@@ -1477,7 +1498,8 @@ impl TemplateGenerator {
     ///     },
     ///     "data": {
     ///         "hash": null | "0x...", // if this is null, will be calculated from witness.
-    ///         "id": null | "0x..." // if this is null, will be calculated from account.
+    ///         "id": null | "0x...", // if this is null, will be calculated from account.
+    ///         "sponsor_lock": Script | null // if this is null, the registration is not sponsored.
     ///     },
     ///     "witness": {
     ///         "account": "xxxxx.bit",
@@ -1569,7 +1591,14 @@ impl TemplateGenerator {
                     );
                     let account_id =
                         parse_json_hex_with_default("cell.data.id", &data["id"], util::account_to_id(account));
-                    let outputs_data = [hash, account_id].concat();
+                    let sponsor_lock = if !data["sponsor_lock"].is_null() {
+                        parse_json_script_to_mol("cell.data.sponsor_lock", &data["sponsor_lock"])
+                            .as_slice()
+                            .to_vec()
+                    } else {
+                        Vec::new()
+                    };
+                    let outputs_data = [hash, account_id, sponsor_lock].concat();
 
                     (
                         json!({
@@ -1609,8 +1638,8 @@ impl TemplateGenerator {
     ///     "type": {
     ///         "code_hash": "{{proposal-cell-type}}"
     ///     },
-    ///     "data": {
-    ///         "hash": null | "0x..." // if this is null, will be calculated from witness.
+    ///     "data": null | "0x..." | { // if this is null, will be calculated from witness.
+    ///         "confirmed_slices": [u8] // the hash of witness with a bitmap of the confirmed slices.
     ///     },
     ///     "witness": {
     ///         "proposer_lock": Script,
@@ -1679,11 +1708,19 @@ impl TemplateGenerator {
                         .created_at_height(Uint64::from(created_at_height))
                         .slices(slice_list_builder.build())
                         .build();
-                    let outputs_data = parse_json_hex_with_default(
-                        "cell.data",
-                        &cell["data"],
-                        blake2b_256(entity.as_slice()).to_vec(),
-                    );
+                    let outputs_data = if cell["data"]["confirmed_slices"].is_array() {
+                        let slices_len = entity.slices().len();
+                        let mut bitmap = vec![0u8; (slices_len + 7) / 8];
+                        for (i, item) in cell["data"]["confirmed_slices"].as_array().unwrap().iter().enumerate() {
+                            let index =
+                                parse_json_u8(&format!("cell.data.confirmed_slices[{}]", i), item, None) as usize;
+                            bitmap[index / 8] |= 1 << (index % 8);
+                        }
+
+                        [blake2b_256(entity.as_slice()).to_vec(), bitmap].concat()
+                    } else {
+                        parse_json_hex_with_default("cell.data", &cell["data"], blake2b_256(entity.as_slice()).to_vec())
+                    };
 
                     (
                         json!({
@@ -1729,7 +1766,9 @@ impl TemplateGenerator {
     ///         "id": null | "xxxxx.bit" | "0x...", // If this is null, it will be calculated from account. If this is not hex, it will be treated as account to calculate account ID.
     ///         "next": "yyyyy.bit" | "0x...", // If this is not hex, it will be be treated as account to calculate account ID.
     ///         "expired_at": u64,
-    ///         "account": "xxxxx.bit"
+    ///         "account": "xxxxx.bit",
+    ///         "lease_end": null | u64, // The time when the lease ends, it is appended to the account with a 0x00 separator.
+    ///         "records_updated_at": null | [u64] // The time when each record is updated, it is appended after the lease_end.
     ///     },
     ///     "witness": {
     ///         "id": null | "xxxxx.bit" | "0x...", // If this is null, it will be calculated from account. If this is not hex, it will be treated as account to calculate account ID.
@@ -1744,7 +1783,7 @@ impl TemplateGenerator {
     ///                 "type": "xxxxx",
     ///                 "key": ""yyyyy,
     ///                 "label": "zzzzz",
-    ///                 "value": "0x...",
+    ///                 "value": "text" | { "hex": "0x..." },
     ///                 "ttl": null | u32
     ///             }
     ///         ],
@@ -1773,6 +1812,19 @@ impl TemplateGenerator {
             };
             let next_id = parse_json_str_to_account_id("cell.data.next", &data["next"]);
             let expired_at = parse_json_u64("cell.data.expired_at", &data["expired_at"], None);
+            let tail = if !data["lease_end"].is_null() || !data["records_updated_at"].is_null() {
+                let lease_end = parse_json_u64("cell.data.lease_end", &data["lease_end"], Some(0));
+                let mut tail = [vec![0u8], lease_end.to_le_bytes().to_vec()].concat();
+                if let Some(items) = data["records_updated_at"].as_array() {
+                    for item in items {
+                        let updated_at = parse_json_u64("cell.data.records_updated_at[]", item, None);
+                        tail.extend(updated_at.to_le_bytes().iter());
+                    }
+                }
+                tail
+            } else {
+                vec![]
+            };
 
             [
                 hash,
@@ -1780,6 +1832,7 @@ impl TemplateGenerator {
                 next_id,
                 expired_at.to_le_bytes().to_vec(),
                 account.as_bytes().to_vec(),
+                tail,
             ]
             .concat()
         }
@@ -1819,38 +1872,7 @@ impl TemplateGenerator {
                 Some(0),
             ));
             let status = Uint8::from(parse_json_u8("cell.witness.status", &witness["status"], Some(0)));
-            // TODO Find the correct way to handle the return type of &Vec.
-            let tmp = Vec::new();
-            let mut records = &tmp;
-            if !witness["records"].is_null() {
-                records = parse_json_array("cell.witness.records", &witness["records"])
-            };
-
-            let mut records_builder = Records::new_builder();
-            for (_i, record) in records.iter().enumerate() {
-                let ttl = if !record["ttl"].is_null() {
-                    Uint32::from(parse_json_u32("cell.witness.records[].ttl", &record["ttl"], Some(300)))
-                } else {
-                    Uint32::from(300)
-                };
-                let record = Record::new_builder()
-                    .record_type(Bytes::from(
-                        parse_json_str("cell.witness.records[].type", &record["type"]).as_bytes(),
-                    ))
-                    .record_key(Bytes::from(
-                        parse_json_str("cell.witness.records[].key", &record["key"]).as_bytes(),
-                    ))
-                    .record_label(Bytes::from(
-                        parse_json_str("cell.witness.records[].label", &record["label"]).as_bytes(),
-                    ))
-                    .record_value(Bytes::from(parse_json_hex(
-                        "cell.witness.records[].value",
-                        &record["value"],
-                    )))
-                    .record_ttl(ttl)
-                    .build();
-                records_builder = records_builder.push(record);
-            }
+            let records = parse_json_to_records_mol("cell.witness.records", &witness["records"]);
 
             match version {
                 2 => {
@@ -1862,7 +1884,7 @@ impl TemplateGenerator {
                         .last_edit_manager_at(last_edit_manager_at)
                         .last_edit_records_at(last_edit_records_at)
                         .status(status)
-                        .records(records_builder.build())
+                        .records(records)
                         .build();
                     let outputs_data = gen_outputs_data(&cell, Some(&entity));
 
@@ -1897,7 +1919,7 @@ impl TemplateGenerator {
                         .last_edit_manager_at(last_edit_manager_at)
                         .last_edit_records_at(last_edit_records_at)
                         .status(status)
-                        .records(records_builder.build())
+                        .records(records)
                         .enable_sub_account(enable_sub_account)
                         .renew_sub_account_price(renew_sub_account_price)
                         .build();
@@ -2013,11 +2035,17 @@ impl TemplateGenerator {
                         .started_at(started_at)
                         .buyer_inviter_profit_rate(buyer_inviter_profit_rate)
                         .build();
-                    let outputs_data = parse_json_hex_with_default(
-                        "cell.data",
-                        &cell["data"],
-                        blake2b_256(entity.as_slice()).to_vec(),
-                    );
+                    let outputs_data = if cell["data"]["bundle"].is_array() {
+                        // The accounts sold together are appended to the hash of witness by their account IDs.
+                        let mut outputs_data = blake2b_256(entity.as_slice()).to_vec();
+                        for item in cell["data"]["bundle"].as_array().unwrap() {
+                            let account = item.as_str().expect("cell.data.bundle[] should be account strings");
+                            outputs_data.extend_from_slice(&util::account_to_id(account));
+                        }
+                        outputs_data
+                    } else {
+                        parse_json_hex_with_default("cell.data", &cell["data"], blake2b_256(entity.as_slice()).to_vec())
+                    };
 
                     (
                         json!({
@@ -2058,7 +2086,9 @@ impl TemplateGenerator {
     ///     "type": {
     ///         "code_hash": "{{income-cell-type}}"
     ///     },
-    ///     "data": null | "0x...", // if this is null, it will be calculated from witness.
+    ///     "data": null | "0x..." | { // if this is null, it will be calculated from witness.
+    ///         "payouts": null | [u8, ...] // one payout for each record, see IncomePayoutType
+    ///     },
     ///     "witness": {
     ///         "creator": null | Script, // if this is null, it will be filled with Script::default().
     ///         "records": [
@@ -2109,11 +2139,18 @@ impl TemplateGenerator {
                         .creator(creator)
                         .records(records_builder.build())
                         .build();
-                    let outputs_data = parse_json_hex_with_default(
-                        "cell.data",
-                        &cell["data"],
-                        blake2b_256(entity.as_slice()).to_vec(),
-                    );
+                    let outputs_data = if cell["data"].is_object() {
+                        // The payouts of records are appended to the hash of witness.
+                        let mut outputs_data = blake2b_256(entity.as_slice()).to_vec();
+                        if let Some(payouts) = cell["data"]["payouts"].as_array() {
+                            for (i, item) in payouts.iter().enumerate() {
+                                outputs_data.push(parse_json_u8(&format!("cell.data.payouts[{}]", i), item, None));
+                            }
+                        }
+                        outputs_data
+                    } else {
+                        parse_json_hex_with_default("cell.data", &cell["data"], blake2b_256(entity.as_slice()).to_vec())
+                    };
 
                     (
                         json!({
@@ -2156,7 +2193,8 @@ impl TemplateGenerator {
     ///         "code_hash": "{{reverse-record-cell-type}}"
     ///     },
     ///     "data": {
-    ///         "account": null | "xxxx.bit" // It is possible to create an invalid cell without account.
+    ///         "account": null | "xxxx.bit", // It is possible to create an invalid cell without account.
+    ///         "verified": null | bool // If this is true, the verified flag will be appended after the account.
     ///     }
     /// })
     /// ```
@@ -2169,7 +2207,11 @@ impl TemplateGenerator {
             String::from("")
         } else {
             let account = parse_json_str("cell.data.account", &cell["data"]["account"]);
-            util::bytes_to_hex(account.as_bytes())
+            let mut data = account.as_bytes().to_vec();
+            if cell["data"]["verified"].as_bool().unwrap_or(false) {
+                data.extend(&[0u8, REVERSE_RECORD_VERIFIED]);
+            }
+            util::bytes_to_hex(&data)
         };
 
         (
@@ -2228,11 +2270,21 @@ impl TemplateGenerator {
                         .inviter_lock(inviter_lock)
                         .channel_lock(channel_lock)
                         .build();
-                    let outputs_data = parse_json_hex_with_default(
-                        "cell.data",
-                        &cell["data"],
-                        blake2b_256(entity.as_slice()).to_vec(),
-                    );
+                    let outputs_data = if cell["data"].is_object() {
+                        // The expired_at and the collection of accounts are appended to the hash of witness.
+                        let data = &cell["data"];
+                        let mut outputs_data = blake2b_256(entity.as_slice()).to_vec();
+                        let expired_at = parse_json_u64("cell.data.expired_at", &data["expired_at"], Some(0));
+                        outputs_data.extend(expired_at.to_le_bytes());
+                        if data["collection"].is_object() {
+                            let collection = &data["collection"];
+                            outputs_data.push(parse_json_u8("cell.data.collection.type", &collection["type"], None));
+                            outputs_data.extend(parse_json_hex("cell.data.collection.args", &collection["args"]));
+                        }
+                        outputs_data
+                    } else {
+                        parse_json_hex_with_default("cell.data", &cell["data"], blake2b_256(entity.as_slice()).to_vec())
+                    };
                     (
                         json!({
                           "tmp_type": "full",
@@ -2445,7 +2497,7 @@ impl TemplateGenerator {
     ///                 "type": "xxxxx",
     ///                 "key": ""yyyyy,
     ///                 "label": "zzzzz",
-    ///                 "value": "0x...",
+    ///                 "value": "text" | { "hex": "0x..." },
     ///                 "ttl": null | u32
     ///             },
     ///             ...
@@ -2626,6 +2678,7 @@ impl TemplateGenerator {
             "inputs": self.inputs,
             "outputs": self.outputs,
             "witnesses": witnesses,
+            "das_message": self.das_message,
         })
     }
 
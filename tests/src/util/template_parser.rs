@@ -1,4 +1,4 @@
-use super::{constants::*, error::Error, util};
+use super::{constants::*, error::Error, typed_data, util};
 use crate::util::template_generator::TemplateGenerator;
 use ckb_testtool::{
     ckb_error, ckb_jsonrpc_types as rpc_types,
@@ -194,6 +194,9 @@ impl TemplateParser {
         if let Some(witnesses) = self.data["witnesses"].as_array().map(to_owned) {
             self.parse_witnesses(witnesses)?
         }
+        if let Some(das_message) = self.data["das_message"].as_str().map(|v| v.to_owned()) {
+            typed_data::sign_typed_data(self, &das_message)?
+        }
 
         Ok(())
     }
@@ -0,0 +1,501 @@
+use super::{constants::*, template_parser::TemplateParser, util};
+use ckb_testtool::{
+    ckb_hash::new_blake2b,
+    ckb_types::{bytes::Bytes, core::TransactionView, packed::*, prelude::*},
+};
+use das_types_std::{
+    constants::{DataType, Source},
+    packed as das_packed,
+    prelude::Entity as DasEntity,
+};
+use eip712::{eip712::Value, hash_data, typed_data_v4};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryInto,
+    error::Error as StdError,
+};
+
+// The following constants and functions mirror the typed data generation in das-core/src/eip712.rs, keep them consistent
+// when the contracts change.
+
+const DAS_LOCK_TYPE_ETH_TYPED_DATA: u8 = 5;
+const CKB_HASH_DIGEST: usize = 32;
+const EIP712_CHAIN_ID_SIZE: usize = 8;
+const DATA_OMIT_SIZE: usize = 20;
+const PARAM_OMIT_SIZE: usize = 10;
+
+// The code_hash of secp256k1_blake160_multisig_all in dev environment.
+const MULTISIG_DEV_CODE_HASH: &str = "0x4b099301d800e502332f9e4d01ad427e07e6e1c799a68329843ac473e8321848";
+
+/// Sign the typed data of the transaction like a wallet does with `eth_signTypedData_v4`.
+///
+/// The inputs with das-lock whose type byte is ETHTypedData are grouped by their lock args, then the hash of the typed
+/// data is filled into the lock of the first witness of each group. The secp256k1 signature is left empty, because it
+/// is verified by das-lock instead of the type scripts.
+pub fn sign_typed_data(parser: &mut TemplateParser, das_message: &str) -> Result<(), Box<dyn StdError>> {
+    let tx = parser.build_tx();
+    let (action, params) = parse_action(parser)?;
+    let input_groups = group_inputs(parser, &tx, &action)?;
+    if input_groups.is_empty() {
+        return Ok(());
+    }
+
+    let mut chain_id = Vec::new();
+    let mut digests = Vec::new();
+    for (_args, idxs) in input_groups.iter() {
+        let lock = load_witness_lock(parser, idxs[0])?;
+        if lock.len() != SECP_SIGNATURE_SIZE + CKB_HASH_DIGEST + EIP712_CHAIN_ID_SIZE {
+            return Err(format!("SignErr: The lock of witnesses[{}] has invalid length.", idxs[0]).into());
+        }
+        if chain_id.is_empty() {
+            let from = SECP_SIGNATURE_SIZE + CKB_HASH_DIGEST;
+            chain_id = lock[from..(from + EIP712_CHAIN_ID_SIZE)].to_vec();
+        }
+
+        digests.push((idxs[0], tx_to_digest(parser, &tx, idxs)));
+    }
+
+    let mut typed_data = tx_to_typed_data(parser, &tx, &action, &params, chain_id, das_message)?;
+    for (index, digest) in digests {
+        typed_data.digest(hex::encode(&digest));
+        let hash = hash_data(&typed_data).map_err(|err| format!("SignErr: Hash typed data failed: {:?}", err))?;
+
+        let mut lock = load_witness_lock(parser, index)?;
+        lock[SECP_SIGNATURE_SIZE..(SECP_SIGNATURE_SIZE + CKB_HASH_DIGEST)].copy_from_slice(&hash);
+        let witness = WitnessArgs::new_builder().lock(Some(Bytes::from(lock)).pack()).build();
+        parser.set_witnesses(index, witness.as_bytes().pack());
+    }
+
+    Ok(())
+}
+
+fn parse_action(parser: &TemplateParser) -> Result<(String, Vec<Vec<u8>>), Box<dyn StdError>> {
+    let raw = parser
+        .witnesses
+        .iter()
+        .map(|witness| witness.raw_data())
+        .find(|raw| raw.starts_with(b"das"))
+        .ok_or("SignErr: The ActionData witness is missing.")?;
+    let action_data = das_packed::ActionData::from_slice(&raw[7..])?;
+    let action = String::from_utf8(action_data.action().raw_data().to_vec())?;
+    let bytes = action_data.params().raw_data().to_vec();
+
+    let params = match action.as_str() {
+        "buy_account" => {
+            let length_of_inviter_lock = u32::from_le_bytes(bytes[..4].try_into().unwrap()) as usize;
+            let from = length_of_inviter_lock;
+            let length_of_channel_lock = u32::from_le_bytes(bytes[from..(from + 4)].try_into().unwrap()) as usize;
+            let to = from + length_of_channel_lock;
+
+            vec![bytes[..from].to_vec(), bytes[from..to].to_vec(), bytes[to..].to_vec()]
+        }
+        "lease_account" => vec![bytes[..8].to_vec(), bytes[8..].to_vec()],
+        "accept_offer" if bytes.len() > 1 => {
+            vec![bytes[..(bytes.len() - 1)].to_vec(), bytes[(bytes.len() - 1)..].to_vec()]
+        }
+        _ if bytes.is_empty() => Vec::new(),
+        _ => vec![bytes],
+    };
+
+    Ok((action, params))
+}
+
+fn group_inputs(
+    parser: &TemplateParser,
+    tx: &TransactionView,
+    action: &str,
+) -> Result<BTreeMap<Vec<u8>, Vec<usize>>, Box<dyn StdError>> {
+    let das_lock_code_hash = util::hex_to_byte32(TYPE_ID_TABLE.get("fake-das-lock").unwrap())?;
+    let account_cell_type_id = parser.contracts.get("account-cell-type").unwrap();
+    let inputs = load_input_cells(parser, tx);
+
+    // Skip the inputs which do not require signature, see verify_eip712_hashes for details.
+    let start = match action {
+        "buy_account" => {
+            inputs
+                .iter()
+                .filter(|(cell, _)| {
+                    cell.type_()
+                        .to_opt()
+                        .map(|type_| &type_.code_hash() == account_cell_type_id)
+                        .unwrap_or(false)
+                })
+                .count()
+                + 1
+        }
+        "accept_offer" => 1,
+        "accept_counter_offer" => 2,
+        _ => 0,
+    };
+    let is_manager_required = action == "edit_records";
+
+    let mut groups: BTreeMap<Vec<u8>, Vec<usize>> = BTreeMap::new();
+    for (i, (cell, _)) in inputs.iter().enumerate().skip(start) {
+        let lock = cell.lock();
+        if lock.code_hash() != das_lock_code_hash || lock.hash_type().as_slice() != [ScriptHashType::Type as u8] {
+            continue;
+        }
+
+        let args = lock.args().raw_data().to_vec();
+        let type_byte = if is_manager_required {
+            match args[0] {
+                1 => args[29],
+                6 => args[33],
+                _ => args[21],
+            }
+        } else {
+            args[0]
+        };
+        if type_byte == DAS_LOCK_TYPE_ETH_TYPED_DATA {
+            groups.entry(args).or_default().push(i);
+        }
+    }
+
+    Ok(groups)
+}
+
+fn load_input_cells(parser: &TemplateParser, tx: &TransactionView) -> Vec<(CellOutput, Bytes)> {
+    tx.inputs()
+        .into_iter()
+        .map(|input| {
+            parser
+                .context
+                .get_cell(&input.previous_output())
+                .expect("The cells in inputs should exist.")
+        })
+        .collect()
+}
+
+fn load_witness_lock(parser: &TemplateParser, index: usize) -> Result<Vec<u8>, Box<dyn StdError>> {
+    let witness = parser
+        .witnesses
+        .get(index)
+        .ok_or(format!("SignErr: The witnesses[{}] is missing.", index))?;
+    let witness_args = WitnessArgs::from_slice(&witness.raw_data())?;
+    let lock = witness_args
+        .lock()
+        .to_opt()
+        .ok_or(format!("SignErr: The lock of witnesses[{}] is empty.", index))?;
+
+    Ok(lock.raw_data().to_vec())
+}
+
+fn tx_to_digest(parser: &TemplateParser, tx: &TransactionView, idxs: &[usize]) -> [u8; 32] {
+    // The digest is calculated with a lock which contains only an empty signature.
+    let empty_witness = WitnessArgs::new_builder()
+        .lock(Some(Bytes::from(vec![0u8; SECP_SIGNATURE_SIZE])).pack())
+        .build();
+
+    let mut blake2b = new_blake2b();
+    blake2b.update(&tx.hash().raw_data());
+    blake2b.update(&(empty_witness.as_bytes().len() as u64).to_le_bytes());
+    blake2b.update(&empty_witness.as_bytes());
+    let other_idxs = idxs
+        .iter()
+        .skip(1)
+        .cloned()
+        .chain(tx.inputs().len()..parser.witnesses.len());
+    for i in other_idxs {
+        let witness = parser.witnesses[i].raw_data();
+        blake2b.update(&(witness.len() as u64).to_le_bytes());
+        blake2b.update(&witness);
+    }
+
+    let mut digest = [0u8; 32];
+    blake2b.finalize(&mut digest);
+    digest
+}
+
+fn tx_to_typed_data(
+    parser: &TemplateParser,
+    tx: &TransactionView,
+    action: &str,
+    params: &[Vec<u8>],
+    chain_id: Vec<u8>,
+    das_message: &str,
+) -> Result<eip712::eip712::TypedDataV4, Box<dyn StdError>> {
+    let inputs = load_input_cells(parser, tx);
+    let outputs: Vec<(CellOutput, Bytes)> = tx.outputs_with_data_iter().collect();
+
+    let tx_action = to_typed_action(action, params);
+    let (inputs_capacity, inputs) = to_typed_cells(parser, &inputs, Source::Input)?;
+    let (outputs_capacity, outputs) = to_typed_cells(parser, &outputs, Source::Output)?;
+    let inputs_capacity_str = to_semantic_capacity(inputs_capacity);
+    let outputs_capacity_str = to_semantic_capacity(outputs_capacity);
+
+    let fee_str = if outputs_capacity <= inputs_capacity {
+        to_semantic_capacity(inputs_capacity - outputs_capacity)
+    } else {
+        format!("-{}", to_semantic_capacity(outputs_capacity - inputs_capacity))
+    };
+
+    let chain_id_num = u64::from_be_bytes(chain_id.try_into().unwrap()).to_string();
+    let typed_data = typed_data_v4!({
+        types: {
+            EIP712Domain: {
+                chainId: "uint256",
+                name: "string",
+                verifyingContract: "address",
+                version: "string"
+            },
+            Action: {
+                action: "string",
+                params: "string"
+            },
+            Cell: {
+                capacity: "string",
+                lock: "string",
+                type: "string",
+                data: "string",
+                extraData: "string"
+            },
+            Transaction: {
+                DAS_MESSAGE: "string",
+                inputsCapacity: "string",
+                outputsCapacity: "string",
+                fee: "string",
+                action: "Action",
+                inputs: "Cell[]",
+                outputs: "Cell[]",
+                digest: "bytes32"
+            }
+        },
+        primaryType: "Transaction",
+        domain: {
+            chainId: chain_id_num,
+            name: "da.systems",
+            verifyingContract: "0x0000000000000000000000000000000020210722",
+            version: "1"
+        },
+        message: {
+            DAS_MESSAGE: das_message,
+            inputsCapacity: inputs_capacity_str,
+            outputsCapacity: outputs_capacity_str,
+            fee: fee_str,
+            action: tx_action,
+            inputs: inputs,
+            outputs: outputs,
+            digest: ""
+        }
+    });
+
+    Ok(typed_data)
+}
+
+fn to_typed_action(action: &str, params: &[Vec<u8>]) -> Value {
+    let params = params
+        .iter()
+        .map(|param| {
+            if param.len() > PARAM_OMIT_SIZE {
+                format!("0x{}...", hex::encode(&param[..PARAM_OMIT_SIZE]))
+            } else {
+                format!("0x{}", hex::encode(param))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    typed_data_v4!(@object {
+        action: action,
+        params: params.join(",")
+    })
+}
+
+fn to_typed_cells(
+    parser: &TemplateParser,
+    cells: &[(CellOutput, Bytes)],
+    source: Source,
+) -> Result<(u64, Value), Box<dyn StdError>> {
+    let names = contract_names(parser);
+    let balance_cell_type_id = parser.contracts.get("balance-cell-type").unwrap();
+    let account_cell_type_id = parser.contracts.get("account-cell-type").unwrap();
+
+    let mut total_capacity = 0;
+    let mut typed_cells = Vec::new();
+    for (i, (cell, data)) in cells.iter().enumerate() {
+        let capacity_in_shannon: u64 = cell.capacity().unpack();
+        total_capacity += capacity_in_shannon;
+
+        // Skip NormalCells which has no type script and BalanceCells which has the type script named balance-cell-type.
+        let type_script = match cell.type_().to_opt() {
+            Some(type_script) if &type_script.code_hash() != balance_cell_type_id => type_script,
+            _ => continue,
+        };
+
+        let capacity = to_semantic_capacity(capacity_in_shannon);
+        let lock = to_typed_script(&names, true, &cell.lock());
+        let type_ = to_typed_script(&names, false, &type_script);
+        let (data, extra_data) = if &type_script.code_hash() == account_cell_type_id {
+            (
+                to_semantic_account_cell_data(data)?,
+                to_semantic_account_witness(parser, &data[..32], i, source)?,
+            )
+        } else {
+            (to_typed_common_data(data), String::new())
+        };
+
+        typed_cells.push(typed_data_v4!(@object {
+            capacity: capacity,
+            lock: lock,
+            type: type_,
+            data: data,
+            extraData: extra_data
+        }));
+    }
+
+    Ok((total_capacity, Value::Array(typed_cells)))
+}
+
+fn contract_names(parser: &TemplateParser) -> HashMap<Byte32, (bool, &'static str)> {
+    let mut names = HashMap::new();
+
+    // CAREFUL The names of secp256k1 locks are the same as the contracts.
+    let lock_names = [
+        ("always_success", "always-success"),
+        ("fake-das-lock", "das-lock"),
+        ("fake-secp256k1-blake160-signhash-all", "account-cell-type"),
+    ];
+    for (key, name) in lock_names.iter() {
+        names.insert(
+            util::hex_to_byte32(TYPE_ID_TABLE.get(key).unwrap()).unwrap(),
+            (true, *name),
+        );
+    }
+    names.insert(
+        util::hex_to_byte32(MULTISIG_DEV_CODE_HASH).unwrap(),
+        (true, "account-sale-cell-type"),
+    );
+
+    let type_names = [
+        "apply-register-cell-type",
+        "account-cell-type",
+        "account-sale-cell-type",
+        "account-auction-cell-type",
+        "balance-cell-type",
+        "config-cell-type",
+        "income-cell-type",
+        "offer-cell-type",
+        "pre-account-cell-type",
+        "proposal-cell-type",
+        "reverse-record-cell-type",
+        "sub-account-cell-type",
+    ];
+    for name in type_names.iter() {
+        if let Some(type_id) = parser.contracts.get(*name) {
+            names.insert(type_id.to_owned(), (false, *name));
+        }
+    }
+
+    names
+}
+
+fn to_typed_script(names: &HashMap<Byte32, (bool, &'static str)>, is_lock: bool, script: &Script) -> String {
+    let code_hash = match names.get(&script.code_hash()) {
+        Some((is_lock_name, name))
+            if *is_lock_name == is_lock && script.hash_type().as_slice() == [ScriptHashType::Type as u8] =>
+        {
+            name.to_string()
+        }
+        _ => format!("0x{}...", hex::encode(&script.code_hash().raw_data()[..DATA_OMIT_SIZE])),
+    };
+
+    let hash_type = hex::encode(script.hash_type().as_slice());
+    let args_in_bytes = script.args().raw_data();
+    let args = if args_in_bytes.len() > DATA_OMIT_SIZE {
+        hex::encode(&args_in_bytes[..DATA_OMIT_SIZE]) + "..."
+    } else {
+        hex::encode(&args_in_bytes)
+    };
+
+    format!("{},0x{},0x{}", code_hash, hash_type, args)
+}
+
+fn to_typed_common_data(data: &[u8]) -> String {
+    if data.len() > DATA_OMIT_SIZE {
+        format!("0x{}...", hex::encode(&data[..DATA_OMIT_SIZE]))
+    } else if !data.is_empty() {
+        format!("0x{}", hex::encode(data))
+    } else {
+        String::new()
+    }
+}
+
+fn to_semantic_account_cell_data(data: &[u8]) -> Result<String, Box<dyn StdError>> {
+    let start = 32 + ACCOUNT_ID_LENGTH * 2;
+    let expired_at = u64::from_le_bytes(data[start..(start + 8)].try_into().unwrap());
+    let account = String::from_utf8(data[(start + 8)..].to_vec())?;
+
+    Ok(format!("{{ account: {}, expired_at: {} }}", account, expired_at))
+}
+
+fn to_semantic_account_witness(
+    parser: &TemplateParser,
+    expected_hash: &[u8],
+    index: usize,
+    source: Source,
+) -> Result<String, Box<dyn StdError>> {
+    for witness in parser.witnesses.iter() {
+        let raw = witness.raw_data();
+        if !raw.starts_with(b"das") || raw.len() < 7 {
+            continue;
+        }
+        let data_type = u32::from_le_bytes(raw[3..7].try_into().unwrap());
+        if data_type != DataType::AccountCellData as u32 {
+            continue;
+        }
+
+        let data = das_packed::Data::from_slice(&raw[7..])?;
+        let entity_opt = match source {
+            Source::Input => data.old().to_opt(),
+            _ => data.new().to_opt(),
+        };
+        let entity = match entity_opt {
+            Some(entity) if u32::from(entity.index()) as usize == index => entity,
+            _ => continue,
+        };
+
+        let entity_bytes = entity.entity().raw_data();
+        if ckb_testtool::ckb_hash::blake2b_256(&entity_bytes)[..] != expected_hash[..] {
+            return Err(format!(
+                "SignErr: The hash of AccountCellData at {:?}[{}] is mismatched.",
+                source, index
+            )
+            .into());
+        }
+
+        let (status, records) = if u32::from(entity.version()) == 2 {
+            let witness = das_packed::AccountCellDataV2::from_slice(&entity_bytes)?;
+            (u8::from(witness.status()), witness.records())
+        } else {
+            let witness = das_packed::AccountCellData::from_slice(&entity_bytes)?;
+            (u8::from(witness.status()), witness.records())
+        };
+        let records_hash = ckb_testtool::ckb_hash::blake2b_256(records.as_slice());
+
+        return Ok(format!(
+            "{{ status: {}, records_hash: 0x{} }}",
+            status,
+            hex::encode(&records_hash)
+        ));
+    }
+
+    Err(format!("SignErr: The AccountCellData of {:?}[{}] is missing.", source, index).into())
+}
+
+pub fn to_semantic_capacity(capacity: u64) -> String {
+    let capacity_str = capacity.to_string();
+    let length = capacity_str.len();
+    if length > 8 {
+        let integer = &capacity_str[0..length - 8];
+        let decimal = capacity_str[length - 8..length].trim_end_matches("0");
+        if decimal.is_empty() {
+            format!("{} CKB", integer)
+        } else {
+            format!("{}.{} CKB", integer, decimal)
+        }
+    } else if capacity == 0 {
+        String::from("0 CKB")
+    } else {
+        let padded_str = format!("{:0>8}", capacity_str);
+        format!("0.{} CKB", padded_str.trim_end_matches("0"))
+    }
+}